@@ -0,0 +1,52 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+use geo::{LineString, Polygon};
+use geo_utility::geometry::convex_hull::{deduplicate_polygons, deduplicate_polygons_parallel};
+
+// Builds `count` hulls where every polygon occurs twice
+fn create_hulls(count: usize) -> Vec<Polygon> {
+    (0..count)
+        .map(|i| {
+            let offset = (i % (count / 2)) as f64 * 0.001;
+            let ring = vec![
+                (offset, offset),
+                (offset + 1.0, offset),
+                (offset + 1.5, offset + 0.5),
+                (offset + 1.0, offset + 1.0),
+                (offset, offset + 1.0),
+                (offset, offset),
+            ];
+            Polygon::new(LineString::from(ring), vec![])
+        })
+        .collect()
+}
+
+fn bench_100k_hulls(c: &mut Criterion) {
+    let hulls_100k = create_hulls(100_000);
+
+    c.bench_function("deduplicate_polygons_100k_hulls", |b| {
+        b.iter_with_setup(
+            || hulls_100k.clone(), // Clone the data here
+            |input_data| {
+                black_box(deduplicate_polygons(input_data));
+            },
+        )
+    });
+
+    c.bench_function("deduplicate_polygons_parallel_100k_hulls", |b| {
+        b.iter_with_setup(
+            || hulls_100k.clone(), // Clone the data here
+            |input_data| {
+                black_box(deduplicate_polygons_parallel(input_data));
+            },
+        )
+    });
+}
+
+criterion_group!(
+    name = benches_100k;
+    config = Criterion::default().sample_size(10);
+    targets = bench_100k_hulls
+);
+
+criterion_main!(benches_100k);
@@ -1,7 +1,12 @@
-use geojson::{Feature, FeatureCollection, Geometry, JsonObject, Value};
+use geojson::{
+    Bbox, Feature, FeatureCollection, Geometry, JsonObject, JsonValue, Value, feature::Id,
+};
 use rstar::{PointDistance, RTree, RTreeObject};
 use std::convert::TryInto;
-use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::utils::error::Error;
+use crate::utils::helpers::union_bboxes;
 
 // Define the struct to be stored in the R-tree
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -207,20 +212,20 @@ pub fn concatenate_features(collection: &FeatureCollection) -> FeatureCollection
                         if endpoint_ref.is_start {
                             // Merge: Append potential_coords to current_merged_coords
                             // Optional: Remove duplicate point if current_end_point == potential_coords[0]
-                            if let Some(potential_start_point) = potential_points.first() {
-                                if current_end_point == *potential_start_point {
-                                    current_merged_coords.pop_back(); // Remove duplicate end point
-                                }
+                            if let Some(potential_start_point) = potential_points.first()
+                                && current_end_point == *potential_start_point
+                            {
+                                current_merged_coords.pop_back(); // Remove duplicate end point
                             }
                              current_merged_coords.extend(potential_points);
                         } else {
                             // This endpoint is the END of the potential feature
                             // Merge: Append potential_coords (reversed) to current_merged_coords
                             // Optional: Remove duplicate point if current_end_point == potential_coords.last()
-                            if let Some(potential_end_point) = potential_points.last() {
-                                if current_end_point == *potential_end_point {
-                                    current_merged_coords.pop_back(); // Remove duplicate end point
-                                }
+                            if let Some(potential_end_point) = potential_points.last()
+                                && current_end_point == *potential_end_point
+                            {
+                                current_merged_coords.pop_back(); // Remove duplicate end point
                             }
                             current_merged_coords.extend(potential_points.into_iter().rev());
                         }
@@ -267,10 +272,10 @@ pub fn concatenate_features(collection: &FeatureCollection) -> FeatureCollection
                         if endpoint_ref.is_start {
                             // Merge: Prepend potential_coords (reversed) to current_merged_coords
                             // Optional: Remove duplicate point if current_start_point == potential_coords[0]
-                            if let Some(potential_start_point) = potential_points.first() {
-                                if current_start_point == *potential_start_point {
-                                    current_merged_coords.pop_front(); // Remove duplicate start point
-                                }
+                            if let Some(potential_start_point) = potential_points.first()
+                                && current_start_point == *potential_start_point
+                            {
+                                current_merged_coords.pop_front(); // Remove duplicate start point
                             }
                             for point in potential_points.into_iter().rev() {
                                 current_merged_coords.push_front(point);
@@ -279,10 +284,10 @@ pub fn concatenate_features(collection: &FeatureCollection) -> FeatureCollection
                             // This endpoint is the END of the potential feature
                             // Merge: Prepend potential_points to current_merged_coords
                             // Optional: Remove duplicate point if current_start_point == potential_points.last()
-                            if let Some(potential_end_point) = potential_points.last() {
-                                if current_start_point == *potential_end_point {
-                                    current_merged_coords.pop_front(); // Remove duplicate start point
-                                }
+                            if let Some(potential_end_point) = potential_points.last()
+                                && current_start_point == *potential_end_point
+                            {
+                                current_merged_coords.pop_front(); // Remove duplicate start point
                             }
                             // Prepend points
                             for point in potential_points.into_iter().rev() { // Iterate in reverse to push_front in correct order
@@ -334,11 +339,173 @@ pub fn concatenate_features(collection: &FeatureCollection) -> FeatureCollection
     }
 }
 
+/// Property that `concatenate_with_source` stores the source name in.
+pub const SOURCE_PROPERTY: &str = "__source";
+
+/// Concatenates the features of several named collections, recording where each
+/// output feature came from.
+///
+/// Every input feature gets a `__source` property holding the name of its
+/// collection before all features are concatenated with `concatenate_features`.
+/// A merged LineString keeps the properties, and so the source, of the line it
+/// was started from.
+pub fn concatenate_with_source(collections: &[(String, FeatureCollection)]) -> FeatureCollection {
+    let features = collections
+        .iter()
+        .flat_map(|(source, collection)| {
+            collection.features.iter().map(move |feature| {
+                let mut feature = feature.clone();
+                feature
+                    .properties
+                    .get_or_insert_with(JsonObject::new)
+                    .insert(SOURCE_PROPERTY.to_string(), JsonValue::from(source.as_str()));
+                feature
+            })
+        })
+        .collect();
+
+    concatenate_features(&FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    })
+}
+
+/// Options controlling how `concatenate_collections` combines the collection-level
+/// members of its inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ConcatOptions {
+    /// Merge the foreign members of all inputs, a later input winning on key conflicts.
+    pub merge_foreign_members: bool,
+    /// Set the bbox to the union of the input bboxes. The result has no bbox
+    /// unless every input has one and all of them have the same dimension.
+    pub compute_bbox: bool,
+}
+
+/// Concatenates the features of several collections with `concatenate_features`.
+///
+/// The collection-level foreign members and bbox are combined as set in
+/// `options`, and left empty otherwise.
+pub fn concatenate_collections(
+    collections: &[&FeatureCollection],
+    options: &ConcatOptions,
+) -> FeatureCollection {
+    let features = collections
+        .iter()
+        .flat_map(|collection| collection.features.iter().cloned())
+        .collect();
+    let mut concatenated = concatenate_features(&FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    });
+
+    if options.merge_foreign_members {
+        concatenated.foreign_members = collections
+            .iter()
+            .filter_map(|collection| collection.foreign_members.as_ref())
+            .fold(None, |merged: Option<JsonObject>, members| {
+                let mut merged = merged.unwrap_or_default();
+                merged.extend(members.iter().map(|(k, v)| (k.clone(), v.clone())));
+                Some(merged)
+            });
+    }
+    if options.compute_bbox && !collections.is_empty() {
+        let bboxes: Option<Vec<&Bbox>> = collections
+            .iter()
+            .map(|collection| collection.bbox.as_ref())
+            .collect();
+        concatenated.bbox = bboxes
+            .filter(|bboxes| bboxes.iter().all(|bbox| bbox.len() == bboxes[0].len()))
+            .and_then(|bboxes| union_bboxes(bboxes.iter().copied(), bboxes[0].len() / 2));
+    }
+    concatenated
+}
+
+/// What `concatenate_features_dedup` does with features whose id appeared before.
+///
+/// String and numeric ids are compared by their string form. Features
+/// without an id are never duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupStrategy {
+    /// Keep the first feature with an id and drop later ones.
+    #[default]
+    KeepFirst,
+    /// Keep the last feature with an id and drop earlier ones.
+    KeepLast,
+    /// Fail with `Error::DuplicateFeatureId`.
+    Error,
+}
+
+/// Concatenates the features of several collections, keeping one feature per id.
+///
+/// The input features are deduplicated by `feature.id` according to `strategy`,
+/// keeping the surviving features in input order, and then concatenated with
+/// `concatenate_features`.
+///
+/// # Returns
+/// The concatenated collection, or `Error::DuplicateFeatureId` for the first
+/// repeated id under `DedupStrategy::Error`.
+pub fn concatenate_features_dedup(
+    collections: &[&FeatureCollection],
+    strategy: DedupStrategy,
+) -> Result<FeatureCollection, Error> {
+    let features: Vec<&Feature> = collections
+        .iter()
+        .flat_map(|collection| collection.features.iter())
+        .collect();
+    let id_key = |feature: &Feature| {
+        feature.id.as_ref().map(|id| match id {
+            Id::String(id) => id.clone(),
+            Id::Number(id) => id.to_string(),
+        })
+    };
+
+    let kept: Vec<Feature> = match strategy {
+        DedupStrategy::KeepFirst | DedupStrategy::Error => {
+            let mut seen = HashSet::new();
+            let mut kept = Vec::with_capacity(features.len());
+            for feature in features {
+                if let Some(id) = id_key(feature)
+                    && !seen.insert(id.clone())
+                {
+                    if strategy == DedupStrategy::Error {
+                        return Err(Error::DuplicateFeatureId(id));
+                    }
+                    continue;
+                }
+                kept.push(feature.clone());
+            }
+            kept
+        }
+        DedupStrategy::KeepLast => {
+            let last_index: HashMap<String, usize> = features
+                .iter()
+                .enumerate()
+                .filter_map(|(index, feature)| Some((id_key(feature)?, index)))
+                .collect();
+            features
+                .iter()
+                .enumerate()
+                .filter(|(index, feature)| {
+                    id_key(feature).is_none_or(|id| last_index[&id] == *index)
+                })
+                .map(|(_, feature)| (*feature).clone())
+                .collect()
+        }
+    };
+
+    Ok(concatenate_features(&FeatureCollection {
+        bbox: None,
+        features: kept,
+        foreign_members: None,
+    }))
+}
+
 // --- Test Suite ---
 #[cfg(test)]
 mod tests {
     use super::*; // Import items from the outer scope
-    use geojson::JsonValue;
 
     // Helper function to create a simple LineString feature
     fn create_line_string_feature(
@@ -605,4 +772,180 @@ mod tests {
             "Points in R-tree should match expected endpoints"
         );
     }
+
+    #[test]
+    fn test_concatenate_with_source_tags_features() {
+        let tile_a = FeatureCollection {
+            bbox: None,
+            features: vec![
+                create_line_string_feature(vec![vec![0.0, 0.0], vec![1.0, 1.0]], None),
+                create_point_feature(vec![10.0, 10.0], None),
+            ],
+            foreign_members: None,
+        };
+        let tile_b = FeatureCollection {
+            bbox: None,
+            features: vec![create_nogeometry_feature(None)],
+            foreign_members: None,
+        };
+
+        let result = concatenate_with_source(&[
+            ("tile_a".to_string(), tile_a),
+            ("tile_b".to_string(), tile_b),
+        ]);
+
+        let sources: Vec<&str> = result
+            .features
+            .iter()
+            .map(|f| f.property(SOURCE_PROPERTY).and_then(|v| v.as_str()).unwrap())
+            .collect();
+        assert_eq!(sources, vec!["tile_a", "tile_a", "tile_b"]);
+    }
+
+    #[test]
+    fn test_concatenate_with_source_keeps_existing_properties() {
+        let mut feature = create_point_feature(vec![1.0, 1.0], None);
+        feature.set_property("name", "mast");
+        let collection = FeatureCollection {
+            bbox: None,
+            features: vec![feature],
+            foreign_members: None,
+        };
+
+        let result = concatenate_with_source(&[("tile".to_string(), collection)]);
+
+        assert_eq!(result.features[0].property("name"), Some(&JsonValue::from("mast")));
+        assert_eq!(
+            result.features[0].property(SOURCE_PROPERTY),
+            Some(&JsonValue::from("tile"))
+        );
+        assert!(concatenate_with_source(&[]).features.is_empty());
+    }
+
+    fn feature_with_id(id: Option<Id>, name: &str) -> Feature {
+        let mut feature = create_point_feature(vec![1.0, 1.0], None);
+        feature.id = id;
+        feature.set_property("name", name);
+        feature
+    }
+
+    fn dedup_inputs() -> (FeatureCollection, FeatureCollection) {
+        let first = FeatureCollection {
+            bbox: None,
+            features: vec![
+                feature_with_id(Some(Id::String("a".to_string())), "a1"),
+                feature_with_id(None, "anonymous1"),
+                feature_with_id(Some(Id::Number(7.into())), "seven1"),
+            ],
+            foreign_members: None,
+        };
+        let second = FeatureCollection {
+            bbox: None,
+            features: vec![
+                feature_with_id(Some(Id::String("7".to_string())), "seven2"),
+                feature_with_id(None, "anonymous2"),
+                feature_with_id(Some(Id::String("a".to_string())), "a2"),
+                feature_with_id(Some(Id::String("b".to_string())), "b1"),
+            ],
+            foreign_members: None,
+        };
+        (first, second)
+    }
+
+    fn names(collection: &FeatureCollection) -> Vec<&str> {
+        collection
+            .features
+            .iter()
+            .map(|f| f.property("name").and_then(|v| v.as_str()).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_concatenate_features_dedup_keep_first() {
+        let (first, second) = dedup_inputs();
+
+        let result = concatenate_features_dedup(&[&first, &second], DedupStrategy::KeepFirst);
+
+        let result = result.unwrap();
+        assert_eq!(names(&result), vec!["a1", "anonymous1", "seven1", "anonymous2", "b1"]);
+    }
+
+    #[test]
+    fn test_concatenate_features_dedup_keep_last() {
+        let (first, second) = dedup_inputs();
+
+        let result = concatenate_features_dedup(&[&first, &second], DedupStrategy::KeepLast);
+
+        let result = result.unwrap();
+        assert_eq!(names(&result), vec!["anonymous1", "seven2", "anonymous2", "a2", "b1"]);
+    }
+
+    #[test]
+    fn test_concatenate_features_dedup_error() {
+        let (first, second) = dedup_inputs();
+
+        let result = concatenate_features_dedup(&[&first, &second], DedupStrategy::Error);
+        assert!(matches!(result, Err(Error::DuplicateFeatureId(id)) if id == "7"));
+
+        // Without overlapping ids every feature is kept
+        let result = concatenate_features_dedup(&[&first], DedupStrategy::Error).unwrap();
+        assert_eq!(names(&result), vec!["a1", "anonymous1", "seven1"]);
+    }
+
+    fn collection_with(bbox: Option<Bbox>, foreign_members: JsonValue) -> FeatureCollection {
+        FeatureCollection {
+            bbox,
+            features: vec![create_point_feature(vec![1.0, 1.0], None)],
+            foreign_members: foreign_members.as_object().cloned(),
+        }
+    }
+
+    #[test]
+    fn test_concatenate_collections_merges_foreign_members() {
+        let first = collection_with(None, serde_json::json!({ "source": "a", "version": 1 }));
+        let second =
+            collection_with(None, serde_json::json!({ "source": "b", "crs": "EPSG:4326" }));
+        let options = ConcatOptions {
+            merge_foreign_members: true,
+            ..Default::default()
+        };
+
+        let result = concatenate_collections(&[&first, &second], &options);
+
+        assert_eq!(result.features.len(), 2);
+        assert_eq!(
+            result.foreign_members,
+            serde_json::json!({ "source": "b", "version": 1, "crs": "EPSG:4326" })
+                .as_object()
+                .cloned()
+        );
+        // Off by default
+        let result = concatenate_collections(&[&first, &second], &ConcatOptions::default());
+        assert_eq!(result.foreign_members, None);
+        assert_eq!(result.bbox, None);
+    }
+
+    #[test]
+    fn test_concatenate_collections_unions_bboxes() {
+        let options = ConcatOptions {
+            compute_bbox: true,
+            ..Default::default()
+        };
+        let first = collection_with(Some(vec![0.0, 0.0, 2.0, 2.0]), JsonValue::Null);
+        let second = collection_with(Some(vec![1.0, -1.0, 3.0, 1.0]), JsonValue::Null);
+
+        let result = concatenate_collections(&[&first, &second], &options);
+        assert_eq!(result.bbox, Some(vec![0.0, -1.0, 3.0, 2.0]));
+        assert_eq!(result.foreign_members, None);
+
+        let first_3d = collection_with(Some(vec![0.0, 0.0, 5.0, 2.0, 2.0, 6.0]), JsonValue::Null);
+        let second_3d = collection_with(Some(vec![1.0, 1.0, 4.0, 3.0, 3.0, 5.0]), JsonValue::Null);
+        let result = concatenate_collections(&[&first_3d, &second_3d], &options);
+        assert_eq!(result.bbox, Some(vec![0.0, 0.0, 4.0, 3.0, 3.0, 6.0]));
+
+        // A missing or differently sized bbox leaves the result without one
+        let unbounded = collection_with(None, JsonValue::Null);
+        assert_eq!(concatenate_collections(&[&first, &unbounded], &options).bbox, None);
+        assert_eq!(concatenate_collections(&[&first, &first_3d], &options).bbox, None);
+    }
 }
@@ -1,30 +1,35 @@
-use geo::{Geometry as GeoGeometry, Point};
-use geojson::{Feature, FeatureCollection, GeoJson, Geometry, feature::Id};
+use geo::{
+    Area, BoundingRect, ConvexHull, GeodesicArea, Geometry as GeoGeometry, LineString,
+    MinimumRotatedRect, MultiPolygon, Point, Polygon, Rect, Validation,
+};
+use geojson::{Bbox, Feature, FeatureCollection, GeoJson, Geometry, feature::Id};
 use serde_json::{Map, Value, from_str};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::sync::OnceLock;
 use crate::utils::error::Error;
+use crate::utils::helpers::{
+    CLOSED_RING_EPSILON, InBoundingBox, is_closed_eps, snap_ring_closed, union_bboxes,
+};
 
-// --- Macro for Into<Feature> ---
+// --- Macro for From<&Entity> for Feature ---
 macro_rules! impl_into_feature_for_point_entity {
     ($struct_name:ident) => {
-        impl Into<Feature> for &$struct_name {
+        impl From<&$struct_name> for Feature {
             /// Helper macro to convert a DomainEntity variant to a GeoJSON feature.
             ///
             /// # Arguments
             ///
-            /// * `self` - The DomainEntity variant to convert.
+            /// * `val` - The DomainEntity variant to convert.
             ///
             /// # Returns
             ///
             /// * `Feature` - The converted GeoJSON feature.
-            fn into(self) -> Feature {
-                let geometry = Geometry::from(&self.geometry).clone();
-                let mut properties = self.original_inner_properties.clone();
-
-                // Example: Add objectId back to output properties if present in original inner
-                if let Some(Value::String(obj_id)) = self.original_inner_properties.get("objectId")
-                {
-                    properties.insert("objectId".to_string(), Value::String(obj_id.clone()));
-                }
+            fn from(val: &$struct_name) -> Feature {
+                let geometry = Geometry::from(&val.geometry).clone();
+                let properties = nested_properties(
+                    &val.original_outer_properties,
+                    &val.original_inner_properties,
+                );
                 // // Example: Add CapturedMarker's specific field if it exists
                 //  if let Some(marker) = self.as_captured_marker() { // Requires as_captured_marker helper on DomainEntity
                 //      properties.insert("object_id_name".to_string(), Value::String(marker.object_id_name.clone()));
@@ -33,9 +38,36 @@ macro_rules! impl_into_feature_for_point_entity {
                 Feature {
                     geometry: Some(geometry),
                     properties: Some(properties),
-                    bbox: None,
-                    id: Some(Id::String(self.id.clone())),
-                    foreign_members: None, // You might need to store and forward these
+                    bbox: rect_to_bbox(Some(val.geometry.bounding_rect())),
+                    id: val.id.clone().into(),
+                    foreign_members: val.foreign_members.clone(),
+                }
+            }
+        }
+    };
+}
+
+// --- Macro for TryFrom<&Feature> for Entity ---
+macro_rules! impl_try_from_feature_for_entity {
+    ($struct_name:ident) => {
+        impl TryFrom<&Feature> for $struct_name {
+            type Error = Error;
+            /// Helper macro to parse a GeoJSON feature into a specific domain struct.
+            ///
+            /// # Arguments
+            ///
+            /// * `feature` - The feature to parse.
+            ///
+            /// # Returns
+            ///
+            /// * `Result<Self, Error>` - The parsed struct, or
+            ///   `Error::InvalidObjectId` if the feature is a different entity.
+            fn try_from(feature: &Feature) -> Result<Self, Self::Error> {
+                match DomainEntity::try_from(feature)? {
+                    DomainEntity::$struct_name(entity) => Ok(entity),
+                    other => Err(Error::InvalidObjectId(
+                        other.object_id().unwrap_or_default().to_string(),
+                    )),
                 }
             }
         }
@@ -49,47 +81,158 @@ impl_into_feature_for_point_entity!(OperationSite);
 impl_into_feature_for_point_entity!(DrillingPoint);
 impl_into_feature_for_point_entity!(CableTunnel);
 
+impl_try_from_feature_for_entity!(CapturedMarker);
+impl_try_from_feature_for_entity!(SupplyPoint);
+impl_try_from_feature_for_entity!(OperationSite);
+impl_try_from_feature_for_entity!(DrillingPoint);
+impl_try_from_feature_for_entity!(CableTunnel);
+impl_try_from_feature_for_entity!(Building);
+impl_try_from_feature_for_entity!(CableRoute);
+impl_try_from_feature_for_entity!(Cable);
+
+/// The id of a domain entity, keeping the type of the feature id.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum EntityId {
+    String(String),
+    Number(serde_json::Number),
+    /// The feature has no id.
+    Missing,
+}
+
+impl std::fmt::Display for EntityId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EntityId::String(id) => write!(f, "{}", id),
+            EntityId::Number(id) => write!(f, "{}", id),
+            EntityId::Missing => write!(f, "No ID"),
+        }
+    }
+}
+
+impl From<Id> for EntityId {
+    fn from(id: Id) -> Self {
+        match id {
+            Id::String(id) => EntityId::String(id),
+            Id::Number(id) => EntityId::Number(id),
+        }
+    }
+}
+
+impl From<Option<Id>> for EntityId {
+    fn from(id: Option<Id>) -> Self {
+        id.map_or(EntityId::Missing, EntityId::from)
+    }
+}
+
+impl From<&str> for EntityId {
+    fn from(id: &str) -> Self {
+        EntityId::String(id.to_string())
+    }
+}
+
+impl From<String> for EntityId {
+    fn from(id: String) -> Self {
+        EntityId::String(id)
+    }
+}
+
+impl From<EntityId> for Option<Id> {
+    /// Converts back to a feature id; `EntityId::Missing` becomes `None`.
+    fn from(id: EntityId) -> Self {
+        match id {
+            EntityId::String(id) => Some(Id::String(id)),
+            EntityId::Number(id) => Some(Id::Number(id)),
+            EntityId::Missing => None,
+        }
+    }
+}
+
 // --- Example Domain Structs ---
 #[derive(Debug, Clone)]
 pub struct CapturedMarker {
-    pub id: String,                                    // From feature.id
+    pub id: EntityId,                                  // From feature.id
     pub geometry: Point,                               // Assuming Markers are always Points
     pub original_inner_properties: Map<String, Value>, // Store original inner map
+    pub original_outer_properties: Map<String, Value>, // Outer properties except the inner map
+    pub foreign_members: Option<Map<String, Value>>,   // Forwarded to the output feature
 }
 
 #[derive(Debug, Clone)]
 pub struct SupplyPoint {
-    pub id: String,
+    pub id: EntityId,
     pub geometry: Point, // Assuming Versorgungspunkte are Points (if they appear in your data)
     pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct OperationSite {
-    pub id: String,
+    pub id: EntityId,
     pub geometry: Point, // Assuming Betriebsstellen are Points (if they appear in your data)
     pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct DrillingPoint {
-    pub id: String,
+    pub id: EntityId,
     pub geometry: Point, // Assuming Bohrpunkte are Points (if they appear in your data)
     pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct CableTunnel {
-    pub id: String,
+    pub id: EntityId,
     pub geometry: Point, // Assuming Kabelschachte are Points (if they appear in your data)
     pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
 }
 
 #[derive(Debug, Clone)]
 pub struct Building {
-    pub id: String,
+    pub id: EntityId,
     pub geometry: GeoGeometry, // Assuming Kabelschachte are Points (if they appear in your data)
     pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CableRoute {
+    pub id: EntityId,
+    pub geometry: LineString, // Kabeltrassen are open or closed LineStrings
+    pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Cable {
+    pub id: EntityId,
+    pub geometry: LineString, // Kabel are LineStrings
+    pub original_inner_properties: Map<String, Value>,
+    pub original_outer_properties: Map<String, Value>,
+    pub foreign_members: Option<Map<String, Value>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct UnknownEntity {
+    pub id: EntityId,     // From feature.id
+    pub feature: Feature, // The feature that could not be identified
+}
+
+impl From<Feature> for UnknownEntity {
+    fn from(feature: Feature) -> Self {
+        UnknownEntity {
+            id: EntityId::from(feature.id.clone()),
+            feature,
+        }
+    }
 }
 
 // This impl should NOT use the macro defined for Point entities
@@ -98,25 +241,83 @@ impl From<&Building> for Feature {
         // Convert the geo::Geometry to geojson::Geometry
         let geometry = Geometry::from(&val.geometry).clone(); // Convert GeoGeometry
 
-        // Clone the original inner properties
-        let mut properties = val.original_inner_properties.clone();
-
-        // Add objectId back to output properties if present in original inner (optional)
-        if let Some(Value::String(obj_id)) = val.original_inner_properties.get("objectId") {
-            properties.insert("objectId".to_string(), Value::String(obj_id.clone()));
-        }
+        // Nest the original inner properties under the outer ones again
+        let properties =
+            nested_properties(&val.original_outer_properties, &val.original_inner_properties);
         // Add Building-specific fields to output properties if needed
 
         Feature {
             geometry: Some(geometry),
             properties: Some(properties),
-            bbox: None, // Calculate this if needed
-            id: Some(Id::String(val.id.clone())),
-            foreign_members: None, // Forward foreign members if your structs held them
+            bbox: rect_to_bbox(val.geometry.bounding_rect()),
+            id: val.id.clone().into(),
+            foreign_members: val.foreign_members.clone(),
         }
     }
 }
 
+// --- Macro for From<&Entity> for Feature of LineString entities ---
+macro_rules! impl_into_feature_for_line_entity {
+    ($struct_name:ident) => {
+        impl From<&$struct_name> for Feature {
+            fn from(val: &$struct_name) -> Feature {
+                let geometry = Geometry::from(&val.geometry);
+                let properties = nested_properties(
+                    &val.original_outer_properties,
+                    &val.original_inner_properties,
+                );
+
+                Feature {
+                    geometry: Some(geometry),
+                    properties: Some(properties),
+                    bbox: rect_to_bbox(val.geometry.bounding_rect()),
+                    id: val.id.clone().into(),
+                    foreign_members: val.foreign_members.clone(),
+                }
+            }
+        }
+    };
+}
+
+impl_into_feature_for_line_entity!(CableRoute);
+impl_into_feature_for_line_entity!(Cable);
+
+/// Helper function to build the properties of a feature in the input layout.
+///
+/// # Arguments
+///
+/// * `outer_properties` - The outer properties without the nested `properties` map.
+/// * `inner_properties` - The parsed inner properties.
+///
+/// # Returns
+///
+/// * `Map<String, Value>` - The outer properties with the inner ones nested
+///   under `properties`.
+fn nested_properties(
+    outer_properties: &Map<String, Value>,
+    inner_properties: &Map<String, Value>,
+) -> Map<String, Value> {
+    let mut properties = outer_properties.clone();
+    properties.insert("properties".to_string(), Value::Object(inner_properties.clone()));
+    properties
+}
+
+/// Helper function to convert a bounding rect to a GeoJSON bbox.
+///
+/// # Arguments
+///
+/// * `rect` - The bounding rect, `None` for empty geometries.
+///
+/// # Returns
+///
+/// * `Option<Bbox>` - `[min_x, min_y, max_x, max_y]`, or `None` if there is no
+///   rect or it has non-finite coordinates.
+fn rect_to_bbox(rect: Option<Rect>) -> Option<Bbox> {
+    let rect = rect?;
+    let bbox = vec![rect.min().x, rect.min().y, rect.max().x, rect.max().y];
+    bbox.iter().all(|value| value.is_finite()).then_some(bbox)
+}
+
 // --- Domain Entity Enum ---
 #[derive(Debug, Clone)]
 pub enum DomainEntity {
@@ -126,9 +327,14 @@ pub enum DomainEntity {
     DrillingPoint(DrillingPoint),
     CableTunnel(CableTunnel),
     Building(Building),
-    Unknown(Feature),
+    CableRoute(CableRoute),
+    Cable(Cable),
+    Unknown(UnknownEntity),
 }
 
+/// The original outer and inner properties of an identified feature.
+type OriginalProperties<'a> = (&'a Map<String, Value>, &'a Map<String, Value>);
+
 impl DomainEntity {
     /// Helper function to get the ID of the feature.
     ///
@@ -138,20 +344,67 @@ impl DomainEntity {
     ///
     /// # Returns
     ///
-    /// * `Option<&String>` - The ID of the feature.
-    pub fn id(&self) -> Option<&String> {
-        match self {
-            DomainEntity::CapturedMarker(marker) => Some(&marker.id),
-            DomainEntity::SupplyPoint(point) => Some(&point.id),
-            DomainEntity::OperationSite(site) => Some(&site.id),
-            DomainEntity::DrillingPoint(point) => Some(&point.id),
-            DomainEntity::CableTunnel(tunnel) => Some(&tunnel.id),
-            DomainEntity::Building(building) => Some(&building.id),
-            DomainEntity::Unknown(feature) => feature.id.as_ref().and_then(|id| match id {
-                Id::String(s) => Some(s),
-                Id::Number(_) => None, // Or convert number ID to string reference if possible
-            }), // Handle geojson::feature::Id
-        }
+    /// * `Option<&EntityId>` - The ID of the feature, or None if it has no id.
+    pub fn id(&self) -> Option<&EntityId> {
+        let id = match self {
+            DomainEntity::CapturedMarker(marker) => &marker.id,
+            DomainEntity::SupplyPoint(point) => &point.id,
+            DomainEntity::OperationSite(site) => &site.id,
+            DomainEntity::DrillingPoint(point) => &point.id,
+            DomainEntity::CableTunnel(tunnel) => &tunnel.id,
+            DomainEntity::Building(building) => &building.id,
+            DomainEntity::CableRoute(route) => &route.id,
+            DomainEntity::Cable(cable) => &cable.id,
+            DomainEntity::Unknown(unknown) => &unknown.id,
+        };
+        (*id != EntityId::Missing).then_some(id)
+    }
+
+    /// Helper function to get the objectId stored in the inner properties.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<&str>` - The objectId, or None for Unknown entities.
+    pub fn object_id(&self) -> Option<&str> {
+        let (_, inner_properties) = self.original_properties()?;
+        inner_properties.get("objectId").and_then(Value::as_str)
+    }
+
+    /// Helper function to get the original outer and inner properties.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<OriginalProperties>` - The outer and inner properties, or
+    ///   None for Unknown entities.
+    fn original_properties(&self) -> Option<OriginalProperties<'_>> {
+        let properties = match self {
+            DomainEntity::CapturedMarker(marker) => {
+                (&marker.original_outer_properties, &marker.original_inner_properties)
+            }
+            DomainEntity::SupplyPoint(point) => {
+                (&point.original_outer_properties, &point.original_inner_properties)
+            }
+            DomainEntity::OperationSite(site) => {
+                (&site.original_outer_properties, &site.original_inner_properties)
+            }
+            DomainEntity::DrillingPoint(point) => {
+                (&point.original_outer_properties, &point.original_inner_properties)
+            }
+            DomainEntity::CableTunnel(tunnel) => {
+                (&tunnel.original_outer_properties, &tunnel.original_inner_properties)
+            }
+            DomainEntity::Building(building) => {
+                (&building.original_outer_properties, &building.original_inner_properties)
+            }
+            DomainEntity::CableRoute(route) => {
+                (&route.original_outer_properties, &route.original_inner_properties)
+            }
+            DomainEntity::Cable(cable) => {
+                (&cable.original_outer_properties, &cable.original_inner_properties)
+            }
+            DomainEntity::Unknown(_) => return None,
+        };
+        Some(properties)
     }
 }
 
@@ -173,7 +426,9 @@ impl From<&DomainEntity> for Feature {
             DomainEntity::DrillingPoint(point) => point.into(),
             DomainEntity::CableTunnel(tunnel) => tunnel.into(),
             DomainEntity::Building(building) => building.into(),
-            DomainEntity::Unknown(feature) => feature.clone(),
+            DomainEntity::CableRoute(route) => route.into(),
+            DomainEntity::Cable(cable) => cable.into(),
+            DomainEntity::Unknown(unknown) => unknown.feature.clone(),
         }
     }
 }
@@ -196,6 +451,8 @@ impl From<DomainEntity> for GeoGeometry {
             DomainEntity::DrillingPoint(point) => GeoGeometry::Point(point.geometry),
             DomainEntity::CableTunnel(tunnel) => GeoGeometry::Point(tunnel.geometry),
             DomainEntity::Building(building) => building.geometry,
+            DomainEntity::CableRoute(route) => GeoGeometry::LineString(route.geometry),
+            DomainEntity::Cable(cable) => GeoGeometry::LineString(cable.geometry),
             DomainEntity::Unknown(_) => GeoGeometry::Point(Point::new(0.0, 0.0)),
         }
     }
@@ -223,14 +480,149 @@ impl DomainEntity {
     pub fn is_building(&self) -> bool {
         matches!(self, DomainEntity::Building(_))
     }
+    pub fn is_cable_route(&self) -> bool {
+        matches!(self, DomainEntity::CableRoute(_))
+    }
+    pub fn is_cable(&self) -> bool {
+        matches!(self, DomainEntity::Cable(_))
+    }
+    /// Name of the variant, e.g. "CapturedMarker" or "Unknown".
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            DomainEntity::CapturedMarker(_) => "CapturedMarker",
+            DomainEntity::SupplyPoint(_) => "SupplyPoint",
+            DomainEntity::OperationSite(_) => "OperationSite",
+            DomainEntity::DrillingPoint(_) => "DrillingPoint",
+            DomainEntity::CableTunnel(_) => "CableTunnel",
+            DomainEntity::Building(_) => "Building",
+            DomainEntity::CableRoute(_) => "CableRoute",
+            DomainEntity::Cable(_) => "Cable",
+            DomainEntity::Unknown(_) => "Unknown",
+        }
+    }
+    /// The kind of the variant.
+    pub fn kind(&self) -> EntityKind {
+        match self {
+            DomainEntity::CapturedMarker(_) => EntityKind::CapturedMarker,
+            DomainEntity::SupplyPoint(_) => EntityKind::SupplyPoint,
+            DomainEntity::OperationSite(_) => EntityKind::OperationSite,
+            DomainEntity::DrillingPoint(_) => EntityKind::DrillingPoint,
+            DomainEntity::CableTunnel(_) => EntityKind::CableTunnel,
+            DomainEntity::Building(_) => EntityKind::Building,
+            DomainEntity::CableRoute(_) => EntityKind::CableRoute,
+            DomainEntity::Cable(_) => EntityKind::Cable,
+            DomainEntity::Unknown(_) => EntityKind::Unknown,
+        }
+    }
+}
+
+impl InBoundingBox for DomainEntity {
+    /// Checks if the entity geometry lies entirely within the bounding box.
+    ///
+    /// Unknown entities are checked by their feature geometry; features
+    /// without a convertible geometry are never inside.
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        match self {
+            DomainEntity::CapturedMarker(marker) => marker.geometry.in_bounding_box(bbox),
+            DomainEntity::SupplyPoint(point) => point.geometry.in_bounding_box(bbox),
+            DomainEntity::OperationSite(site) => site.geometry.in_bounding_box(bbox),
+            DomainEntity::DrillingPoint(point) => point.geometry.in_bounding_box(bbox),
+            DomainEntity::CableTunnel(tunnel) => tunnel.geometry.in_bounding_box(bbox),
+            DomainEntity::Building(building) => building.geometry.in_bounding_box(bbox),
+            DomainEntity::CableRoute(route) => route.geometry.in_bounding_box(bbox),
+            DomainEntity::Cable(cable) => cable.geometry.in_bounding_box(bbox),
+            DomainEntity::Unknown(unknown) => unknown
+                .feature
+                .geometry
+                .as_ref()
+                .and_then(|geometry| GeoGeometry::try_from(geometry).ok())
+                .is_some_and(|geometry| geometry.in_bounding_box(bbox)),
+        }
+    }
+}
+
+/// The kind of a DomainEntity variant, without its data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum EntityKind {
+    CapturedMarker,
+    SupplyPoint,
+    OperationSite,
+    DrillingPoint,
+    CableTunnel,
+    Building,
+    CableRoute,
+    Cable,
+    Unknown,
+}
+
+/// A list of identified DomainEntity variants with helpers to filter and
+/// split them by kind.
+#[derive(Debug, Clone, Default)]
+pub struct DomainEntityCollection(pub Vec<DomainEntity>);
+
+impl From<Vec<DomainEntity>> for DomainEntityCollection {
+    fn from(entities: Vec<DomainEntity>) -> Self {
+        Self(entities)
+    }
 }
 
+impl DomainEntityCollection {
+    /// The entities in the collection.
+    pub fn entities(&self) -> &[DomainEntity] {
+        &self.0
+    }
+
+    /// Consumes the collection, returning the entities.
+    pub fn into_inner(self) -> Vec<DomainEntity> {
+        self.0
+    }
+
+    /// The captured markers in the collection.
+    pub fn markers(&self) -> impl Iterator<Item = &CapturedMarker> {
+        self.0.iter().filter_map(|entity| match entity {
+            DomainEntity::CapturedMarker(marker) => Some(marker),
+            _ => None,
+        })
+    }
+
+    /// The buildings in the collection.
+    pub fn buildings(&self) -> impl Iterator<Item = &Building> {
+        self.0.iter().filter_map(|entity| match entity {
+            DomainEntity::Building(building) => Some(building),
+            _ => None,
+        })
+    }
+
+    /// The entities of the given kind.
+    pub fn of_kind(&self, kind: EntityKind) -> impl Iterator<Item = &DomainEntity> {
+        self.0.iter().filter(move |entity| entity.kind() == kind)
+    }
+
+    /// Splits the collection by kind, keeping the input order within each kind.
+    pub fn partition_by_kind(self) -> HashMap<EntityKind, Vec<DomainEntity>> {
+        let mut partitions: HashMap<EntityKind, Vec<DomainEntity>> = HashMap::new();
+        for entity in self.0 {
+            partitions.entry(entity.kind()).or_default().push(entity);
+        }
+        partitions
+    }
+
+    /// Drops the entities whose geometry does not lie entirely within `rect`.
+    pub fn retain_in_bbox(&mut self, rect: Rect) {
+        let bbox = [rect.min().x, rect.min().y, rect.max().x, rect.max().y];
+        self.0.retain(|entity| entity.in_bounding_box(&bbox));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ObjectId {
     Kugelmarker,
     Versorgungspunkt,
     Betriebsstelle,
     Bohrpunkt,
     Kabelschacht,
+    Kabeltrasse,
+    Kabel,
     Building,
 }
 
@@ -252,12 +644,127 @@ impl TryFrom<String> for ObjectId {
             "Betriebsstelle" => Ok(ObjectId::Betriebsstelle),
             "Bohrpunkt" => Ok(ObjectId::Bohrpunkt),
             "Kabelschacht" => Ok(ObjectId::Kabelschacht),
+            "Kabeltrasse" => Ok(ObjectId::Kabeltrasse),
+            "Kabel" => Ok(ObjectId::Kabel),
             "Gebaeude" => Ok(ObjectId::Building),
             _ => Err(Error::InvalidObjectId(value)),
         }
     }
 }
 
+impl ObjectId {
+    /// Helper function to get the objectId string of an ObjectId variant.
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The string accepted by `ObjectId::try_from`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ObjectId::Kugelmarker => "Kugelmarker",
+            ObjectId::Versorgungspunkt => "Versorgungspunkt",
+            ObjectId::Betriebsstelle => "Betriebsstelle",
+            ObjectId::Bohrpunkt => "Bohrpunkt",
+            ObjectId::Kabelschacht => "Kabelschacht",
+            ObjectId::Kabeltrasse => "Kabeltrasse",
+            ObjectId::Kabel => "Kabel",
+            ObjectId::Building => "Gebaeude",
+        }
+    }
+}
+
+/// Maps objectId strings to ObjectId variants.
+///
+/// The default mapping holds the German identifiers accepted by
+/// `ObjectId::try_from` and matches them case-sensitively. Aliases such as
+/// English or lowercase identifiers can be registered on top of it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectIdMapping {
+    aliases: HashMap<String, ObjectId>,
+    case_insensitive: bool,
+}
+
+impl Default for ObjectIdMapping {
+    fn default() -> Self {
+        Self::empty()
+            .with_alias("Kugelmarker", ObjectId::Kugelmarker)
+            .with_alias("Versorgungspunkt", ObjectId::Versorgungspunkt)
+            .with_alias("Betriebsstelle", ObjectId::Betriebsstelle)
+            .with_alias("Bohrpunkt", ObjectId::Bohrpunkt)
+            .with_alias("Kabelschacht", ObjectId::Kabelschacht)
+            .with_alias("Kabeltrasse", ObjectId::Kabeltrasse)
+            .with_alias("Kabel", ObjectId::Kabel)
+            .with_alias("Gebaeude", ObjectId::Building)
+    }
+}
+
+impl From<&HashMap<String, ObjectId>> for ObjectIdMapping {
+    /// The default mapping extended by `aliases`, which take precedence.
+    fn from(aliases: &HashMap<String, ObjectId>) -> Self {
+        let mut mapping = ObjectIdMapping::default();
+        for (alias, object_id) in aliases {
+            mapping.insert(alias.clone(), *object_id);
+        }
+        mapping
+    }
+}
+
+static DEFAULT_OBJECT_ID_MAPPING: OnceLock<ObjectIdMapping> = OnceLock::new();
+
+impl ObjectIdMapping {
+    /// A mapping without any objectId strings.
+    pub fn empty() -> Self {
+        Self {
+            aliases: HashMap::new(),
+            case_insensitive: false,
+        }
+    }
+
+    /// Registers `alias` as an objectId string for `object_id`.
+    pub fn with_alias(mut self, alias: impl Into<String>, object_id: ObjectId) -> Self {
+        self.insert(alias, object_id);
+        self
+    }
+
+    /// Sets whether objectId strings are matched ignoring case.
+    pub fn case_insensitive(mut self, case_insensitive: bool) -> Self {
+        self.case_insensitive = case_insensitive;
+        self
+    }
+
+    /// Registers `alias` as an objectId string for `object_id`.
+    pub fn insert(&mut self, alias: impl Into<String>, object_id: ObjectId) {
+        self.aliases.insert(alias.into(), object_id);
+    }
+
+    /// Looks up the ObjectId for an objectId string.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ObjectId, Error>` - The ObjectId, or `Error::InvalidObjectId`
+    ///   if the string is not mapped.
+    pub fn resolve(&self, value: &str) -> Result<ObjectId, Error> {
+        if let Some(object_id) = self.aliases.get(value) {
+            return Ok(*object_id);
+        }
+        if self.case_insensitive {
+            let value = value.to_lowercase();
+            if let Some((_, object_id)) = self
+                .aliases
+                .iter()
+                .find(|(alias, _)| alias.to_lowercase() == value)
+            {
+                return Ok(*object_id);
+            }
+        }
+        Err(Error::InvalidObjectId(value.to_string()))
+    }
+
+    /// The shared default mapping.
+    fn default_ref() -> &'static ObjectIdMapping {
+        DEFAULT_OBJECT_ID_MAPPING.get_or_init(ObjectIdMapping::default)
+    }
+}
+
 /// Helper function to create the specific DomainEntity variant
 /// for a Point type, given the common data and the identified ObjectId.
 ///
@@ -266,15 +773,19 @@ impl TryFrom<String> for ObjectId {
 /// * `id` - The ID of the feature.
 /// * `geometry` - The geometry of the feature.
 /// * `original_inner_properties` - The original inner properties of the feature.
+/// * `original_outer_properties` - The outer properties without the inner map.
+/// * `foreign_members` - The foreign members of the feature.
 /// * `object_id` - The identified ObjectId.
 ///
 /// # Returns
 ///
 /// * `DomainEntity` - The created DomainEntity variant.
 fn create_point_domain_entity(
-    id: String,
+    id: EntityId,
     geometry: Point,
     original_inner_properties: Map<String, Value>,
+    original_outer_properties: Map<String, Value>,
+    foreign_members: Option<Map<String, Value>>,
     object_id: ObjectId,
 ) -> DomainEntity {
     match object_id {
@@ -282,360 +793,1591 @@ fn create_point_domain_entity(
             id,
             geometry,
             original_inner_properties,
+            original_outer_properties,
+            foreign_members,
         }),
         ObjectId::Versorgungspunkt => DomainEntity::SupplyPoint(SupplyPoint {
             id,
             geometry,
             original_inner_properties,
+            original_outer_properties,
+            foreign_members,
         }),
         ObjectId::Betriebsstelle => DomainEntity::OperationSite(OperationSite {
             id,
             geometry,
             original_inner_properties,
+            original_outer_properties,
+            foreign_members,
         }),
         ObjectId::Bohrpunkt => DomainEntity::DrillingPoint(DrillingPoint {
             id,
             geometry,
             original_inner_properties,
+            original_outer_properties,
+            foreign_members,
         }),
         ObjectId::Kabelschacht => DomainEntity::CableTunnel(CableTunnel {
             id,
             geometry,
             original_inner_properties,
+            original_outer_properties,
+            foreign_members,
         }),
         _ => unimplemented!(),
     }
 }
 
-/// Helper function to identify the specific DomainEntity variant
-/// for a Point type, given the feature.
-///
-/// # Arguments
-///
-/// * `feature` - The feature to identify.
-///
-/// # Returns
-///
-/// * `DomainEntity` - The identified DomainEntity variant.
-fn indentify_domain_entity(feature: Feature) -> DomainEntity {
-    let feature_id = match feature.id.clone() {
-        Some(id) => match id {
-            geojson::feature::Id::String(id) => id,
-            geojson::feature::Id::Number(id) => id.to_string(),
-        },
-        None => "No ID".to_string(),
-    };
-
-    let original_feature = feature.clone();
-
-    let outer_properties = match &feature.properties {
-        Some(properties) => properties,
-        None => {
-            #[cfg(debug_assertions)]
-            eprintln!("Skipping feature {:?} with no properties", feature_id);
-            return DomainEntity::Unknown(original_feature);
-        }
-    };
+/// Options controlling how features are identified as DomainEntity variants.
+#[derive(Debug, Clone, Copy)]
+pub struct IdentifyConfig {
+    /// Tolerance for treating a Building LineString as closed.
+    pub closed_eps: f64,
+    /// Store the convex hull of a Building LineString as a Polygon instead of
+    /// the LineString itself.
+    ///
+    /// The hull always has an area and does not depend on the ring being
+    /// closed, so slightly open rings are still accepted. The tradeoff is that
+    /// concave footprints (L- or U-shaped buildings) are over-approximated,
+    /// whereas the default keeps the exact outline but requires the ring to
+    /// close within `closed_eps`.
+    pub line_string_buildings_as_hull: bool,
+    /// Close Building LineStrings that are not closed within `closed_eps` by
+    /// appending their first coordinate, as long as the closed ring encloses
+    /// an area. Disable to reject such rings as Unknown.
+    pub auto_close_building_rings: bool,
+    /// How to handle features that share an id with an earlier feature.
+    pub on_duplicate_id: DupIdPolicy,
+}
 
-    let inner_properties: Map<String, Value> = match &outer_properties.get("properties") {
-        Some(Value::String(s)) => match from_str(s) {
-            Ok(properties) => properties,
-            Err(e) => {
-                #[cfg(debug_assertions)]
-                eprintln!(
-                    "Inner properties string failed to parse for feature {}: {}",
-                    feature_id, e
-                );
-                return DomainEntity::Unknown(original_feature);
-            }
-        },
-        Some(Value::Object(properties)) => properties.clone(),
-        _ => {
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "Outer properties['properties'] is missing or not string/object for feature {}",
-                feature_id
-            );
-            return DomainEntity::Unknown(original_feature);
-        }
-    };
-    let identified_domain_entity = match inner_properties.get("objectId") {
-        Some(Value::String(object_id_value)) => {
-            match ObjectId::try_from(object_id_value.clone()) {
-                Ok(object_id_enum) => {
-                    match object_id_enum {
-                        ObjectId::Kugelmarker
-                        | ObjectId::Versorgungspunkt
-                        | ObjectId::Betriebsstelle
-                        | ObjectId::Bohrpunkt
-                        | ObjectId::Kabelschacht => {
-                            // --- Common logic for ALL known Point types ---
-                            let point_geometry = match get_point_geometry(
-                                &feature, // Pass by reference
-                                &original_feature,
-                                object_id_value, // Still need the string for logging context in helper
-                            ) {
-                                Ok(value) => value,
-                                Err(unknown_entity) => return unknown_entity, // Return Unknown if geometry extraction fails
-                            };
-
-                            // --- Use the factory function to create the specific entity ---
-                            create_point_domain_entity(
-                                feature_id,       // Move id
-                                point_geometry,   // Move geometry
-                                inner_properties, // Move properties
-                                object_id_enum,   // Pass the enum variant
-                            )
-                        }
-                        ObjectId::Building => {
-                            let building_geometry_option = feature.geometry.clone(); // Clone geometry
-
-                            let building_geometry = match &building_geometry_option {
-                                Some(geom) => {
-                                    // Try converting the geojson::Geometry into the general geo::Geometry enum
-                                    match GeoGeometry::try_from(geom) {
-                                        Ok(geo_geom) => geo_geom, // Successfully converted to GeoGeometry
-                                        Err(e) => {
-                                            let geojson_type = geom.value.type_name();
-                                            let conversion_error =
-                                                Error::GeometryConversionError(Box::new(e));
-                                            eprintln!(
-                                                "Failed to convert geojson::{} to geo::Geometry for Building (feature {}): {}",
-                                                geojson_type, feature_id, conversion_error
-                                            );
-                                            return DomainEntity::Unknown(original_feature); // Conversion error
-                                        }
-                                    }
-                                }
-                                None => {
-                                    #[cfg(debug_assertions)]
-                                    eprintln!(
-                                        "Geometry is missing for Building (feature {})",
-                                        feature_id
-                                    );
-                                    return DomainEntity::Unknown(original_feature); // Missing geometry
-                                }
-                            };
-                            match building_geometry {
-                                GeoGeometry::Polygon(polygon) => {
-                                    DomainEntity::Building(Building {
-                                        id: feature_id,
-                                        geometry: GeoGeometry::Polygon(polygon),
-                                        original_inner_properties: inner_properties,
-                                    })
-                                }
-                                GeoGeometry::MultiPolygon(multi_polygon) => {
-                                    DomainEntity::Building(Building {
-                                        id: feature_id,
-                                        geometry: GeoGeometry::MultiPolygon(multi_polygon),
-                                        original_inner_properties: inner_properties,
-                                    })
-                                }
-                                GeoGeometry::LineString(ls) => {
-                                    // Allowed type: Closed LineString
-                                    if ls.is_closed() {
-                                        DomainEntity::Building(Building {
-                                            id: feature_id,
-                                            geometry: GeoGeometry::LineString(ls), // Store the LineString
-                                            original_inner_properties: inner_properties,
-                                            // Add other specific fields
-                                        })
-                                    } else {
-                                        #[cfg(debug_assertions)]
-                                        eprintln!(
-                                            "LineString geometry for Building (feature {}) is not closed",
-                                            feature_id
-                                        );
-                                        DomainEntity::Unknown(original_feature) // LineString but not closed
-                                    }
-                                }
-                                // Disallowed geometry type for Building
-                                other_geometry => {
-                                    #[cfg(debug_assertions)]
-                                    eprintln!(
-                                        "Disallowed geometry type for Building (feature {}): {:?}",
-                                        feature_id,
-                                        other_geometry // Use geom_type() for better logging
-                                    );
-                                    DomainEntity::Unknown(original_feature)
-                                }
-                            }
-                        }
-                    }
-                }
-                Err(Error::InvalidObjectId(unrecognized_id_string)) => {
-                    // This branch handles valid strings that are NOT known ObjectIds
-                    #[cfg(debug_assertions)]
-                    eprintln!(
-                        "Unrecognized objectId string: {} for feature {}",
-                        unrecognized_id_string, feature_id
-                    );
-                    DomainEntity::Unknown(original_feature)
-                }
-                Err(e) => {
-                    #[cfg(debug_assertions)]
-                    eprintln!(
-                        "Failed to convert objectId for feature {}: {}",
-                        feature_id, e
-                    );
-                    DomainEntity::Unknown(original_feature)
-                }
-            }
-        }
-        // --- Fallback for missing or invalid objectId type ---
-        _ => {
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "Missing or invalid 'objectId' in inner properties for feature {}",
-                feature_id
-            );
-            DomainEntity::Unknown(original_feature)
+impl Default for IdentifyConfig {
+    fn default() -> Self {
+        Self {
+            closed_eps: CLOSED_RING_EPSILON,
+            line_string_buildings_as_hull: false,
+            auto_close_building_rings: true,
+            on_duplicate_id: DupIdPolicy::default(),
         }
-    };
+    }
+}
 
-    // Return the identified entity
-    identified_domain_entity
+/// Policy for features whose id repeats an earlier feature's id.
+///
+/// Ids are compared as `EntityId`s, so the numeric id `1` and the string id
+/// `"1"` are different ids. Features without an id are never duplicates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DupIdPolicy {
+    /// Keep duplicate ids as they are.
+    #[default]
+    Allow,
+    /// Fail identification with `Error::DuplicateFeatureId`.
+    Error,
+    /// Rename repeated ids to `{id}_{n}`, picking the lowest unused `n`.
+    Suffix,
 }
 
-/// Helper function to extract the geometry from a feature.
+/// Helper function to apply a DupIdPolicy to the ids of a feature list.
 ///
 /// # Arguments
 ///
-/// * `feature` - The feature to extract the geometry from.
+/// * `features` - The features whose ids are checked, renamed in place for `Suffix`.
+/// * `policy` - The policy to apply.
 ///
 /// # Returns
 ///
-/// * `Result<Point, DomainEntity>` - The extracted geometry.
-fn get_point_geometry(
-    feature: &Feature,          // Take by reference
-    original_feature: &Feature, // Take by reference
-    object_id_value: &str,      // Use &str, no need for &String
-) -> Result<Point, DomainEntity> {
-    let cloned_feature = original_feature.clone(); // Clone only when needed for Unknown
-
-    let geometry_option = feature.geometry.clone(); // Clone geometry to consume
-
-    let feature_id = match feature.id.clone() {
-        Some(id) => match id {
-            geojson::feature::Id::String(id) => id,
-            geojson::feature::Id::Number(id) => id.to_string(),
-        },
-        None => "No ID".to_string(),
-    };
+/// * `Result<(), Error>` - `Error::DuplicateFeatureId` for the first repeat under `Error`.
+fn apply_duplicate_id_policy(features: &mut [Feature], policy: DupIdPolicy) -> Result<(), Error> {
+    if policy == DupIdPolicy::Allow {
+        return Ok(());
+    }
 
-    let geometry = match &geometry_option {
-        Some(geom) => geom,
-        None => {
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "Geometry is missing for objectId '{}' (feature {})",
-                object_id_value, feature_id
-            );
-            return Err(DomainEntity::Unknown(cloned_feature));
-        }
-    };
+    // All ids in the input, so generated suffixes never clash with a later original id
+    let mut taken: HashSet<EntityId> = features
+        .iter()
+        .filter_map(|feature| feature.id.clone().map(EntityId::from))
+        .collect();
+    let mut seen: HashSet<EntityId> = HashSet::with_capacity(taken.len());
 
-    match geo::Point::try_from(geometry) {
-        Ok(point) => Ok(point),
-        Err(e) => {
-            let geojson_type = geometry.value.type_name();
-            // Use the new specific error variant
-            let conversion_error = Error::GeometryConversionError(Box::new(e));
-            #[cfg(debug_assertions)]
-            eprintln!(
-                "Failed to convert geojson::{} to geo::Point for objectId '{}' (feature {}): {}",
-                geojson_type,
-                object_id_value,
-                feature_id,
-                conversion_error // Use conversion_error here
-            );
-            Err(DomainEntity::Unknown(cloned_feature))
+    for feature in features.iter_mut() {
+        let Some(id) = feature.id.clone().map(EntityId::from) else {
+            continue;
+        };
+        if seen.insert(id.clone()) {
+            continue;
+        }
+        match policy {
+            DupIdPolicy::Allow => {}
+            DupIdPolicy::Error => return Err(Error::DuplicateFeatureId(id.to_string())),
+            DupIdPolicy::Suffix => {
+                let unique_id = (1..)
+                    .map(|n| EntityId::String(format!("{}_{}", id, n)))
+                    .find(|candidate| !taken.contains(candidate))
+                    .expect("an unused suffix always exists");
+                taken.insert(unique_id.clone());
+                seen.insert(unique_id.clone());
+                feature.id = unique_id.into();
+            }
         }
     }
+    Ok(())
 }
 
-/// Helper function to identify the specific DomainEntity variants
-/// for a Point type, given the feature collection.
+/// Identifies the specific DomainEntity variant of a single feature.
+///
+/// Features that cannot be identified are returned as `DomainEntity::Unknown`;
+/// use `DomainEntity::try_from` to get the reason instead.
 ///
 /// # Arguments
 ///
-/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+/// * `feature` - The feature to identify.
 ///
 /// # Returns
 ///
-/// * `Result<Vec<DomainEntity>, Error>` - The identified DomainEntity variant.
-pub fn indentify_domain_entities(geojson: GeoJson) -> Result<Vec<DomainEntity>, Error> {
-    let feature_collection = match geojson {
-        GeoJson::FeatureCollection(feature_collection) => feature_collection,
-        _ => return Err(Error::InvalidFeatureCollection),
-    };
-
-    // Process each feature using the helper function
-    let domain_entities: Vec<DomainEntity> = feature_collection
-        .features
-        .into_iter()
-        .map(indentify_domain_entity) // Apply helper to each feature
-        .collect(); // Collect results
+/// * `DomainEntity` - The identified DomainEntity variant.
+pub fn identify_domain_entity(feature: Feature) -> DomainEntity {
+    identify_domain_entity_with_config(feature, &IdentifyConfig::default())
+}
 
-    Ok(domain_entities)
+/// Misspelled alias of `identify_domain_entity`.
+#[deprecated(note = "use `identify_domain_entity` instead")]
+pub fn indentify_domain_entity(feature: Feature) -> DomainEntity {
+    identify_domain_entity(feature)
 }
 
-/// Helper function to convert a DomainEntity variant to a GeoJSON feature.
+/// Identifies a single feature like `identify_domain_entity`, using the
+/// given identification options.
 ///
 /// # Arguments
 ///
-/// * `domain_entity` - The DomainEntity variant to convert.
+/// * `feature` - The feature to identify.
+/// * `config` - Options controlling the identification.
 ///
 /// # Returns
 ///
-/// * `Feature` - The converted GeoJSON feature.
-#[allow(dead_code)]
-fn convert_domain_entity_to_geojson_feature(domain_entity: DomainEntity) -> Feature {
-    match &domain_entity {
-        // Match all Point variants and bind the enum value to `point_entity`
-        entity_ref @ DomainEntity::CapturedMarker(_) |
-         entity_ref @ DomainEntity::SupplyPoint(_) |
-         entity_ref @ DomainEntity::OperationSite(_) |
-         entity_ref @ DomainEntity::DrillingPoint(_) |
-         entity_ref @ DomainEntity::CableTunnel(_) |
-         entity_ref @ DomainEntity::Building(_) |
-         entity_ref @ DomainEntity::Unknown(_) // Also include Unknown here
-         => {
-             entity_ref.into() // Calls the Into<Feature> for &DomainEntity impl
-         }
+/// * `DomainEntity` - The identified DomainEntity variant.
+pub fn identify_domain_entity_with_config(
+    feature: Feature,
+    config: &IdentifyConfig,
+) -> DomainEntity {
+    identify_domain_entity_with_reason(feature, config, ObjectIdMapping::default_ref()).0
+}
+
+/// Helper function to identify a single feature, keeping the reason it was
+/// identified as `DomainEntity::Unknown`.
+fn identify_domain_entity_with_reason(
+    feature: Feature,
+    config: &IdentifyConfig,
+    mapping: &ObjectIdMapping,
+) -> (DomainEntity, Option<UnknownReason>) {
+    match try_identify_domain_entity(&feature, config, mapping) {
+        Ok(domain_entity) => (domain_entity, None),
+        Err(error) => (DomainEntity::Unknown(feature.into()), Some(UnknownReason::from(error))),
     }
 }
 
-/// Helper function to convert a vector of DomainEntity variants to a GeoJSON feature collection.
+/// Helper function to identify the specific DomainEntity variant of a feature,
+/// failing instead of falling back to `DomainEntity::Unknown`.
 ///
 /// # Arguments
 ///
-/// * `domain_entities` - The vector of DomainEntity variants to convert.
+/// * `feature` - The feature to identify.
+/// * `config` - Options controlling the identification.
+/// * `mapping` - The mapping from objectId strings to ObjectId variants.
 ///
 /// # Returns
 ///
-/// * `GeoJson` - The converted GeoJSON feature collection.
-#[allow(dead_code)]
-fn convert_domain_entities_to_geojson_features(domain_entities: Vec<DomainEntity>) -> GeoJson {
-    let features = domain_entities
+/// * `Result<DomainEntity, Error>` - The identified DomainEntity variant, or
+///   `Error::InvalidFeatureProperties` when the inner properties or objectId are
+///   missing, `Error::InvalidObjectId` for unrecognized objectIds,
+///   `Error::MissingGeometry`, `Error::InvalidFeatureGeometry` for geometry
+///   types not allowed for the objectId, and `Error::GeometryConversion`.
+fn try_identify_domain_entity(
+    feature: &Feature,
+    config: &IdentifyConfig,
+    mapping: &ObjectIdMapping,
+) -> Result<DomainEntity, Error> {
+    let inner_properties = try_inner_properties(feature)?;
+    let object_id = try_object_id(&inner_properties, mapping)?;
+    let feature_id = EntityId::from(feature.id.clone());
+    let outer_properties = outer_properties_without_inner(feature);
+    let foreign_members = feature.foreign_members.clone();
+
+    match object_id {
+        ObjectId::Building => Ok(DomainEntity::Building(Building {
+            id: feature_id,
+            geometry: try_building_geometry(feature, config)?,
+            original_inner_properties: inner_properties,
+            original_outer_properties: outer_properties,
+            foreign_members,
+        })),
+        ObjectId::Kabeltrasse => Ok(DomainEntity::CableRoute(CableRoute {
+            id: feature_id,
+            geometry: try_line_geometry(feature)?,
+            original_inner_properties: inner_properties,
+            original_outer_properties: outer_properties,
+            foreign_members,
+        })),
+        ObjectId::Kabel => Ok(DomainEntity::Cable(Cable {
+            id: feature_id,
+            geometry: try_line_geometry(feature)?,
+            original_inner_properties: inner_properties,
+            original_outer_properties: outer_properties,
+            foreign_members,
+        })),
+        // --- Common logic for ALL known Point types ---
+        _ => Ok(create_point_domain_entity(
+            feature_id,
+            try_point_geometry(feature)?,
+            inner_properties,
+            outer_properties,
+            foreign_members,
+            object_id,
+        )),
+    }
+}
+
+/// Helper function to read the nested inner properties of a feature.
+///
+/// # Returns
+///
+/// * `Result<Map<String, Value>, Error>` - The inner properties,
+///   `Error::Serialization` if they are a JSON string that fails to parse, or
+///   `Error::InvalidFeatureProperties` if they are missing.
+fn try_inner_properties(feature: &Feature) -> Result<Map<String, Value>, Error> {
+    match feature
+        .properties
+        .as_ref()
+        .and_then(|outer_properties| outer_properties.get("properties"))
+    {
+        Some(Value::String(s)) => Ok(from_str(s)?),
+        Some(Value::Object(properties)) => Ok(properties.clone()),
+        _ => Err(Error::InvalidFeatureProperties),
+    }
+}
+
+/// Helper function to copy the outer properties of a feature without the
+/// nested inner properties.
+fn outer_properties_without_inner(feature: &Feature) -> Map<String, Value> {
+    let mut outer_properties = feature.properties.clone().unwrap_or_default();
+    outer_properties.remove("properties");
+    outer_properties
+}
+
+/// Helper function to read the objectId from the inner properties.
+///
+/// # Returns
+///
+/// * `Result<ObjectId, Error>` - The ObjectId, `Error::InvalidFeatureProperties`
+///   if it is missing, or `Error::InvalidObjectId` if it is not recognized.
+fn try_object_id(
+    inner_properties: &Map<String, Value>,
+    mapping: &ObjectIdMapping,
+) -> Result<ObjectId, Error> {
+    match inner_properties.get("objectId") {
+        Some(Value::String(object_id_value)) => mapping.resolve(object_id_value),
+        Some(other) => Err(Error::InvalidObjectId(other.to_string())),
+        None => Err(Error::InvalidFeatureProperties),
+    }
+}
+
+/// Helper function to extract the Point geometry of a point entity.
+///
+/// # Returns
+///
+/// * `Result<Point, Error>` - The Point, `Error::MissingGeometry`,
+///   `Error::InvalidFeatureGeometry` for other geometry types, or
+///   `Error::GeometryConversion` for malformed coordinates.
+fn try_point_geometry(feature: &Feature) -> Result<Point, Error> {
+    match &feature.geometry {
+        Some(geometry) if !matches!(geometry.value, geojson::Value::Point(_)) => {
+            Err(Error::InvalidFeatureGeometry)
+        }
+        _ => get_point_geometry(feature, feature).map_err(|unknown| unknown.1),
+    }
+}
+
+/// Helper function to extract the footprint geometry of a Building.
+///
+/// Polygons and MultiPolygons are kept as they are. LineStrings are kept if
+/// they are closed (snapping them shut) or can be auto-closed, or replaced by
+/// their convex hull if `config.line_string_buildings_as_hull` is set.
+///
+/// # Returns
+///
+/// * `Result<GeoGeometry, Error>` - The footprint, `Error::MissingGeometry`,
+///   `Error::GeometryConversion`, or `Error::InvalidFeatureGeometry` for
+///   disallowed geometry types, open LineStrings and degenerate hulls.
+fn try_building_geometry(feature: &Feature, config: &IdentifyConfig) -> Result<GeoGeometry, Error> {
+    let geometry = feature.geometry.as_ref().ok_or(Error::MissingGeometry)?;
+    // Try converting the geojson::Geometry into the general geo::Geometry enum
+    let building_geometry = GeoGeometry::try_from(geometry)
+        .map_err(|e| Error::GeometryConversion(Box::new(e)))?;
+
+    match building_geometry {
+        GeoGeometry::Polygon(_) | GeoGeometry::MultiPolygon(_) => Ok(building_geometry),
+        GeoGeometry::LineString(ls) if config.line_string_buildings_as_hull => {
+            // Opt-in: store the convex hull as the footprint
+            let hull = ls.convex_hull();
+            if hull.unsigned_area() > 0.0 {
+                Ok(GeoGeometry::Polygon(hull))
+            } else {
+                Err(Error::InvalidFeatureGeometry)
+            }
+        }
+        GeoGeometry::LineString(mut ls) => {
+            // Allowed type: Closed LineString
+            if is_closed_eps(&ls, config.closed_eps) {
+                snap_ring_closed(&mut ls);
+                Ok(GeoGeometry::LineString(ls))
+            } else if config.auto_close_building_rings {
+                // Open ring: append the first coordinate if that encloses an area
+                ls.close();
+                if Polygon::new(ls.clone(), vec![]).unsigned_area() > 0.0 {
+                    Ok(GeoGeometry::LineString(ls))
+                } else {
+                    Err(Error::InvalidFeatureGeometry)
+                }
+            } else {
+                Err(Error::InvalidFeatureGeometry)
+            }
+        }
+        // Disallowed geometry type for Building
+        _ => Err(Error::InvalidFeatureGeometry),
+    }
+}
+
+/// Helper function to extract the LineString geometry of a CableRoute or Cable.
+///
+/// # Returns
+///
+/// * `Result<LineString, Error>` - The open or closed LineString,
+///   `Error::MissingGeometry`, `Error::GeometryConversion`, or
+///   `Error::InvalidFeatureGeometry` for other geometry types and LineStrings
+///   with fewer than two positions.
+fn try_line_geometry(feature: &Feature) -> Result<LineString, Error> {
+    let geometry = feature.geometry.as_ref().ok_or(Error::MissingGeometry)?;
+    if !matches!(geometry.value, geojson::Value::LineString(_)) {
+        return Err(Error::InvalidFeatureGeometry);
+    }
+    let line_geometry = GeoGeometry::try_from(geometry)
+        .map_err(|e| Error::GeometryConversion(Box::new(e)))?;
+
+    match line_geometry {
+        GeoGeometry::LineString(line) if line.0.len() >= 2 => Ok(line),
+        _ => Err(Error::InvalidFeatureGeometry),
+    }
+}
+
+impl TryFrom<&Feature> for DomainEntity {
+    type Error = Error;
+    /// Identifies a single feature with the default identification options.
+    ///
+    /// # Arguments
+    ///
+    /// * `feature` - The feature to identify.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DomainEntity, Error>` - The identified DomainEntity variant,
+    ///   or the reason it could not be identified.
+    fn try_from(feature: &Feature) -> Result<Self, Self::Error> {
+        try_identify_domain_entity(
+            feature,
+            &IdentifyConfig::default(),
+            ObjectIdMapping::default_ref(),
+        )
+    }
+}
+
+/// Helper function to extract the geometry from a feature.
+///
+/// # Arguments
+///
+/// * `feature` - The feature to extract the geometry from.
+/// * `original_feature` - The feature to wrap in `DomainEntity::Unknown` on failure.
+///
+/// # Returns
+///
+/// * `Result<Point, Box<(DomainEntity, Error)>>` - The extracted geometry, or
+///   the Unknown entity together with the reason: `Error::MissingGeometry`, or
+///   `Error::GeometryConversion` carrying the underlying conversion error.
+fn get_point_geometry(
+    feature: &Feature,          // Take by reference
+    original_feature: &Feature, // Take by reference
+) -> Result<Point, Box<(DomainEntity, Error)>> {
+    let geometry = match &feature.geometry {
+        Some(geom) => geom,
+        None => {
+            return Err(Box::new((
+                DomainEntity::Unknown(original_feature.clone().into()),
+                Error::MissingGeometry,
+            )));
+        }
+    };
+
+    geo::Point::try_from(geometry).map_err(|e| {
+        Box::new((
+            DomainEntity::Unknown(original_feature.clone().into()),
+            Error::GeometryConversion(Box::new(e)),
+        ))
+    })
+}
+
+/// Helper function to identify the specific DomainEntity variants
+/// for a Point type, given the feature collection.
+///
+/// A lone Feature is identified as a one-element collection, and a bare
+/// Geometry yields a single `DomainEntity::Unknown`. This is
+/// `identify_domain_entities_reporting` without the parse errors.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+///
+/// # Returns
+///
+/// * `Result<Vec<DomainEntity>, Error>` - The identified DomainEntity variant.
+pub fn indentify_domain_entities(geojson: GeoJson) -> Result<Vec<DomainEntity>, Error> {
+    identify_domain_entities_reporting(geojson).map(|(domain_entities, _)| domain_entities)
+}
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, using
+/// `closed_eps` as the tolerance for closed Building LineStrings.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+/// * `closed_eps` - Tolerance for treating a Building LineString as closed.
+///
+/// # Returns
+///
+/// * `Result<Vec<DomainEntity>, Error>` - The identified DomainEntity variant.
+pub fn indentify_domain_entities_with_tolerance(
+    geojson: GeoJson,
+    closed_eps: f64,
+) -> Result<Vec<DomainEntity>, Error> {
+    let config = IdentifyConfig {
+        closed_eps,
+        ..Default::default()
+    };
+    indentify_domain_entities_with_config(geojson, &config)
+}
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, using
+/// the given identification options.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+/// * `config` - Options controlling the identification.
+///
+/// # Returns
+///
+/// * `Result<Vec<DomainEntity>, Error>` - The identified DomainEntity variant.
+pub fn indentify_domain_entities_with_config(
+    geojson: GeoJson,
+    config: &IdentifyConfig,
+) -> Result<Vec<DomainEntity>, Error> {
+    Ok(domain_entities_iter(geojson, *config)?.collect())
+}
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, resolving
+/// objectId strings through `mapping` instead of the built-in German identifiers.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+/// * `mapping` - The mapping from objectId strings to ObjectId variants.
+///
+/// # Returns
+///
+/// * `Result<Vec<DomainEntity>, Error>` - The identified DomainEntity variant.
+pub fn indentify_domain_entities_with_mapping(
+    geojson: GeoJson,
+    mapping: &ObjectIdMapping,
+) -> Result<Vec<DomainEntity>, Error> {
+    let config = IdentifyConfig::default();
+    Ok(prepare_features(geojson, &config)?
         .into_iter()
-        .map(convert_domain_entity_to_geojson_feature)
-        .collect::<Vec<Feature>>();
-    GeoJson::FeatureCollection(FeatureCollection {
+        .map(|feature| identify_domain_entity_with_reason(feature, &config, mapping).0)
+        .collect())
+}
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, but
+/// returns a lazy iterator instead of collecting into a Vec.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+///
+/// # Returns
+///
+/// * `Result<impl Iterator<Item = DomainEntity>, Error>` - An iterator identifying
+///   one feature per step.
+pub fn identify_domain_entities_iter(
+    geojson: GeoJson,
+) -> Result<impl Iterator<Item = DomainEntity>, Error> {
+    domain_entities_iter(geojson, IdentifyConfig::default())
+}
+
+/// Helper function to check the feature collection and apply the duplicate id
+/// policy up front, leaving the per-feature identification to the iterator.
+fn domain_entities_iter(
+    geojson: GeoJson,
+    config: IdentifyConfig,
+) -> Result<impl Iterator<Item = DomainEntity>, Error> {
+    // Process each feature using the helper function
+    Ok(prepare_features(geojson, &config)?
+        .into_iter()
+        .map(move |feature| identify_domain_entity_with_config(feature, &config)))
+}
+
+/// Helper function to take the features out of a GeoJson object and
+/// apply the duplicate id policy.
+///
+/// A lone Feature becomes a one-element vector, and a bare Geometry becomes a
+/// feature without properties, which is identified as `DomainEntity::Unknown`.
+fn prepare_features(geojson: GeoJson, config: &IdentifyConfig) -> Result<Vec<Feature>, Error> {
+    let mut features = match geojson {
+        GeoJson::FeatureCollection(feature_collection) => feature_collection.features,
+        GeoJson::Feature(feature) => vec![feature],
+        GeoJson::Geometry(geometry) => vec![Feature::from(geometry)],
+    };
+
+    apply_duplicate_id_policy(&mut features, config.on_duplicate_id)?;
+    Ok(features)
+}
+
+/// Why a feature was identified as `DomainEntity::Unknown`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum UnknownReason {
+    /// The feature has no geometry.
+    MissingGeometry,
+    /// The outer or inner properties, or the inner objectId, are missing or unparseable.
+    UnparseableInnerProperties,
+    /// The inner objectId is not a known entity type.
+    UnrecognizedObjectId(String),
+    /// The geometry type is not allowed for the objectId.
+    InvalidGeometryType,
+    /// The geometry could not be converted, with the conversion error message.
+    GeometryConversionError(String),
+}
+
+impl From<Error> for UnknownReason {
+    fn from(error: Error) -> Self {
+        match error {
+            Error::MissingGeometry => UnknownReason::MissingGeometry,
+            Error::InvalidObjectId(object_id) => UnknownReason::UnrecognizedObjectId(object_id),
+            Error::InvalidFeatureGeometry | Error::UnsupportedGeometryType => {
+                UnknownReason::InvalidGeometryType
+            }
+            Error::InvalidFeatureProperties | Error::Serialization(_) => {
+                UnknownReason::UnparseableInnerProperties
+            }
+            other => UnknownReason::GeometryConversionError(other.to_string()),
+        }
+    }
+}
+
+impl std::fmt::Display for UnknownReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UnknownReason::MissingGeometry => write!(f, "missing geometry"),
+            UnknownReason::UnparseableInnerProperties => {
+                write!(f, "missing or unparseable inner properties")
+            }
+            UnknownReason::UnrecognizedObjectId(object_id) => {
+                write!(f, "unrecognized objectId '{}'", object_id)
+            }
+            UnknownReason::InvalidGeometryType => write!(f, "geometry type not allowed"),
+            UnknownReason::GeometryConversionError(message) => {
+                write!(f, "geometry conversion failed: {}", message)
+            }
+        }
+    }
+}
+
+/// Summary of an identification run: entities per type and why features
+/// ended up as `DomainEntity::Unknown`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConversionReport {
+    /// Number of identified entities per type name, see `DomainEntity::type_name`.
+    pub counts: BTreeMap<&'static str, usize>,
+    /// Feature id (`EntityId::Missing` if none) and reason for every Unknown
+    /// entity, in input order.
+    pub unknowns: Vec<(EntityId, UnknownReason)>,
+}
+
+impl ConversionReport {
+    /// Total number of features processed.
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// Number of entities of the given type name.
+    pub fn count(&self, type_name: &str) -> usize {
+        self.counts.get(type_name).copied().unwrap_or(0)
+    }
+
+    /// Ids of the features identified as Unknown; features without an id are skipped.
+    pub fn unknown_ids(&self) -> Vec<String> {
+        self.unknowns
+            .iter()
+            .filter(|(id, _)| *id != EntityId::Missing)
+            .map(|(id, _)| id.to_string())
+            .collect()
+    }
+
+    /// Number of Unknown entities per reason.
+    pub fn reason_counts(&self) -> BTreeMap<&UnknownReason, usize> {
+        let mut reason_counts = BTreeMap::new();
+        for (_, reason) in &self.unknowns {
+            *reason_counts.entry(reason).or_insert(0) += 1;
+        }
+        reason_counts
+    }
+
+    fn record(
+        &mut self,
+        domain_entity: &DomainEntity,
+        id: EntityId,
+        reason: Option<UnknownReason>,
+    ) {
+        *self.counts.entry(domain_entity.type_name()).or_insert(0) += 1;
+        if let Some(reason) = reason {
+            self.unknowns.push((id, reason));
+        }
+    }
+}
+
+impl std::fmt::Display for ConversionReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} features", self.total())?;
+        for (type_name, count) in &self.counts {
+            writeln!(f, "  {}: {}", type_name, count)?;
+        }
+        for (reason, count) in self.reason_counts() {
+            writeln!(f, "  Unknown ({}): {}", reason, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, and
+/// reports the entities per type and why features ended up as Unknown.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+///
+/// # Returns
+///
+/// * `Result<(Vec<DomainEntity>, ConversionReport), Error>` - The identified
+///   DomainEntity variants and the report.
+pub fn indentify_domain_entities_with_report(
+    geojson: GeoJson,
+) -> Result<(Vec<DomainEntity>, ConversionReport), Error> {
+    let config = IdentifyConfig::default();
+    let features = prepare_features(geojson, &config)?;
+
+    let mut report = ConversionReport::default();
+    let domain_entities = features
+        .into_iter()
+        .map(|feature| {
+            let id = EntityId::from(feature.id.clone());
+            let mapping = ObjectIdMapping::default_ref();
+            let (domain_entity, reason) =
+                identify_domain_entity_with_reason(feature, &config, mapping);
+            report.record(&domain_entity, id, reason);
+            domain_entity
+        })
+        .collect();
+
+    Ok((domain_entities, report))
+}
+
+/// Feature ids paired with the error raised for that feature.
+pub type FeatureErrors = Vec<(Option<Id>, Error)>;
+
+/// Identifies DomainEntity variants like `indentify_domain_entities`, and
+/// returns the features whose inner properties failed to parse.
+///
+/// Such features are still returned as `DomainEntity::Unknown`, so callers can
+/// log or reject bad records instead of losing them silently.
+///
+/// # Arguments
+///
+/// * `geojson` - The feature collection to identify the DomainEntity variants from.
+///
+/// # Returns
+///
+/// * `Result<(Vec<DomainEntity>, FeatureErrors), Error>` - The
+///   identified DomainEntity variants, and the id and `Error::Serialization`
+///   of every feature with unparseable inner properties.
+pub fn identify_domain_entities_reporting(
+    geojson: GeoJson,
+) -> Result<(Vec<DomainEntity>, FeatureErrors), Error> {
+    let config = IdentifyConfig::default();
+    let mapping = ObjectIdMapping::default_ref();
+    let mut parse_errors = Vec::new();
+    let domain_entities = prepare_features(geojson, &config)?
+        .into_iter()
+        .map(|feature| match try_identify_domain_entity(&feature, &config, mapping) {
+            Ok(domain_entity) => domain_entity,
+            Err(error) => {
+                if matches!(error, Error::Serialization(_)) {
+                    parse_errors.push((feature.id.clone(), error));
+                }
+                DomainEntity::Unknown(feature.into())
+            }
+        })
+        .collect();
+
+    Ok((domain_entities, parse_errors))
+}
+
+/// Helper function to read the nested inner properties of a feature.
+///
+/// The inner properties live under the outer `properties` key and are
+/// either a JSON object or a JSON-encoded string.
+///
+/// # Arguments
+///
+/// * `outer_properties` - The outer properties of the feature.
+///
+/// # Returns
+///
+/// * `Option<Map<String, Value>>` - The inner properties, if present and parseable.
+fn get_inner_properties(outer_properties: &Map<String, Value>) -> Option<Map<String, Value>> {
+    match outer_properties.get("properties") {
+        Some(Value::String(s)) => from_str(s).ok(),
+        Some(Value::Object(properties)) => Some(properties.clone()),
+        _ => None,
+    }
+}
+
+/// Copies the nested `objectId` of every feature up to its outer properties.
+///
+/// Some downstream tools only read `objectId` from the top-level properties.
+/// Features without a parseable inner `objectId` are left untouched.
+///
+/// # Arguments
+///
+/// * `fc` - The feature collection to flatten.
+///
+/// # Returns
+///
+/// * `FeatureCollection` - A copy of the collection with `objectId` in the outer properties.
+pub fn flatten_object_id(fc: &FeatureCollection) -> FeatureCollection {
+    let features = fc
+        .features
+        .iter()
+        .map(|feature| {
+            let mut feature = feature.clone();
+            if let Some(outer_properties) = feature.properties.as_mut()
+                && let Some(object_id) = get_inner_properties(outer_properties)
+                    .and_then(|inner| inner.get("objectId").cloned())
+            {
+                outer_properties.insert("objectId".to_string(), object_id);
+            }
+            feature
+        })
+        .collect();
+
+    FeatureCollection {
+        bbox: fc.bbox.clone(),
         features,
-        bbox: None,
-        foreign_members: None,
-    })
+        foreign_members: fc.foreign_members.clone(),
+    }
 }
 
-#[cfg(test)]
-mod tests {
-    use geojson::{FeatureCollection, feature::Id};
+/// Bounds used to score how plausible a Building geometry is.
+#[derive(Debug, Clone, Copy)]
+pub struct PlausibilityConfig {
+    /// Smallest plausible footprint in square meters.
+    pub min_area: f64,
+    /// Largest plausible footprint in square meters.
+    pub max_area: f64,
+    /// Largest plausible number of exterior vertices.
+    pub max_vertices: usize,
+}
 
-    use super::*;
+impl Default for PlausibilityConfig {
+    fn default() -> Self {
+        Self {
+            min_area: 10.0,
+            max_area: 100_000.0,
+            max_vertices: 500,
+        }
+    }
+}
+
+/// Helper function to turn a Building geometry into a MultiPolygon.
+///
+/// # Arguments
+///
+/// * `geometry` - The Building geometry.
+///
+/// # Returns
+///
+/// * `Option<MultiPolygon>` - The footprint, or None for non-areal geometries.
+fn building_footprint(geometry: &GeoGeometry) -> Option<MultiPolygon> {
+    match geometry {
+        GeoGeometry::Polygon(polygon) => Some(MultiPolygon::new(vec![polygon.clone()])),
+        GeoGeometry::MultiPolygon(multi_polygon) => Some(multi_polygon.clone()),
+        GeoGeometry::LineString(ls) if ls.is_closed() => {
+            Some(MultiPolygon::new(vec![Polygon::new(ls.clone(), vec![])]))
+        }
+        _ => None,
+    }
+}
+
+/// Scores how plausible a Building geometry is, using the default bounds.
+///
+/// See `building_plausibility_with_config`.
+///
+/// # Arguments
+///
+/// * `b` - The Building to score.
+///
+/// # Returns
+///
+/// * `f64` - A score from 0 (implausible) to 1 (plausible).
+pub fn building_plausibility(b: &Building) -> f64 {
+    building_plausibility_with_config(b, &PlausibilityConfig::default())
+}
+
+/// Scores how plausible a Building geometry is.
+///
+/// The score is the mean of three parts, each in 0..1:
+/// * area: 1 inside `min_area..=max_area` (geodesic, coordinates in WGS84),
+///   falling off proportionally outside it,
+/// * compactness: footprint area over the area of its minimum rotated rectangle,
+/// * vertex count: 1 up to `max_vertices`, falling off proportionally above it.
+///
+/// Invalid geometries (self-intersecting or unclosed rings, too few vertices)
+/// and non-areal geometries score 0.
+///
+/// # Arguments
+///
+/// * `b` - The Building to score.
+/// * `config` - The bounds to score against.
+///
+/// # Returns
+///
+/// * `f64` - A score from 0 (implausible) to 1 (plausible).
+pub fn building_plausibility_with_config(b: &Building, config: &PlausibilityConfig) -> f64 {
+    let Some(footprint) = building_footprint(&b.geometry) else {
+        return 0.0;
+    };
+    if footprint.0.is_empty() || !footprint.is_valid() {
+        return 0.0;
+    }
+
+    let area = footprint.geodesic_area_unsigned();
+    if area <= 0.0 {
+        return 0.0;
+    }
+    let area_score = if area < config.min_area {
+        area / config.min_area
+    } else if area > config.max_area {
+        config.max_area / area
+    } else {
+        1.0
+    };
+
+    let compactness = footprint
+        .minimum_rotated_rect()
+        .map(|rect| rect.unsigned_area())
+        .filter(|rect_area| *rect_area > 0.0)
+        .map_or(0.0, |rect_area| {
+            (footprint.unsigned_area() / rect_area).min(1.0)
+        });
+
+    let vertex_count: usize = footprint
+        .iter()
+        .map(|polygon| polygon.exterior().0.len().saturating_sub(1))
+        .sum();
+    let vertex_score = if vertex_count <= config.max_vertices {
+        1.0
+    } else {
+        config.max_vertices as f64 / vertex_count as f64
+    };
+
+    (area_score + compactness + vertex_score) / 3.0
+}
+
+/// Helper function to convert a DomainEntity variant to a GeoJSON feature.
+///
+/// # Arguments
+///
+/// * `domain_entity` - The DomainEntity variant to convert.
+///
+/// # Returns
+///
+/// * `Feature` - The converted GeoJSON feature in the input layout; Unknown
+///   entities keep the properties of their feature.
+pub(crate) fn convert_domain_entity_to_geojson_feature(domain_entity: DomainEntity) -> Feature {
+    Feature::from(&domain_entity)
+}
+
+/// Converts DomainEntity variants back to a GeoJSON feature collection, the
+/// inverse of `indentify_domain_entities`.
+///
+/// The inner properties are nested under `properties` again, so the features
+/// can be identified once more. The collection bbox is the union of the
+/// feature bboxes.
+///
+/// # Arguments
+///
+/// * `entities` - The DomainEntity variants to convert.
+///
+/// # Returns
+///
+/// * `FeatureCollection` - One feature per entity, in input order.
+pub fn domain_entities_to_feature_collection(entities: Vec<DomainEntity>) -> FeatureCollection {
+    domain_entities_to_feature_collection_with(entities, None, None)
+}
+
+/// Converts DomainEntity variants like `domain_entities_to_feature_collection`,
+/// using the given collection-level bbox and foreign members.
+///
+/// # Arguments
+///
+/// * `entities` - The DomainEntity variants to convert.
+/// * `bbox` - The collection bbox; `None` uses the union of the feature bboxes.
+/// * `foreign_members` - The collection foreign members, e.g. those of the input collection.
+///
+/// # Returns
+///
+/// * `FeatureCollection` - One feature per entity, in input order.
+pub fn domain_entities_to_feature_collection_with(
+    entities: Vec<DomainEntity>,
+    bbox: Option<Bbox>,
+    foreign_members: Option<Map<String, Value>>,
+) -> FeatureCollection {
+    let features = entities
+        .into_iter()
+        .map(convert_domain_entity_to_geojson_feature)
+        .collect::<Vec<Feature>>();
+    let bbox =
+        bbox.or_else(|| union_bboxes(features.iter().filter_map(|feature| feature.bbox.as_ref()), 2));
+    FeatureCollection {
+        features,
+        bbox,
+        foreign_members,
+    }
+}
+
+/// Recomputes the bbox of every feature from its geometry, and the collection
+/// bbox as their union.
+///
+/// Features without a convertible geometry get `bbox: None`, and a collection
+/// without any feature bbox gets `bbox: None` too. Existing bboxes are replaced.
+///
+/// # Arguments
+///
+/// * `fc` - The feature collection to update.
+///
+/// # Returns
+///
+/// * `FeatureCollection` - The feature collection with computed bboxes.
+pub fn with_computed_bboxes(mut fc: FeatureCollection) -> FeatureCollection {
+    for feature in &mut fc.features {
+        feature.bbox = feature
+            .geometry
+            .as_ref()
+            .and_then(|geometry| GeoGeometry::try_from(geometry).ok())
+            .and_then(|geometry| rect_to_bbox(geometry.bounding_rect()));
+    }
+    fc.bbox = union_bboxes(fc.features.iter().filter_map(|feature| feature.bbox.as_ref()), 2);
+    fc
+}
+
+#[cfg(test)]
+mod tests {
+    use geojson::{FeatureCollection, feature::Id};
+
+    use super::*;
+
+    #[test]
+    fn test_identify_domain_entities() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Kugelmarker"
+                            }
+                        },
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_marker());
+    }
+    #[test]
+    fn test_identify_domain_entities_with_null_properties() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": null,
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_unknown());
+    }
+    #[test]
+    fn test_identify_domain_entities_with_null_geometry() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Kugelmarker"
+                            }
+                        },
+                        "geometry": null
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_unknown());
+    }
+    #[test]
+    fn test_identify_domain_entities_with_invalid_geometry_type() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Kugelmarker"
+                            }
+                        },
+                        "geometry": {
+                            "type": "LineString",
+                            "coordinates": [
+                                [0.0, 0.0],
+                                [1.0, 1.0]
+                            ]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_unknown());
+    }
+    #[test]
+    fn test_identify_domain_untities_with_empty_feature_collection() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": []
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 0);
+    }
+    #[test]
+    fn test_convert_domain_entity_to_geojson_feature() {
+        let domain_entity = DomainEntity::CapturedMarker(CapturedMarker {
+            id: "1".into(),
+            geometry: Point::new(0.0, 0.0),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        });
+        let geojson_feature = convert_domain_entity_to_geojson_feature(domain_entity);
+        println!("geojson_feature: {:#?}", geojson_feature);
+        assert_eq!(geojson_feature.id.unwrap(), Id::String("1".to_string()));
+        // assert_eq!(geojson_feature.geometry.unwrap().to_owned().geometry_type(), "Point");
+    }
+    #[test]
+    fn test_convert_domain_entity_to_geojson_feature_with_unknown() {
+        let domain_entity = DomainEntity::Unknown(UnknownEntity::from(Feature {
+            geometry: None,
+            properties: None,
+            bbox: None,
+            id: Some(Id::String("No ID".to_string())),
+            foreign_members: None,
+        }));
+        let geojson_feature = convert_domain_entity_to_geojson_feature(domain_entity);
+        assert_eq!(geojson_feature.id.unwrap(), Id::String("No ID".to_string()));
+        assert!(geojson_feature.geometry.is_none());
+        assert!(geojson_feature.properties.is_none());
+    }
+    #[test]
+    fn test_domain_entities_to_feature_collection() {
+        let domain_entities = vec![
+            DomainEntity::CapturedMarker(CapturedMarker {
+                id: "1".into(),
+                geometry: Point::new(0.0, 0.0),
+                original_inner_properties: Map::new(),
+                original_outer_properties: Map::new(),
+                foreign_members: None,
+            }),
+            DomainEntity::Unknown(UnknownEntity::from(Feature {
+                geometry: None,
+                properties: None,
+                bbox: None,
+                id: Some(Id::String("No ID".to_string())),
+                foreign_members: None,
+            })),
+        ];
+        let collection = domain_entities_to_feature_collection(domain_entities);
+        assert_eq!(collection.features.len(), 2);
+        let marker_properties = collection.features[0].properties.as_ref().unwrap();
+        assert_eq!(marker_properties["properties"], Value::Object(Map::new()));
+        assert!(collection.features[1].properties.is_none());
+    }
+    #[test]
+    fn test_domain_entities_to_feature_collection_with_empty_vector() {
+        let domain_entities = vec![];
+        let collection = domain_entities_to_feature_collection(domain_entities);
+        assert_eq!(
+            collection,
+            FeatureCollection {
+                features: vec![],
+                bbox: None,
+                foreign_members: None
+            }
+        );
+    }
+    #[test]
+    fn test_domain_entities_to_feature_collection_emits_bboxes() {
+        let marker = DomainEntity::CapturedMarker(CapturedMarker {
+            id: "marker".into(),
+            geometry: Point::new(9.5, 50.25),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        });
+        let building = DomainEntity::Building(Building {
+            id: "building".into(),
+            geometry: GeoGeometry::MultiPolygon(MultiPolygon::new(vec![
+                Polygon::new(
+                    vec![(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 0.0)].into(),
+                    vec![],
+                ),
+                Polygon::new(
+                    vec![(5.0, -1.0), (6.0, -1.0), (6.0, 3.0), (5.0, -1.0)].into(),
+                    vec![],
+                ),
+            ])),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        });
+        let empty_building = DomainEntity::Building(Building {
+            id: "empty".into(),
+            geometry: GeoGeometry::MultiPolygon(MultiPolygon::new(vec![])),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        });
+
+        let marker_feature = Feature::from(&marker);
+        assert_eq!(marker_feature.bbox, Some(vec![9.5, 50.25, 9.5, 50.25]));
+        let building_feature = Feature::from(&building);
+        assert_eq!(building_feature.bbox, Some(vec![0.0, -1.0, 6.0, 3.0]));
+        assert_eq!(Feature::from(&empty_building).bbox, None);
+
+        let collection =
+            domain_entities_to_feature_collection(vec![marker, building, empty_building]);
+        assert_eq!(collection.bbox, Some(vec![0.0, -1.0, 9.5, 50.25]));
+    }
+
+    #[test]
+    fn test_with_computed_bboxes() {
+        let feature = |geometry: Option<Geometry>| Feature {
+            bbox: Some(vec![0.0, 0.0, 0.0, 0.0]),
+            geometry,
+            id: None,
+            properties: None,
+            foreign_members: None,
+        };
+        let point = Geometry::new(geojson::Value::Point(vec![9.0, 50.0]));
+        let polygon = Geometry::new(geojson::Value::Polygon(vec![vec![
+            vec![10.0, 51.0],
+            vec![11.0, 51.0],
+            vec![11.0, 52.5],
+            vec![10.0, 51.0],
+        ]]));
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![feature(Some(point)), feature(Some(polygon)), feature(None)],
+            foreign_members: None,
+        };
+
+        let fc = with_computed_bboxes(fc);
+
+        assert_eq!(fc.features[0].bbox, Some(vec![9.0, 50.0, 9.0, 50.0]));
+        assert_eq!(fc.features[1].bbox, Some(vec![10.0, 51.0, 11.0, 52.5]));
+        assert_eq!(fc.features[2].bbox, None);
+        assert_eq!(fc.bbox, Some(vec![9.0, 50.0, 11.0, 52.5]));
+
+        let empty = with_computed_bboxes(FeatureCollection {
+            bbox: Some(vec![0.0, 0.0, 1.0, 1.0]),
+            features: vec![],
+            foreign_members: None,
+        });
+        assert_eq!(empty.bbox, None);
+        assert!(empty.features.is_empty());
+    }
+
+    #[test]
+    fn test_domain_entities_to_feature_collection_round_trip() {
+        let geojson: GeoJson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "source": "survey-2024",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Kugelmarker" } },
+                        "geometry": { "type": "Point", "coordinates": [9.0, 50.0] }
+                    },
+                    {
+                        "id": "2",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [10.0, 51.0] }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+        let GeoJson::FeatureCollection(input) = geojson.clone() else {
+            panic!("expected a feature collection");
+        };
+
+        let entities = indentify_domain_entities(geojson).unwrap();
+        let collection = domain_entities_to_feature_collection(entities.clone());
+        assert_eq!(collection.bbox, Some(vec![9.0, 50.0, 10.0, 51.0]));
+        assert_eq!(collection.foreign_members, None);
+        assert_eq!(
+            collection.features[0].properties.as_ref().unwrap()["properties"]["objectId"],
+            Value::from("Kugelmarker")
+        );
+        let reidentified =
+            indentify_domain_entities(GeoJson::FeatureCollection(collection.clone())).unwrap();
+        let kinds = |entities: &[DomainEntity]| -> Vec<EntityKind> {
+            entities.iter().map(DomainEntity::kind).collect()
+        };
+        assert_eq!(
+            kinds(&reidentified),
+            vec![EntityKind::CapturedMarker, EntityKind::DrillingPoint]
+        );
+        assert_eq!(kinds(&reidentified), kinds(&entities));
+        assert_eq!(domain_entities_to_feature_collection(reidentified), collection);
+
+        let collection = domain_entities_to_feature_collection_with(
+            entities,
+            Some(vec![0.0, 0.0, 20.0, 60.0]),
+            input.foreign_members.clone(),
+        );
+        assert_eq!(collection.bbox, Some(vec![0.0, 0.0, 20.0, 60.0]));
+        assert_eq!(collection.foreign_members, input.foreign_members);
+        assert_eq!(collection.features.len(), 2);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_foreign_members_and_outer_properties() {
+        let input: Feature = serde_json::from_str(
+            r#"{
+                "id": "m1",
+                "type": "Feature",
+                "properties": {
+                    "layer": "survey",
+                    "properties": { "objectId": "Kugelmarker", "depth": 2 }
+                },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] },
+                "source": "field-app"
+            }"#,
+        )
+        .unwrap();
+
+        let domain_entity = identify_domain_entity(input.clone());
+        let DomainEntity::CapturedMarker(marker) = &domain_entity else {
+            panic!("expected a captured marker");
+        };
+        assert_eq!(marker.original_outer_properties.len(), 1);
+        assert_eq!(marker.original_outer_properties["layer"], Value::from("survey"));
+
+        let output = Feature::from(&domain_entity);
+        assert_eq!(output.id, input.id);
+        assert_eq!(output.geometry, input.geometry);
+        assert_eq!(output.foreign_members, input.foreign_members);
+        // The inner properties are nested under the outer ones again
+        assert_eq!(output.properties, input.properties);
+        assert!(identify_domain_entity(output).is_marker());
+    }
+
+    #[test]
+    fn test_numeric_ids_round_trip() {
+        let geojson: GeoJson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": 42,
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Kugelmarker" } },
+                        "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [3.0, 4.0] }
+                    },
+                    {
+                        "id": 7,
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Mast" } },
+                        "geometry": { "type": "Point", "coordinates": [5.0, 6.0] }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        let numeric_id = EntityId::Number(serde_json::Number::from(42));
+        assert_eq!(domain_entities[0].id(), Some(&numeric_id));
+        assert_eq!(numeric_id.to_string(), "42");
+        assert_eq!(domain_entities[1].id(), None);
+        assert!(domain_entities[2].is_unknown());
+        assert_eq!(
+            domain_entities[2].id(),
+            Some(&EntityId::Number(serde_json::Number::from(7)))
+        );
+
+        let features: Vec<Feature> = domain_entities.iter().map(Feature::from).collect();
+        assert_eq!(features[0].id, Some(Id::Number(serde_json::Number::from(42))));
+        assert_eq!(features[1].id, None);
+        assert_eq!(features[2].id, Some(Id::Number(serde_json::Number::from(7))));
+    }
+
+    #[test]
+    fn test_numeric_id_marker_round_trips_as_number() {
+        let input = feature_from_json(
+            r#"{ "id": 42, "type": "Feature",
+                 "properties": { "properties": { "objectId": "Kugelmarker" } },
+                 "geometry": { "type": "Point", "coordinates": [1.0, 2.0] } }"#,
+        );
+
+        let marker = CapturedMarker::try_from(&input).unwrap();
+        assert_eq!(marker.id, EntityId::Number(serde_json::Number::from(42)));
+        let output = Feature::from(&marker);
+        assert_eq!(output.id, Some(Id::Number(serde_json::Number::from(42))));
+
+        // Identifying the output again keeps the numeric id
+        let again = CapturedMarker::try_from(&output).unwrap();
+        assert_eq!(again.id, marker.id);
+    }
+
+    #[test]
+    fn test_foreign_members_survive_reserialization() {
+        let input = feature_from_json(
+            r#"{ "id": "b1", "type": "Feature",
+                 "properties": { "properties": { "objectId": "Gebaeude" } },
+                 "geometry": { "type": "Polygon",
+                               "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]] },
+                 "when": { "start": "2024-01-01", "end": "2024-12-31" } }"#,
+        );
+
+        let domain_entity = identify_domain_entity(input.clone());
+        assert!(domain_entity.is_building());
+
+        let serialized = serde_json::to_string(&Feature::from(&domain_entity)).unwrap();
+        let reparsed: Feature = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(reparsed.foreign_members, input.foreign_members);
+        assert_eq!(
+            reparsed.foreign_members.unwrap()["when"]["start"],
+            Value::from("2024-01-01")
+        );
+    }
+
+    #[test]
+    fn test_indentify_domain_entity() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Kugelmarker"
+                            }
+                        },
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_marker());
+    }
+    #[test]
+    fn test_indentify_domain_entity_with_supply_point() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Versorgungspunkt"
+                            }
+                        },
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_supply_point());
+    }
+    #[test]
+    fn test_indentify_domain_entity_with_operation_site() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Betriebsstelle"
+                            }
+                        },
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
+
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_operation_site());
+    }
+    #[test]
+    fn test_indentify_domain_entity_with_drilling_point() {
+        let geojson = serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Bohrpunkt"
+                            }
+                        },
+                        "geometry": {
+                            "type": "Point",
+                            "coordinates": [0.0, 0.0]
+                        }
+                    }
+                ]
+            }
+            "#,
+        )
+        .unwrap();
 
+        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_drilling_point());
+    }
     #[test]
-    fn test_identify_domain_entities() {
+    fn test_indentify_domain_entity_with_cable_tunnel() {
         let geojson = serde_json::from_str(
             r#"
             {
@@ -646,7 +2388,7 @@ mod tests {
                         "type": "Feature",
                         "properties": {
                             "properties": {
-                                "objectId": "Kugelmarker"
+                                "objectId": "Kabelschacht"
                             }
                         },
                         "geometry": {
@@ -662,10 +2404,10 @@ mod tests {
 
         let domain_entities = indentify_domain_entities(geojson).unwrap();
         assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_marker());
+        assert!(domain_entities[0].is_cable_tunnel());
     }
     #[test]
-    fn test_identify_domain_entities_with_null_properties() {
+    fn test_indentify_domain_entity_with_unknown() {
         let geojson = serde_json::from_str(
             r#"
             {
@@ -674,7 +2416,11 @@ mod tests {
                     {
                         "id": "1",
                         "type": "Feature",
-                        "properties": null,
+                        "properties": {
+                            "properties": {
+                                "objectId": "Unknown"
+                            }
+                        },
                         "geometry": {
                             "type": "Point",
                             "coordinates": [0.0, 0.0]
@@ -690,8 +2436,9 @@ mod tests {
         assert_eq!(domain_entities.len(), 1);
         assert!(domain_entities[0].is_unknown());
     }
+
     #[test]
-    fn test_identify_domain_entities_with_null_geometry() {
+    fn test_identify_domain_entity_with_line_string_geomtery() {
         let geojson = serde_json::from_str(
             r#"
             {
@@ -702,10 +2449,16 @@ mod tests {
                         "type": "Feature",
                         "properties": {
                             "properties": {
-                                "objectId": "Kugelmarker"
+                                "objectId": "Kabelschacht"
                             }
                         },
-                        "geometry": null
+                        "geometry": {
+                            "type": "LineString",
+                            "coordinates": [
+                                [0.0, 0.0],
+                                [1.0, 1.0]
+                            ]
+                        }
                     }
                 ]
             }
@@ -718,7 +2471,7 @@ mod tests {
         assert!(domain_entities[0].is_unknown());
     }
     #[test]
-    fn test_identify_domain_entities_with_invalid_geometry_type() {
+    fn test_indentify_domain_entity_with_polygon_geomtery() {
         let geojson = serde_json::from_str(
             r#"
             {
@@ -729,14 +2482,19 @@ mod tests {
                         "type": "Feature",
                         "properties": {
                             "properties": {
-                                "objectId": "Kugelmarker"
+                                "objectId": "Gebaeude"
                             }
                         },
                         "geometry": {
-                            "type": "LineString",
+                            "type": "Polygon",
                             "coordinates": [
-                                [0.0, 0.0],
-                                [1.0, 1.0]
+                                [
+                                    [0.0, 0.0],
+                                    [1.0, 1.0],
+                                    [1.0, 0.0],
+                                    [0.0, 1.0],
+                                    [0.0, 0.0]
+                                ]
                             ]
                         }
                     }
@@ -748,85 +2506,134 @@ mod tests {
 
         let domain_entities = indentify_domain_entities(geojson).unwrap();
         assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_unknown());
+        assert!(domain_entities[0].is_building());
     }
     #[test]
-    fn test_identify_domain_untities_with_empty_feature_collection() {
+    fn test_identify_domain_entity_with_multi_polygon_geomtery() {
         let geojson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
-                "features": []
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Gebaeude"
+                            }
+                        },
+                        "geometry": {
+                            "type": "MultiPolygon",
+                            "coordinates": [
+                                [
+                                    [
+                                        [0.0, 0.0],
+                                        [1.0, 1.0],
+                                        [1.0, 0.0],
+                                        [0.0, 1.0],
+                                        [0.0, 0.0]
+                                    ]
+                                ]
+                            ]
+                        }
+                    }
+                ]
             }
             "#,
         )
         .unwrap();
 
         let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 0);
-    }
-    #[test]
-    fn test_convert_domain_entity_to_geojson_feature() {
-        let domain_entity = DomainEntity::CapturedMarker(CapturedMarker {
-            id: "1".to_string(),
-            geometry: Point::new(0.0, 0.0),
-            original_inner_properties: Map::new(),
-        });
-        let geojson_feature = convert_domain_entity_to_geojson_feature(domain_entity);
-        println!("geojson_feature: {:#?}", geojson_feature);
-        assert_eq!(geojson_feature.id.unwrap(), Id::String("1".to_string()));
-        // assert_eq!(geojson_feature.geometry.unwrap().to_owned().geometry_type(), "Point");
-    }
-    #[test]
-    fn test_convert_domain_entity_to_geojson_feature_with_unknown() {
-        let domain_entity = DomainEntity::Unknown(Feature {
-            geometry: None,
-            properties: None,
-            bbox: None,
-            id: Some(Id::String("No ID".to_string())),
-            foreign_members: None,
-        });
-        let geojson_feature = convert_domain_entity_to_geojson_feature(domain_entity);
-        assert_eq!(geojson_feature.id.unwrap(), Id::String("No ID".to_string()));
-        assert!(geojson_feature.geometry.is_none());
-        assert!(geojson_feature.properties.is_none());
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_building());
     }
     #[test]
-    fn test_convert_domain_entities_to_geojson_features() {
-        let domain_entities = vec![
-            DomainEntity::CapturedMarker(CapturedMarker {
-                id: "1".to_string(),
-                geometry: Point::new(0.0, 0.0),
-                original_inner_properties: Map::new(),
-            }),
-            DomainEntity::Unknown(Feature {
-                geometry: None,
-                properties: None,
-                bbox: None,
-                id: Some(Id::String("No ID".to_string())),
-                foreign_members: None,
-            }),
-        ];
-        let geojson_features = convert_domain_entities_to_geojson_features(domain_entities);
-        println!("geojson_features: {:#?}", geojson_features);
+    fn test_identify_domain_entity_with_nearly_closed_line_string_building() {
+        let json = r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "1",
+                        "type": "Feature",
+                        "properties": {
+                            "properties": {
+                                "objectId": "Gebaeude"
+                            }
+                        },
+                        "geometry": {
+                            "type": "LineString",
+                            "coordinates": [
+                                [0.0, 0.0],
+                                [1.0, 0.0],
+                                [1.0, 1.0],
+                                [0.000000001, 0.0]
+                            ]
+                        }
+                    }
+                ]
+            }
+            "#;
+
+        let domain_entities = indentify_domain_entities(serde_json::from_str(json).unwrap()).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        match &domain_entities[0] {
+            DomainEntity::Building(building) => match &building.geometry {
+                GeoGeometry::LineString(ls) => assert!(ls.is_closed()),
+                other => panic!("unexpected geometry {:?}", other),
+            },
+            other => panic!("expected a building, got {:?}", other),
+        }
+
+        let config = IdentifyConfig {
+            closed_eps: 1e-12,
+            auto_close_building_rings: false,
+            ..Default::default()
+        };
+        let strict =
+            indentify_domain_entities_with_config(serde_json::from_str(json).unwrap(), &config)
+                .unwrap();
+        assert!(strict[0].is_unknown());
     }
     #[test]
-    fn test_convert_domain_entities_to_geojson_features_with_empty_vector() {
-        let domain_entities = vec![];
-        let geojson_features = convert_domain_entities_to_geojson_features(domain_entities);
-        assert_eq!(
-            geojson_features,
-            GeoJson::FeatureCollection(FeatureCollection {
-                features: vec![],
-                bbox: None,
-                foreign_members: None
-            })
+    fn test_identify_domain_entity_auto_closes_open_building_ring() {
+        let building = feature_from_json(
+            r#"{ "id": "1", "type": "Feature",
+                 "properties": { "properties": { "objectId": "Gebaeude" } },
+                 "geometry": { "type": "LineString",
+                               "coordinates": [[0.0, 0.0], [2.0, 0.0], [2.0, 2.0], [0.0, 2.0]] } }"#,
+        );
+
+        match identify_domain_entity(building.clone()) {
+            DomainEntity::Building(building) => match &building.geometry {
+                GeoGeometry::LineString(ls) => {
+                    assert!(ls.is_closed());
+                    assert_eq!(ls.0.len(), 5);
+                }
+                other => panic!("unexpected geometry {:?}", other),
+            },
+            other => panic!("expected a building, got {:?}", other),
+        }
+
+        let strict = IdentifyConfig {
+            auto_close_building_rings: false,
+            ..Default::default()
+        };
+        assert!(identify_domain_entity_with_config(building, &strict).is_unknown());
+
+        // Closing a collinear line encloses no area
+        let collinear = feature_from_json(
+            r#"{ "id": "2", "type": "Feature",
+                 "properties": { "properties": { "objectId": "Gebaeude" } },
+                 "geometry": { "type": "LineString",
+                               "coordinates": [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]] } }"#,
         );
+        assert!(identify_domain_entity(collinear).is_unknown());
     }
     #[test]
-    fn test_indentify_domain_entity() {
-        let geojson = serde_json::from_str(
-            r#"
+    fn test_identify_domain_entity_with_line_string_building_as_hull() {
+        let json = r#"
             {
                 "type": "FeatureCollection",
                 "features": [
@@ -835,27 +2642,334 @@ mod tests {
                         "type": "Feature",
                         "properties": {
                             "properties": {
-                                "objectId": "Kugelmarker"
+                                "objectId": "Gebaeude"
                             }
                         },
                         "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
+                            "type": "LineString",
+                            "coordinates": [
+                                [0.0, 0.0],
+                                [2.0, 0.0],
+                                [2.0, 2.0],
+                                [0.0, 2.0],
+                                [0.0, 0.1]
+                            ]
                         }
                     }
                 ]
             }
+            "#;
+
+        // The ring is open, so strict identification rejects it
+        let strict_config = IdentifyConfig {
+            auto_close_building_rings: false,
+            ..Default::default()
+        };
+        let strict =
+            indentify_domain_entities_with_config(serde_json::from_str(json).unwrap(), &strict_config)
+                .unwrap();
+        assert!(strict[0].is_unknown());
+
+        let config = IdentifyConfig {
+            line_string_buildings_as_hull: true,
+            ..Default::default()
+        };
+        let hulled =
+            indentify_domain_entities_with_config(serde_json::from_str(json).unwrap(), &config)
+                .unwrap();
+        match &hulled[0] {
+            DomainEntity::Building(building) => match &building.geometry {
+                GeoGeometry::Polygon(polygon) => assert_eq!(polygon.unsigned_area(), 4.0),
+                other => panic!("unexpected geometry {:?}", other),
+            },
+            other => panic!("expected a building, got {:?}", other),
+        }
+    }
+    fn duplicate_id_collection() -> GeoJson {
+        serde_json::from_str(
+            r#"
+            {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "id": "a",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Kugelmarker" } },
+                        "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+                    },
+                    {
+                        "id": "a",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [3.0, 4.0] }
+                    },
+                    {
+                        "id": "a_1",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [5.0, 6.0] }
+                    }
+                ]
+            }
             "#,
         )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_identify_domain_entities_duplicate_id_policies() {
+        let allowed = indentify_domain_entities(duplicate_id_collection()).unwrap();
+        let ids: Vec<_> = allowed.iter().map(|e| e.id().unwrap().to_string()).collect();
+        assert_eq!(ids, vec!["a", "a", "a_1"]);
+
+        let config = IdentifyConfig {
+            on_duplicate_id: DupIdPolicy::Error,
+            ..Default::default()
+        };
+        let result = indentify_domain_entities_with_config(duplicate_id_collection(), &config);
+        assert!(matches!(result, Err(Error::DuplicateFeatureId(id)) if id == "a"));
+
+        let config = IdentifyConfig {
+            on_duplicate_id: DupIdPolicy::Suffix,
+            ..Default::default()
+        };
+        let suffixed =
+            indentify_domain_entities_with_config(duplicate_id_collection(), &config).unwrap();
+        let ids: Vec<_> = suffixed.iter().map(|e| e.id().unwrap().to_string()).collect();
+        assert_eq!(ids, vec!["a", "a_2", "a_1"]);
+    }
+
+    #[test]
+    fn test_duplicate_id_policy_keeps_numeric_and_string_ids_apart() {
+        let point = |id: Value| {
+            serde_json::json!({
+                "id": id,
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Kugelmarker" } },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+            })
+        };
+        let collection = |ids: Vec<Value>| -> GeoJson {
+            serde_json::from_value(serde_json::json!({
+                "type": "FeatureCollection",
+                "features": ids.into_iter().map(point).collect::<Vec<_>>()
+            }))
+            .unwrap()
+        };
+
+        let config = IdentifyConfig {
+            on_duplicate_id: DupIdPolicy::Error,
+            ..Default::default()
+        };
+        let mixed = collection(vec![Value::from(1), Value::from("1")]);
+        assert_eq!(indentify_domain_entities_with_config(mixed, &config).unwrap().len(), 2);
+
+        let config = IdentifyConfig {
+            on_duplicate_id: DupIdPolicy::Suffix,
+            ..Default::default()
+        };
+        let numeric = collection(vec![Value::from(7), Value::from(7)]);
+        let suffixed = indentify_domain_entities_with_config(numeric, &config).unwrap();
+        assert_eq!(suffixed[0].id(), Some(&EntityId::Number(serde_json::Number::from(7))));
+        assert_eq!(suffixed[1].id(), Some(&EntityId::from("7_1")));
+    }
+    fn building(geometry: GeoGeometry) -> Building {
+        Building {
+            id: "b".into(),
+            geometry,
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn test_building_plausibility() {
+        // Roughly 22m x 22m square in Germany
+        let square = Polygon::new(
+            geo::LineString::from(vec![
+                (9.0, 50.0),
+                (9.0003, 50.0),
+                (9.0003, 50.0002),
+                (9.0, 50.0002),
+                (9.0, 50.0),
+            ]),
+            vec![],
+        );
+        let score = building_plausibility(&building(GeoGeometry::Polygon(square)));
+        assert!((score - 1.0).abs() < 1e-6, "score was {}", score);
+
+        // Self-intersecting bow tie
+        let bow_tie = Polygon::new(
+            geo::LineString::from(vec![
+                (9.0, 50.0),
+                (9.0003, 50.0002),
+                (9.0003, 50.0),
+                (9.0, 50.0002),
+                (9.0, 50.0),
+            ]),
+            vec![],
+        );
+        assert_eq!(building_plausibility(&building(GeoGeometry::Polygon(bow_tie))), 0.0);
+
+        // Thin sliver, far below the minimum area
+        let sliver = Polygon::new(
+            geo::LineString::from(vec![
+                (9.0, 50.0),
+                (9.001, 50.0),
+                (9.0, 50.0000001),
+                (9.0, 50.0),
+            ]),
+            vec![],
+        );
+        let sliver_score = building_plausibility(&building(GeoGeometry::Polygon(sliver)));
+        assert!(sliver_score < 0.7, "score was {}", sliver_score);
+
+        let point = building(GeoGeometry::Point(Point::new(9.0, 50.0)));
+        assert_eq!(building_plausibility(&point), 0.0);
+    }
+
+    #[test]
+    fn test_get_point_geometry_errors() {
+        let missing: Feature = serde_json::from_str(
+            r#"{ "type": "Feature", "properties": null, "geometry": null }"#,
+        )
         .unwrap();
+        match get_point_geometry(&missing, &missing).map_err(|unknown| *unknown) {
+            Err((entity, Error::MissingGeometry)) => assert!(entity.is_unknown()),
+            other => panic!("unexpected result {:?}", other.map(|_| ())),
+        }
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_marker());
+        let line: Feature = serde_json::from_str(
+            r#"{
+                "type": "Feature",
+                "properties": null,
+                "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] }
+            }"#,
+        )
+        .unwrap();
+        match get_point_geometry(&line, &line).map_err(|unknown| *unknown) {
+            Err((entity, Error::GeometryConversion(e))) => {
+                assert!(entity.is_unknown());
+                assert!(!e.to_string().is_empty());
+            }
+            other => panic!("unexpected result {:?}", other.map(|_| ())),
+        }
+    }
+
+    fn feature_from_json(json: &str) -> Feature {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn test_try_from_feature_for_domain_structs() {
+        let marker = feature_from_json(
+            r#"{
+                "id": "m1",
+                "type": "Feature",
+                "properties": { "properties": "{\"objectId\": \"Kugelmarker\"}" },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+            }"#,
+        );
+        let captured = CapturedMarker::try_from(&marker).unwrap();
+        assert_eq!(captured.id, EntityId::from("m1"));
+        assert_eq!(captured.geometry, Point::new(1.0, 2.0));
+        assert_eq!(
+            captured.original_inner_properties.get("objectId"),
+            Some(&Value::String("Kugelmarker".to_string()))
+        );
+        assert!(DomainEntity::try_from(&marker).unwrap().is_marker());
+        match SupplyPoint::try_from(&marker) {
+            Err(Error::InvalidObjectId(object_id)) => assert_eq!(object_id, "Kugelmarker"),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let building = feature_from_json(
+            r#"{
+                "id": "b1",
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Gebaeude" } },
+                "geometry": {
+                    "type": "Polygon",
+                    "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]]
+                }
+            }"#,
+        );
+        assert!(matches!(
+            Building::try_from(&building).unwrap().geometry,
+            GeoGeometry::Polygon(_)
+        ));
+    }
+
+    #[test]
+    fn test_try_from_feature_errors() {
+        let no_properties = feature_from_json(
+            r#"{ "type": "Feature", "properties": null,
+                 "geometry": { "type": "Point", "coordinates": [0.0, 0.0] } }"#,
+        );
+        assert!(matches!(
+            DomainEntity::try_from(&no_properties),
+            Err(Error::InvalidFeatureProperties)
+        ));
+
+        let unknown_object_id = feature_from_json(
+            r#"{ "type": "Feature", "properties": { "properties": { "objectId": "Mast" } },
+                 "geometry": { "type": "Point", "coordinates": [0.0, 0.0] } }"#,
+        );
+        match DomainEntity::try_from(&unknown_object_id) {
+            Err(Error::InvalidObjectId(object_id)) => assert_eq!(object_id, "Mast"),
+            other => panic!("unexpected result {:?}", other),
+        }
+
+        let missing_geometry = feature_from_json(
+            r#"{ "type": "Feature", "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                 "geometry": null }"#,
+        );
+        assert!(matches!(
+            DrillingPoint::try_from(&missing_geometry),
+            Err(Error::MissingGeometry)
+        ));
+
+        let line_marker = feature_from_json(
+            r#"{ "type": "Feature", "properties": { "properties": { "objectId": "Kabelschacht" } },
+                 "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] } }"#,
+        );
+        assert!(matches!(
+            CableTunnel::try_from(&line_marker),
+            Err(Error::InvalidFeatureGeometry)
+        ));
+
+        let open_building = feature_from_json(
+            r#"{ "type": "Feature", "properties": { "properties": { "objectId": "Gebaeude" } },
+                 "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] } }"#,
+        );
+        assert!(matches!(
+            Building::try_from(&open_building),
+            Err(Error::InvalidFeatureGeometry)
+        ));
+    }
+
+    #[test]
+    fn test_identify_domain_entity_single_feature() {
+        let marker = feature_from_json(
+            r#"{
+                "id": "m1",
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Versorgungspunkt" } },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+            }"#,
+        );
+        assert!(identify_domain_entity(marker).is_supply_point());
+
+        let unknown = feature_from_json(
+            r#"{ "type": "Feature", "properties": null, "geometry": null }"#,
+        );
+        assert!(identify_domain_entity(unknown).is_unknown());
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_supply_point() {
-        let geojson = serde_json::from_str(
+    fn test_identify_domain_entities_iter() {
+        let geojson: GeoJson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
@@ -863,15 +2977,20 @@ mod tests {
                     {
                         "id": "1",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Versorgungspunkt"
-                            }
-                        },
-                        "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
-                        }
+                        "properties": { "properties": { "objectId": "Kugelmarker" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "2",
+                        "type": "Feature",
+                        "properties": null,
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "3",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [1.0, 1.0] }
                     }
                 ]
             }
@@ -879,59 +2998,129 @@ mod tests {
         )
         .unwrap();
 
+        let known_ids: Vec<String> = identify_domain_entities_iter(geojson.clone())
+            .unwrap()
+            .filter(|entity| !entity.is_unknown())
+            .filter_map(|entity| entity.id().map(|id| id.to_string()))
+            .collect();
+        assert_eq!(known_ids, vec!["1".to_string(), "3".to_string()]);
+        // Unknown entities report the id of their feature
+        let all_ids: Vec<String> = identify_domain_entities_iter(geojson.clone())
+            .unwrap()
+            .filter_map(|entity| entity.id().map(|id| id.to_string()))
+            .collect();
+        assert_eq!(all_ids, vec!["1", "2", "3"]);
+        assert_eq!(
+            identify_domain_entities_iter(geojson.clone()).unwrap().count(),
+            indentify_domain_entities(geojson).unwrap().len()
+        );
+
+        let point = GeoJson::Geometry(Geometry::new(geojson::Value::Point(vec![0.0, 0.0])));
+        let entities: Vec<DomainEntity> = identify_domain_entities_iter(point).unwrap().collect();
+        assert_eq!(entities.len(), 1);
+        assert!(entities[0].is_unknown());
+    }
+
+    #[test]
+    fn test_identify_domain_entities_from_single_feature() {
+        let geojson: GeoJson = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Kugelmarker" } },
+                "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+            }"#,
+        )
+        .unwrap();
         let domain_entities = indentify_domain_entities(geojson).unwrap();
         assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_supply_point());
+        assert!(domain_entities[0].is_marker());
+
+        let geometry: GeoJson =
+            serde_json::from_str(r#"{ "type": "Point", "coordinates": [0.0, 0.0] }"#).unwrap();
+        let domain_entities = indentify_domain_entities(geometry).unwrap();
+        assert_eq!(domain_entities.len(), 1);
+        assert!(domain_entities[0].is_unknown());
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_operation_site() {
-        let geojson = serde_json::from_str(
-            r#"
-            {
-                "type": "FeatureCollection",
-                "features": [
-                    {
-                        "id": "1",
-                        "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Betriebsstelle"
-                            }
-                        },
-                        "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
-                        }
-                    }
-                ]
-            }
-            "#,
+    fn test_identify_entry_points_accept_single_feature() {
+        let geojson: GeoJson = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+            }"#,
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
+        let iterated: Vec<DomainEntity> =
+            identify_domain_entities_iter(geojson.clone()).unwrap().collect();
+        assert_eq!(iterated.len(), 1);
+        assert!(iterated[0].is_drilling_point());
+
+        let (domain_entities, report) = indentify_domain_entities_with_report(geojson).unwrap();
         assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_operation_site());
+        assert_eq!(report.count("DrillingPoint"), 1);
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_drilling_point() {
-        let geojson = serde_json::from_str(
+    #[allow(deprecated)]
+    fn test_indentify_domain_entity_alias() {
+        let feature: Feature = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "type": "Feature",
+                "properties": { "properties": { "objectId": "Kabelschacht" } },
+                "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+            }"#,
+        )
+        .unwrap();
+
+        let domain_entity = indentify_domain_entity(feature.clone());
+        assert!(domain_entity.is_cable_tunnel());
+        assert_eq!(
+            domain_entity.type_name(),
+            identify_domain_entity(feature).type_name()
+        );
+    }
+
+    #[test]
+    fn test_identify_domain_entities_with_report() {
+        let geojson: GeoJson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
                 "features": [
                     {
-                        "id": "1",
+                        "id": "marker",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Bohrpunkt"
-                            }
-                        },
-                        "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
-                        }
+                        "properties": { "properties": { "objectId": "Kugelmarker" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "no-geometry",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": null
+                    },
+                    {
+                        "id": "bad-inner",
+                        "type": "Feature",
+                        "properties": { "properties": "{not json" },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Mast" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "open-building",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Gebaeude" } },
+                        "geometry": { "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] }
                     }
                 ]
             }
@@ -939,13 +3128,37 @@ mod tests {
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_drilling_point());
+        let (domain_entities, report) = indentify_domain_entities_with_report(geojson).unwrap();
+
+        assert_eq!(domain_entities.len(), 5);
+        assert_eq!(report.total(), 5);
+        assert_eq!(report.count("CapturedMarker"), 1);
+        assert_eq!(report.count("Unknown"), 4);
+        assert_eq!(report.count("Building"), 0);
+        assert_eq!(
+            report.unknown_ids(),
+            vec!["no-geometry", "bad-inner", "open-building"]
+        );
+        assert_eq!(
+            report.unknowns[2],
+            (
+                EntityId::Missing,
+                UnknownReason::UnrecognizedObjectId("Mast".to_string())
+            )
+        );
+        let reasons = report.reason_counts();
+        assert_eq!(reasons[&UnknownReason::MissingGeometry], 1);
+        assert_eq!(reasons[&UnknownReason::UnparseableInnerProperties], 1);
+        assert_eq!(reasons[&UnknownReason::InvalidGeometryType], 1);
+
+        let summary = report.to_string();
+        assert!(summary.starts_with("5 features"));
+        assert!(summary.contains("Unknown (unrecognized objectId 'Mast'): 1"));
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_cable_tunnel() {
-        let geojson = serde_json::from_str(
+    fn test_identify_domain_entities_with_mapping() {
+        let geojson: GeoJson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
@@ -953,15 +3166,20 @@ mod tests {
                     {
                         "id": "1",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Kabelschacht"
-                            }
-                        },
-                        "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
-                        }
+                        "properties": { "properties": { "objectId": "ball_marker" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "2",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "KUGELMARKER" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "3",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "manhole" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
                     }
                 ]
             }
@@ -969,13 +3187,46 @@ mod tests {
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_cable_tunnel());
+        let mapping = ObjectIdMapping::default().with_alias("ball_marker", ObjectId::Kugelmarker);
+        let entities = indentify_domain_entities_with_mapping(geojson.clone(), &mapping).unwrap();
+        assert!(entities[0].is_marker());
+        assert!(entities[1].is_unknown());
+        assert!(entities[2].is_unknown());
+
+        let case_insensitive = mapping.case_insensitive(true);
+        let entities =
+            indentify_domain_entities_with_mapping(geojson.clone(), &case_insensitive).unwrap();
+        assert!(entities[1].is_marker());
+
+        // The default mapping reproduces the built-in identifiers
+        let default_entities =
+            indentify_domain_entities_with_mapping(geojson, &ObjectIdMapping::default()).unwrap();
+        assert!(default_entities.iter().all(DomainEntity::is_unknown));
+        let names = [
+            "Kugelmarker",
+            "Versorgungspunkt",
+            "Betriebsstelle",
+            "Bohrpunkt",
+            "Kabelschacht",
+            "Kabeltrasse",
+            "Kabel",
+            "Gebaeude",
+        ];
+        for name in names {
+            assert_eq!(
+                ObjectIdMapping::default().resolve(name).unwrap(),
+                ObjectId::try_from(name.to_string()).unwrap()
+            );
+        }
+        assert!(matches!(
+            ObjectIdMapping::default().resolve("gebaeude"),
+            Err(Error::InvalidObjectId(_))
+        ));
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_unknown() {
-        let geojson = serde_json::from_str(
+    fn test_identify_domain_entities_with_aliases() {
+        let geojson: GeoJson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
@@ -983,49 +3234,56 @@ mod tests {
                     {
                         "id": "1",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Unknown"
-                            }
-                        },
-                        "geometry": {
-                            "type": "Point",
-                            "coordinates": [0.0, 0.0]
-                        }
+                        "properties": { "properties": { "objectId": "ball_marker" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "2",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Bohrpunkt" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
                     }
                 ]
             }
             "#,
         )
         .unwrap();
+        let aliases = HashMap::from([("ball_marker".to_string(), ObjectId::Kugelmarker)]);
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_unknown());
+        let entities =
+            indentify_domain_entities_with_mapping(geojson.clone(), &(&aliases).into()).unwrap();
+        assert!(entities[0].is_marker());
+        assert!(entities[1].is_drilling_point());
+
+        let entities = indentify_domain_entities(geojson).unwrap();
+        assert!(entities[0].is_unknown());
+        assert!(entities[1].is_drilling_point());
     }
 
     #[test]
-    fn test_identify_domain_entity_with_line_string_geomtery() {
-        let geojson = serde_json::from_str(
+    fn test_identify_domain_entities_reporting_collects_parse_errors() {
+        let geojson: GeoJson = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
                 "features": [
                     {
-                        "id": "1",
+                        "id": "good",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Kabelschacht"
-                            }
-                        },
-                        "geometry": {
-                            "type": "LineString",
-                            "coordinates": [
-                                [0.0, 0.0],
-                                [1.0, 1.0]
-                            ]
-                        }
+                        "properties": { "properties": "{\"objectId\": \"Kugelmarker\"}" },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "bad",
+                        "type": "Feature",
+                        "properties": { "properties": "{not json" },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
+                    },
+                    {
+                        "id": "unrecognized",
+                        "type": "Feature",
+                        "properties": { "properties": { "objectId": "Mast" } },
+                        "geometry": { "type": "Point", "coordinates": [0.0, 0.0] }
                     }
                 ]
             }
@@ -1033,37 +3291,205 @@ mod tests {
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_unknown());
+        let (domain_entities, parse_errors) =
+            identify_domain_entities_reporting(geojson.clone()).unwrap();
+        assert_eq!(domain_entities.len(), 3);
+        assert!(domain_entities[0].is_marker());
+        assert!(domain_entities[1].is_unknown());
+        assert!(domain_entities[2].is_unknown());
+        // Only the parse failure is reported, not the unrecognized objectId
+        assert_eq!(parse_errors.len(), 1);
+        assert_eq!(parse_errors[0].0, Some(Id::String("bad".to_string())));
+        assert!(matches!(parse_errors[0].1, Error::Serialization(_)));
+
+        let plain = indentify_domain_entities(geojson).unwrap();
+        assert_eq!(plain.len(), domain_entities.len());
     }
+
     #[test]
-    fn test_indentify_domain_entity_with_polygon_geomtery() {
-        let geojson = serde_json::from_str(
+    fn test_identify_cable_route() {
+        let cable_route = |geometry: &str| {
+            feature_from_json(&format!(
+                r#"{{ "id": "route", "type": "Feature",
+                     "properties": {{ "properties": {{ "objectId": "Kabeltrasse" }} }},
+                     "geometry": {} }}"#,
+                geometry
+            ))
+        };
+
+        let open =
+            cable_route(r#"{ "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 1.0]] }"#);
+        let domain_entity = identify_domain_entity(open.clone());
+        assert!(domain_entity.is_cable_route());
+        assert_eq!(domain_entity.type_name(), "CableRoute");
+        let route = CableRoute::try_from(&open).unwrap();
+        assert_eq!(route.geometry, LineString::from(vec![(0.0, 0.0), (1.0, 1.0)]));
+        assert_eq!(Feature::from(&domain_entity).geometry, open.geometry);
+        assert!(matches!(
+            GeoGeometry::from(domain_entity),
+            GeoGeometry::LineString(_)
+        ));
+
+        let closed = cable_route(
+            r#"{ "type": "LineString", "coordinates": [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]] }"#,
+        );
+        assert!(identify_domain_entity(closed).is_cable_route());
+
+        let point = cable_route(r#"{ "type": "Point", "coordinates": [0.0, 0.0] }"#);
+        assert!(identify_domain_entity(point.clone()).is_unknown());
+        assert!(matches!(
+            DomainEntity::try_from(&point),
+            Err(Error::InvalidFeatureGeometry)
+        ));
+    }
+
+    fn marker_at(id: &str, x: f64, y: f64) -> DomainEntity {
+        DomainEntity::CapturedMarker(CapturedMarker {
+            id: id.into(),
+            geometry: Point::new(x, y),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        })
+    }
+
+    fn square_building(id: &str, min: f64, size: f64) -> DomainEntity {
+        let max = min + size;
+        DomainEntity::Building(Building {
+            id: id.into(),
+            geometry: GeoGeometry::Polygon(Polygon::new(
+                vec![(min, min), (max, min), (max, max), (min, max), (min, min)].into(),
+                vec![],
+            )),
+            original_inner_properties: Map::new(),
+            original_outer_properties: Map::new(),
+            foreign_members: None,
+        })
+    }
+
+    #[test]
+    fn test_domain_entity_collection_partition_by_kind() {
+        let collection = DomainEntityCollection::from(vec![
+            marker_at("m1", 0.0, 0.0),
+            square_building("b1", 0.0, 1.0),
+            DomainEntity::Unknown(Feature::default().into()),
+            marker_at("m2", 1.0, 1.0),
+        ]);
+
+        let marker_ids: Vec<String> = collection.markers().map(|m| m.id.to_string()).collect();
+        assert_eq!(marker_ids, vec!["m1", "m2"]);
+        assert_eq!(collection.buildings().count(), 1);
+        assert_eq!(collection.of_kind(EntityKind::Unknown).count(), 1);
+
+        let partitions = collection.partition_by_kind();
+        assert_eq!(partitions.len(), 3);
+        let markers = &partitions[&EntityKind::CapturedMarker];
+        assert_eq!(markers.len(), 2);
+        assert_eq!(markers[1].id(), Some(&EntityId::from("m2")));
+        assert_eq!(partitions[&EntityKind::Building].len(), 1);
+        assert_eq!(partitions[&EntityKind::Unknown].len(), 1);
+        assert!(!partitions.contains_key(&EntityKind::SupplyPoint));
+    }
+
+    #[test]
+    fn test_domain_entity_collection_retain_in_bbox() {
+        let mut collection = DomainEntityCollection::from(vec![
+            marker_at("inside", 1.0, 1.0),
+            marker_at("outside", 20.0, 1.0),
+            square_building("contained", 2.0, 1.0),
+            // Crosses the right edge of the rect
+            square_building("crossing", 9.5, 1.0),
+            DomainEntity::Unknown(Feature::default().into()),
+        ]);
+
+        collection.retain_in_bbox(Rect::new((0.0, 0.0), (10.0, 10.0)));
+
+        let ids: Vec<String> = collection
+            .entities()
+            .iter()
+            .filter_map(|entity| entity.id().map(|id| id.to_string()))
+            .collect();
+        assert_eq!(ids, vec!["inside", "contained"]);
+        assert_eq!(collection.into_inner().len(), 2);
+    }
+
+    #[test]
+    fn test_identify_cable_and_round_trip() {
+        let input = feature_from_json(
+            r#"{ "id": 7, "type": "Feature",
+                 "properties": { "properties": { "objectId": "Kabel", "voltage": "20kV" } },
+                 "geometry": { "type": "LineString",
+                               "coordinates": [[9.0, 50.0], [9.5, 50.5], [10.0, 50.0]] } }"#,
+        );
+
+        let domain_entity = identify_domain_entity(input.clone());
+        assert!(domain_entity.is_cable());
+        assert_eq!(domain_entity.kind(), EntityKind::Cable);
+        let cable = Cable::try_from(&input).unwrap();
+        assert_eq!(cable.id, EntityId::Number(serde_json::Number::from(7)));
+        assert_eq!(cable.geometry.0.len(), 3);
+
+        let output = Feature::from(&domain_entity);
+        assert_eq!(output.id, input.id);
+        assert_eq!(output.geometry, input.geometry);
+        assert_eq!(output.bbox, Some(vec![9.0, 50.0, 10.0, 50.5]));
+        assert_eq!(output.properties.unwrap()["properties"]["voltage"], Value::from("20kV"));
+        assert_eq!(
+            GeoGeometry::from(domain_entity),
+            GeoGeometry::LineString(cable.geometry)
+        );
+
+        let polygon = feature_from_json(
+            r#"{ "type": "Feature", "properties": { "properties": { "objectId": "Kabel" } },
+                 "geometry": { "type": "Polygon",
+                               "coordinates": [[[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 0.0]]] } }"#,
+        );
+        assert!(identify_domain_entity(polygon.clone()).is_unknown());
+        assert!(matches!(
+            Cable::try_from(&polygon),
+            Err(Error::InvalidFeatureGeometry)
+        ));
+    }
+
+    #[test]
+    fn test_object_id_as_str_round_trip() {
+        for object_id in [
+            ObjectId::Kugelmarker,
+            ObjectId::Versorgungspunkt,
+            ObjectId::Betriebsstelle,
+            ObjectId::Bohrpunkt,
+            ObjectId::Kabelschacht,
+            ObjectId::Kabeltrasse,
+            ObjectId::Kabel,
+            ObjectId::Building,
+        ] {
+            assert_eq!(ObjectId::try_from(object_id.as_str().to_string()).unwrap(), object_id);
+        }
+    }
+
+    #[test]
+    fn test_flatten_object_id() {
+        let fc: FeatureCollection = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
                 "features": [
                     {
-                        "id": "1",
                         "type": "Feature",
                         "properties": {
+                            "name": "a",
                             "properties": {
-                                "objectId": "Gebaeude"
+                                "objectId": "Kugelmarker"
                             }
                         },
-                        "geometry": {
-                            "type": "Polygon",
-                            "coordinates": [
-                                [
-                                    [0.0, 0.0],
-                                    [1.0, 1.0],
-                                    [1.0, 0.0],
-                                    [0.0, 1.0],
-                                    [0.0, 0.0]
-                                ]
-                            ]
-                        }
+                        "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": {
+                            "properties": "{\"objectId\": \"Bohrpunkt\"}"
+                        },
+                        "geometry": { "type": "Point", "coordinates": [3.0, 4.0] }
                     }
                 ]
             }
@@ -1071,39 +3497,34 @@ mod tests {
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_building());
+        let flattened = flatten_object_id(&fc);
+        assert_eq!(flattened.features.len(), 2);
+
+        let first = flattened.features[0].properties.as_ref().unwrap();
+        assert_eq!(first.get("objectId"), Some(&Value::from("Kugelmarker")));
+        assert_eq!(first.get("name"), Some(&Value::from("a")));
+        assert!(first.get("properties").unwrap().is_object());
+
+        let second = flattened.features[1].properties.as_ref().unwrap();
+        assert_eq!(second.get("objectId"), Some(&Value::from("Bohrpunkt")));
+        assert!(second.get("properties").unwrap().is_string());
     }
     #[test]
-    fn test_identify_domain_entity_with_multi_polygon_geomtery() {
-        let geojson = serde_json::from_str(
+    fn test_flatten_object_id_without_inner_properties() {
+        let fc: FeatureCollection = serde_json::from_str(
             r#"
             {
                 "type": "FeatureCollection",
                 "features": [
                     {
-                        "id": "1",
                         "type": "Feature",
-                        "properties": {
-                            "properties": {
-                                "objectId": "Gebaeude"
-                            }
-                        },
-                        "geometry": {
-                            "type": "MultiPolygon",
-                            "coordinates": [
-                                [
-                                    [
-                                        [0.0, 0.0],
-                                        [1.0, 1.0],
-                                        [1.0, 0.0],
-                                        [0.0, 1.0],
-                                        [0.0, 0.0]
-                                    ]
-                                ]
-                            ]
-                        }
+                        "properties": { "name": "a" },
+                        "geometry": { "type": "Point", "coordinates": [1.0, 2.0] }
+                    },
+                    {
+                        "type": "Feature",
+                        "properties": null,
+                        "geometry": null
                     }
                 ]
             }
@@ -1111,8 +3532,7 @@ mod tests {
         )
         .unwrap();
 
-        let domain_entities = indentify_domain_entities(geojson).unwrap();
-        assert_eq!(domain_entities.len(), 1);
-        assert!(domain_entities[0].is_building());
+        let flattened = flatten_object_id(&fc);
+        assert_eq!(flattened, fc);
     }
 }
@@ -1,6 +1,7 @@
 use crate::generation::generate_complex_sample::generate_synthetic_complex_featurecollection;
 use crate::generation::generate_closeness_sample::generate_synthetic_data_concatenate_seeded;
 use crate::generation::generate_curves_sample::generate_synthetic_linestrings;
+use crate::generation::generate_random_sample::{GenConfig, generate_synthetic_data_seeded};
 use std::fs::File;
 use std::io::Write;
 
@@ -8,6 +9,7 @@ pub fn generate_synthetic_data() -> Result<(), Box<dyn std::error::Error>> {
     generate_synthetic_data_curve_test()?;
     generate_synthetic_complex_collection_data()?;
     generate_deterministic_data_concatenate_seeded()?;
+    generate_random_mixed_data()?;
     Ok(())
 }
 
@@ -89,3 +91,33 @@ fn generate_deterministic_data_concatenate_seeded() -> Result<(), Box<dyn std::e
 
     Ok(())
 }
+
+fn generate_random_mixed_data() -> Result<(), Box<dyn std::error::Error>> {
+    let features = vec![100, 1_000, 10_000, 100_000];
+    // Printed so a run can be reproduced with generate_synthetic_data_seeded
+    let seed: u64 = rand::random();
+    println!("Generating random data with seed {}", seed);
+
+    for num_features in features {
+        println!("Generating {} features...", num_features);
+        let config = GenConfig {
+            num_features,
+            ..Default::default()
+        };
+        let feature_collection = generate_synthetic_data_seeded(seed, config);
+        println!("Generation complete. Serializing to JSON...");
+
+        let geojson_string = serde_json::to_string(&feature_collection)?;
+
+        let file_path = format!(
+            "random_data_mixed_featurecollection_{}k_features.geojson",
+            num_features / 1000
+        );
+        let mut file = File::create(&file_path)?;
+        file.write_all(geojson_string.as_bytes())?;
+
+        println!("Data saved to {}", file_path);
+    }
+
+    Ok(())
+}
@@ -110,7 +110,7 @@ pub fn generate_synthetic_complex_featurecollection(
                 // LineString
                 // Deterministically generate between 2 and 10 points.
                 // Ensure 2 points occur predictably (e.g., every 10th feature of this type)
-                let num_points = if (i / NUM_GEOM_TYPES) % 10 == 0 {
+                let num_points = if (i / NUM_GEOM_TYPES).is_multiple_of(10) {
                     2 // Deterministically generate 2 points for testing <3 unique points
                 } else {
                     // Deterministically vary point count between 3 and 10
@@ -139,7 +139,7 @@ pub fn generate_synthetic_complex_featurecollection(
                 // MultiPoint
                 // Deterministically generate between 1 and 10 points.
                 // Ensure 1 or 2 points occur predictably (e.g., every 10th feature of this type)
-                let num_points = if (i / NUM_GEOM_TYPES) % 10 == 0 {
+                let num_points = if (i / NUM_GEOM_TYPES).is_multiple_of(10) {
                      1 + (i % 2) // Deterministically 1 or 2 points
                 } else {
                     // Deterministically vary point count between 3 and 10
@@ -151,7 +151,7 @@ pub fn generate_synthetic_complex_featurecollection(
                     .collect();
 
                 // Deterministically add duplicate points (e.g., every 5th feature of this type)
-                if (i / NUM_GEOM_TYPES) % 5 == 0 && num_points > 0 {
+                if (i / NUM_GEOM_TYPES).is_multiple_of(5) && num_points > 0 {
                     let num_dupes = 1 + (i % 3); // Deterministically add 1 to 3 duplicates
                     for k in 0..num_dupes {
                         // Deterministically select index to duplicate
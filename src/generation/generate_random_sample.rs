@@ -0,0 +1,318 @@
+use geojson::{Feature, FeatureCollection, Geometry, Value};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde_json::json;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+
+use crate::domain::ObjectId;
+use crate::utils::helpers::GERMANY_BBOX;
+
+/// Relative weights of the geometry types `generate_synthetic_data_seeded` produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GeometryMix {
+    pub points: u32,
+    pub line_strings: u32,
+    pub polygons: u32,
+}
+
+impl Default for GeometryMix {
+    fn default() -> Self {
+        Self {
+            points: 1,
+            line_strings: 1,
+            polygons: 1,
+        }
+    }
+}
+
+/// Configuration for `generate_synthetic_data_seeded`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GenConfig {
+    /// The number of features to generate.
+    pub num_features: usize,
+    /// The relative weights of the geometry types.
+    pub geometry_mix: GeometryMix,
+    /// The region all coordinates lie in, as `[min_x, min_y, max_x, max_y]`.
+    pub region: [f64; 4],
+    /// Relative weights of the objectIds to tag features with. If set, it
+    /// replaces `geometry_mix`: every feature gets an objectId and the geometry
+    /// type that objectId is identified with. Empty by default.
+    pub object_id_weights: HashMap<ObjectId, f64>,
+}
+
+impl Default for GenConfig {
+    fn default() -> Self {
+        Self {
+            num_features: 1_000,
+            geometry_mix: GeometryMix::default(),
+            region: GERMANY_BBOX,
+            object_id_weights: HashMap::new(),
+        }
+    }
+}
+
+/// The geometry type of a generated feature.
+#[derive(Clone, Copy)]
+enum Shape {
+    Point,
+    LineString,
+    Polygon,
+}
+
+impl From<ObjectId> for Shape {
+    fn from(object_id: ObjectId) -> Self {
+        match object_id {
+            ObjectId::Building => Shape::Polygon,
+            ObjectId::Kabeltrasse | ObjectId::Kabel => Shape::LineString,
+            _ => Shape::Point,
+        }
+    }
+}
+
+/// Generates random Points, LineStrings and Polygons inside `config.region`.
+///
+/// The same seed and config always produce the same collection. LineStrings
+/// have 2 to 10 vertices and Polygons are closed rings with 3 to 8 vertices,
+/// both spanning at most a hundredth of the region. Features get the ids
+/// `feature_{i}`. A geometry mix or objectId weights that are all zero
+/// generate no features.
+///
+/// With `config.object_id_weights` set, each feature carries an objectId in
+/// the nested `properties` the domain identification reads, with Points for
+/// point entities, LineStrings for Kabeltrasse and Kabel, and Polygons for
+/// Gebaeude.
+///
+/// # Arguments
+/// * `seed` - The seed of the random number generator.
+/// * `config` - The number of features, geometry mix, region and objectId weights.
+///
+/// # Returns
+/// A `FeatureCollection` containing the generated features.
+pub fn generate_synthetic_data_seeded(seed: u64, config: GenConfig) -> FeatureCollection {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mix = config.geometry_mix;
+    let total_weight = mix.points + mix.line_strings + mix.polygons;
+    // Sorted, as the iteration order of the map would make the output differ between runs
+    let mut object_ids: Vec<(ObjectId, f64)> = config
+        .object_id_weights
+        .iter()
+        .filter(|(_, weight)| weight.is_finite() && **weight > 0.0)
+        .map(|(object_id, weight)| (*object_id, *weight))
+        .collect();
+    object_ids.sort_by_key(|(object_id, _)| object_id.as_str());
+    let total_object_id_weight: f64 = object_ids.iter().map(|(_, weight)| weight).sum();
+    let num_features = if config.object_id_weights.is_empty() {
+        if total_weight == 0 { 0 } else { config.num_features }
+    } else if object_ids.is_empty() {
+        0
+    } else {
+        config.num_features
+    };
+
+    let [min_x, min_y, max_x, max_y] = config.region;
+    // Extent of a single LineString or Polygon
+    let (shape_width, shape_height) = ((max_x - min_x) / 100.0, (max_y - min_y) / 100.0);
+    let clamp = |position: [f64; 2]| {
+        vec![
+            position[0].clamp(min_x, max_x),
+            position[1].clamp(min_y, max_y),
+        ]
+    };
+
+    let mut features: Vec<Feature> = Vec::with_capacity(num_features);
+    for i in 0..num_features {
+        let start = [
+            rng.gen_range(min_x..=max_x),
+            rng.gen_range(min_y..=max_y),
+        ];
+        let (shape, object_id) = if object_ids.is_empty() {
+            let pick = rng.gen_range(0..total_weight);
+            let shape = if pick < mix.points {
+                Shape::Point
+            } else if pick < mix.points + mix.line_strings {
+                Shape::LineString
+            } else {
+                Shape::Polygon
+            };
+            (shape, None)
+        } else {
+            let mut pick = rng.gen_range(0.0..total_object_id_weight);
+            let (object_id, _) = *object_ids
+                .iter()
+                .find(|(_, weight)| {
+                    pick -= weight;
+                    pick < 0.0
+                })
+                // Rounding can leave the pick just above the last weight
+                .unwrap_or(&object_ids[object_ids.len() - 1]);
+            (Shape::from(object_id), Some(object_id))
+        };
+        let value = match shape {
+            Shape::Point => Value::Point(clamp(start)),
+            Shape::LineString => {
+                let num_vertices = rng.gen_range(2..=10);
+                let coords = (0..num_vertices)
+                    .map(|_| {
+                        clamp([
+                            start[0] + rng.gen_range(0.0..=shape_width),
+                            start[1] + rng.gen_range(0.0..=shape_height),
+                        ])
+                    })
+                    .collect();
+                Value::LineString(coords)
+            }
+            Shape::Polygon => {
+                // Vertices at increasing angles around the start make a simple ring
+                let num_vertices = rng.gen_range(3..=8);
+                let mut angles: Vec<f64> =
+                    (0..num_vertices).map(|_| rng.gen_range(0.0..TAU)).collect();
+                angles.sort_by(f64::total_cmp);
+                let mut ring: Vec<Vec<f64>> = angles
+                    .into_iter()
+                    .map(|angle| {
+                        let radius = rng.gen_range(0.25..=0.5);
+                        clamp([
+                            start[0] + angle.cos() * radius * shape_width,
+                            start[1] + angle.sin() * radius * shape_height,
+                        ])
+                    })
+                    .collect();
+                ring.push(ring[0].clone());
+                Value::Polygon(vec![ring])
+            }
+        };
+        let properties = object_id.map(|object_id| {
+            let properties = json!({ "properties": { "objectId": object_id.as_str() } });
+            properties.as_object().cloned().unwrap_or_default()
+        });
+
+        features.push(Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(value)),
+            id: Some(geojson::feature::Id::String(format!("feature_{}", i))),
+            properties,
+            foreign_members: None,
+        });
+    }
+
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::{DomainEntity, indentify_domain_entities};
+    use geojson::GeoJson;
+
+    #[test]
+    fn test_same_seed_produces_identical_geojson() {
+        let config = GenConfig {
+            num_features: 200,
+            ..Default::default()
+        };
+
+        let first =
+            serde_json::to_string(&generate_synthetic_data_seeded(7, config.clone())).unwrap();
+        let second =
+            serde_json::to_string(&generate_synthetic_data_seeded(7, config.clone())).unwrap();
+        let other = serde_json::to_string(&generate_synthetic_data_seeded(8, config)).unwrap();
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn test_generated_features_follow_config() {
+        let config = GenConfig {
+            num_features: 100,
+            geometry_mix: GeometryMix {
+                points: 0,
+                line_strings: 0,
+                polygons: 1,
+            },
+            region: [0.0, 0.0, 10.0, 10.0],
+            ..Default::default()
+        };
+
+        let fc = generate_synthetic_data_seeded(1, config.clone());
+
+        assert_eq!(fc.features.len(), 100);
+        for feature in &fc.features {
+            match &feature.geometry.as_ref().unwrap().value {
+                Value::Polygon(rings) => {
+                    let ring = &rings[0];
+                    assert!(ring.len() >= 4);
+                    assert_eq!(ring.first(), ring.last());
+                    assert!(ring.iter().all(|p| (0.0..=10.0).contains(&p[0])
+                        && (0.0..=10.0).contains(&p[1])));
+                }
+                other => panic!("unexpected geometry {:?}", other),
+            }
+        }
+
+        let no_weights = GenConfig {
+            geometry_mix: GeometryMix {
+                points: 0,
+                line_strings: 0,
+                polygons: 0,
+            },
+            ..config
+        };
+        assert!(generate_synthetic_data_seeded(1, no_weights).features.is_empty());
+    }
+
+    #[test]
+    fn test_object_id_weights_are_identified_in_proportion() {
+        let weights = [
+            (ObjectId::Kugelmarker, 0.2),
+            (ObjectId::Kabelschacht, 0.1),
+            (ObjectId::Kabel, 0.3),
+            (ObjectId::Building, 0.4),
+        ];
+        let config = GenConfig {
+            num_features: 100,
+            object_id_weights: weights.into_iter().collect(),
+            ..Default::default()
+        };
+
+        let fc = generate_synthetic_data_seeded(3, config);
+        let entities = indentify_domain_entities(GeoJson::FeatureCollection(fc)).unwrap();
+
+        assert_eq!(entities.len(), 100);
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for entity in &entities {
+            let object_id = entity.object_id().expect("generated feature not identified");
+            *counts.entry(object_id).or_default() += 1;
+            match (object_id, entity) {
+                ("Gebaeude", DomainEntity::Building(building)) => {
+                    assert!(matches!(building.geometry, geo::Geometry::Polygon(_)))
+                }
+                ("Kugelmarker", DomainEntity::CapturedMarker(_))
+                | ("Kabelschacht", DomainEntity::CableTunnel(_))
+                | ("Kabel", DomainEntity::Cable(_)) => {}
+                other => panic!("unexpected identification {:?}", other),
+            }
+        }
+        for (object_id, weight) in weights {
+            let count = counts.get(object_id.as_str()).copied().unwrap_or_default();
+            assert!(
+                (count as f64 - weight * 100.0).abs() <= 15.0,
+                "{} generated {} times for weight {}",
+                object_id.as_str(),
+                count,
+                weight
+            );
+        }
+
+        let zero_weights = GenConfig {
+            object_id_weights: [(ObjectId::Kabel, 0.0)].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(generate_synthetic_data_seeded(3, zero_weights).features.is_empty());
+    }
+}
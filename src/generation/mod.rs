@@ -2,3 +2,4 @@ pub mod generate_complex_sample;
 pub mod generate_curves_sample;
 pub mod generate_closeness_sample;
 pub mod generate;
+pub mod generate_random_sample;
@@ -0,0 +1,77 @@
+// Projects features to their centroids for point-based spatial functions.
+
+use geo::Point;
+
+use crate::utils::geometry::GeoFeatureCollection;
+
+/// Calculates the centroid of every feature in the collection.
+///
+/// Features without a geometry, or whose geometry has no centroid (such as
+/// an empty LineString), are skipped.
+///
+/// # Arguments
+/// * `fc` - The feature collection to project.
+///
+/// # Returns
+/// The index of each feature in `fc.features` together with its centroid,
+/// in collection order.
+pub fn collection_to_centroid_points(fc: &GeoFeatureCollection) -> Vec<(usize, Point)> {
+    fc.features
+        .iter()
+        .enumerate()
+        .filter_map(|(index, feature)| {
+            let centroid = feature.geometry.as_ref()?.centroid()?;
+            Some((index, centroid))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::{GeoFeature, GeoGeometry};
+    use geo::{LineString, Polygon, coord};
+
+    fn feature(geometry: Option<GeoGeometry>) -> GeoFeature {
+        GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry,
+        }
+    }
+
+    #[test]
+    fn test_collection_to_centroid_points() {
+        let square = Polygon::new(
+            LineString::from(vec![
+                coord! { x: 0.0, y: 0.0 },
+                coord! { x: 2.0, y: 0.0 },
+                coord! { x: 2.0, y: 2.0 },
+                coord! { x: 0.0, y: 2.0 },
+                coord! { x: 0.0, y: 0.0 },
+            ]),
+            vec![],
+        );
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature(Some(GeoGeometry::Point(Point::new(5.0, 6.0)))),
+                feature(None),
+                feature(Some(GeoGeometry::LineString(LineString::new(vec![])))),
+                feature(Some(GeoGeometry::Polygon(square))),
+            ],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            collection_to_centroid_points(&fc),
+            vec![(0, Point::new(5.0, 6.0)), (3, Point::new(1.0, 1.0))]
+        );
+    }
+
+    #[test]
+    fn test_collection_to_centroid_points_empty() {
+        assert!(collection_to_centroid_points(&GeoFeatureCollection::default()).is_empty());
+    }
+}
@@ -0,0 +1,152 @@
+// Options shared by the bounding box and convex hull collectors.
+
+use geo::{Coord, Polygon, Rect};
+
+use crate::utils::geometry::GeoGeometry;
+use crate::utils::helpers::{GERMANY_BBOX, InBoundingBox, geometry_in_polygon};
+pub use crate::utils::helpers::germany_boundary;
+
+/// How precisely the collectors check features against the filter.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum FilterPrecision {
+    /// Only check against `CollectOptions::filter_bbox`.
+    #[default]
+    Bbox,
+    /// Also require every coordinate to lie within the polygon, e.g.
+    /// `germany_boundary()`. The bbox check still runs first as a fast
+    /// pre-filter.
+    Polygon(&'static Polygon),
+}
+
+/// Options for collecting bounding boxes and convex hulls.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CollectOptions {
+    /// Only features entirely within this rectangle, in EPSG:4326, are
+    /// collected. `None` disables geographic filtering.
+    pub filter_bbox: Option<Rect>,
+    /// Maximum number of unique hulls the convex hull collector may return.
+    /// `None` disables the limit.
+    pub max_outputs: Option<usize>,
+    /// Distance in meters the hull collector grows each hull by, measured in
+    /// EPSG:3035. `0.0` returns the hulls unchanged.
+    pub buffer_meters: f64,
+    /// Grid size coordinates are snapped to before the hull collector compares
+    /// hulls, so near-identical hulls are merged. `0.0` compares exactly.
+    pub dedup_tolerance: f64,
+    /// Whether the hull collector computes one hull per polygon of a
+    /// MultiPolygon and per line of a MultiLineString instead of one per feature.
+    pub per_part_hulls: bool,
+    /// Whether features are also checked against a precise boundary polygon.
+    pub filter_precision: FilterPrecision,
+}
+
+impl Default for CollectOptions {
+    /// Filters by the Germany bounding box, matching the original behavior.
+    fn default() -> Self {
+        Self {
+            filter_bbox: Some(germany_rect()),
+            max_outputs: None,
+            buffer_meters: 0.0,
+            dedup_tolerance: 0.0,
+            per_part_hulls: false,
+            filter_precision: FilterPrecision::Bbox,
+        }
+    }
+}
+
+impl CollectOptions {
+    /// Options that collect every feature regardless of location.
+    pub fn unfiltered() -> Self {
+        Self {
+            filter_bbox: None,
+            max_outputs: None,
+            buffer_meters: 0.0,
+            dedup_tolerance: 0.0,
+            per_part_hulls: false,
+            filter_precision: FilterPrecision::Bbox,
+        }
+    }
+
+    /// Options that filter by the given rectangle.
+    pub fn with_filter_bbox(filter_bbox: Rect) -> Self {
+        Self {
+            filter_bbox: Some(filter_bbox),
+            max_outputs: None,
+            buffer_meters: 0.0,
+            dedup_tolerance: 0.0,
+            per_part_hulls: false,
+            filter_precision: FilterPrecision::Bbox,
+        }
+    }
+
+    /// Limits the number of unique hulls the convex hull collector may return.
+    pub fn with_max_outputs(mut self, max_outputs: usize) -> Self {
+        self.max_outputs = Some(max_outputs);
+        self
+    }
+
+    /// Grows every collected hull by the given distance in meters.
+    pub fn with_buffer_meters(mut self, buffer_meters: f64) -> Self {
+        self.buffer_meters = buffer_meters;
+        self
+    }
+
+    /// Merges hulls whose coordinates agree when snapped to the given grid size.
+    pub fn with_dedup_tolerance(mut self, dedup_tolerance: f64) -> Self {
+        self.dedup_tolerance = dedup_tolerance;
+        self
+    }
+
+    /// Computes one hull per part of multi-part geometries.
+    pub fn with_per_part_hulls(mut self, per_part_hulls: bool) -> Self {
+        self.per_part_hulls = per_part_hulls;
+        self
+    }
+
+    /// Returns the filter rectangle as a `[min_x, min_y, max_x, max_y]` array.
+    pub(crate) fn filter_bbox_array(&self) -> Option<[f64; 4]> {
+        self.filter_bbox
+            .map(|rect| [rect.min().x, rect.min().y, rect.max().x, rect.max().y])
+    }
+
+    /// Also checks features against the given boundary polygon.
+    pub fn with_filter_precision(mut self, filter_precision: FilterPrecision) -> Self {
+        self.filter_precision = filter_precision;
+        self
+    }
+
+    /// Whether features are checked against a filter rectangle or polygon.
+    pub(crate) fn has_filter(&self) -> bool {
+        self.filter_bbox.is_some() || self.filter_precision != FilterPrecision::Bbox
+    }
+
+    /// Checks a value against the filter; always true without a filter.
+    pub(crate) fn accepts<T: InBoundingBox + ?Sized>(&self, value: &T) -> bool {
+        self.filter_bbox_array()
+            .is_none_or(|bbox| value.in_bounding_box(&bbox))
+    }
+
+    /// Checks a geometry against the filter bbox and, with
+    /// `FilterPrecision::Polygon`, the boundary polygon.
+    pub(crate) fn accepts_geometry(&self, geometry: &GeoGeometry) -> bool {
+        self.accepts(geometry)
+            && match self.filter_precision {
+                FilterPrecision::Bbox => true,
+                FilterPrecision::Polygon(polygon) => geometry_in_polygon(geometry, polygon),
+            }
+    }
+}
+
+/// Returns the Germany bounding box as a geo::Rect.
+pub fn germany_rect() -> Rect {
+    Rect::new(
+        Coord {
+            x: GERMANY_BBOX[0],
+            y: GERMANY_BBOX[1],
+        },
+        Coord {
+            x: GERMANY_BBOX[2],
+            y: GERMANY_BBOX[3],
+        },
+    )
+}
@@ -3,12 +3,23 @@
 use crate::utils::error::Error;
 use geo::algorithm::convex_hull::ConvexHull;
 use geo::geometry::{LineString as GeoLineString, MultiPoint};
-use geo::{BoundingRect, Coord, Intersects, Point, Rect};
+use geo::orient::{Direction, Orient};
+use geo::{
+    Area, BoundingRect, ConcaveHull, Coord, GeodesicArea, MapCoords, Point, Rect,
+    unary_union,
+};
+use geojson::{Feature, FeatureCollection, Geometry, JsonObject, JsonValue, feature::Id};
 use ordered_float::OrderedFloat;
-use std::collections::HashSet;
+use proj::Proj;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::TAU;
 
+use crate::geometry::collect_options::CollectOptions;
+use crate::geometry::crs::source_crs;
 use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
-use crate::utils::utils::{GERMANY_BBOX, InBoundingBox};
+use crate::utils::transformer::Transformer;
+use crate::utils::helpers::{IntersectsBoundingBox, union_bboxes};
 
 /// Creates a canonical representation of polygon points for hashing purposes.
 ///
@@ -39,39 +50,46 @@ fn canonical_hull_unique_sorted_points(
     sorted_unique
 }
 
-/// Processes a single GeoJSON feature to extract either its convex hull or a
+/// Processes a single GeoJSON feature to extract either its hull or a
 /// bounding box polygon if it's within the specified Germany boundaries.
 ///
 /// Filters by feature bbox, extracts coordinates based on geometry type, checks
-/// if all points are within Germany, and generates either a convex hull (>= 3
-/// unique points) or a bounding box polygon (< 3 unique points).
+/// if all points are within Germany, and generates either a hull of the given
+/// kind (>= 3 unique points) or a bounding box polygon (< 3 unique points).
 ///
 /// # Arguments
 /// * `feature` - The GeoJSON feature to process.
-/// * `germany_rect` - The bounding rectangle for Germany for intersection checks.
+/// * `kind` - Whether to compute a convex or concave hull.
+/// * `options` - The options holding the filter rectangle.
 ///
 /// # Returns
-/// `Some(geo::Polygon)` containing the generated hull or bounding box polygon
-/// if the feature meets the criteria, otherwise `None`.
-fn process_single_feature(feature: &GeoFeature, germany_rect: &Rect) -> Option<geo::Polygon> {
+/// The generated hull or bounding box polygon and how it was computed, or
+/// why the feature was skipped.
+fn process_single_feature_with_source(
+    feature: &GeoFeature,
+    kind: HullKind,
+    options: &CollectOptions,
+) -> Result<(geo::Polygon, HullSource), SkipReason> {
     // --- Early Filtering using Feature Bounding Box ---
-    // Check feature bbox intersection with Germany bbox
-    if let Some(feature_bbox_value) = &feature.bbox {
-        if feature_bbox_value.len() >= 4 {
-            let feature_rect = Rect::new(
-                Coord {
-                    x: feature_bbox_value[0],
-                    y: feature_bbox_value[1],
-                },
-                Coord {
-                    x: feature_bbox_value[2],
-                    y: feature_bbox_value[3],
-                },
-            );
+    // Check feature bbox intersection with the filter bbox
+    if let Some(filter_bbox) = options.filter_bbox_array()
+        && let Some(feature_bbox_value) = &feature.bbox
+        && feature_bbox_value.len() >= 4
+    {
+        let feature_rect = Rect::new(
+            Coord {
+                x: feature_bbox_value[0],
+                y: feature_bbox_value[1],
+            },
+            Coord {
+                x: feature_bbox_value[2],
+                y: feature_bbox_value[3],
+            },
+        );
 
-            if !feature_rect.intersects(germany_rect) {
-                return None; // Skip feature if its bbox doesn't intersect Germany
-            }
+        if !feature_rect.intersects_bounding_box(&filter_bbox) {
+            // Skip feature if its bbox doesn't intersect the filter
+            return Err(SkipReason::OutsideFilter);
         }
     }
 
@@ -79,143 +97,119 @@ fn process_single_feature(feature: &GeoFeature, germany_rect: &Rect) -> Option<g
     let geometry_value = match feature.geometry.as_ref() {
         Some(geometry) => geometry,
         None => {
-            return None; // Skip features without geometry
+            return Err(SkipReason::MissingGeometry); // Skip features without geometry
         }
     };
 
+    // --- Location Check ---
+    if !options.accepts_geometry(geometry_value) {
+        // Skip features that are not entirely within the filter bbox
+        return Err(SkipReason::OutsideFilter);
+    }
+
     let mut coords: Vec<Coord> = Vec::new();
-    let mut all_points_in_germany = true;
     let mut geometry_for_fallback_bbox: Option<GeoGeometry> = None; // Store geo::Geometry for fallback bbox calculation
 
-    // Extract coordinates, check location, and prepare geo::Geometry for fallback
+    // Extract coordinates and prepare geo::Geometry for fallback
     match geometry_value {
         GeoGeometry::Point(coord) => {
-            if coord.in_bounding_box(&GERMANY_BBOX) {
-                coords.push(Coord {
-                    x: coord.x(),
-                    y: coord.y(),
-                }); // Use for unique count check
-                geometry_for_fallback_bbox = Some(GeoGeometry::Point(Point::from(Coord {
-                    x: coord.x(),
-                    y: coord.y(),
-                })));
-            } else {
-                all_points_in_germany = false;
-            }
+            coords.push(Coord {
+                x: coord.x(),
+                y: coord.y(),
+            }); // Use for unique count check
+            geometry_for_fallback_bbox = Some(GeoGeometry::Point(Point::from(Coord {
+                x: coord.x(),
+                y: coord.y(),
+            })));
         }
         GeoGeometry::LineString(line_coords) => {
-            if !line_coords.in_bounding_box(&GERMANY_BBOX) {
-                all_points_in_germany = false;
-            } else {
-                coords.extend(
-                    line_coords
-                        .coords()
-                        .map(|c| Coord { x: c.x, y: c.y })
-                        .collect::<Vec<Coord>>(),
-                ); // Use for unique count check
-                geometry_for_fallback_bbox = Some(GeoGeometry::LineString(line_coords.clone())); // Create geo::LineString for bbox
-            }
+            coords.extend(
+                line_coords
+                    .coords()
+                    .map(|c| Coord { x: c.x, y: c.y })
+                    .collect::<Vec<Coord>>(),
+            ); // Use for unique count check
+            geometry_for_fallback_bbox = Some(GeoGeometry::LineString(line_coords.clone())); // Create geo::LineString for bbox
         }
         GeoGeometry::Polygon(polygon_coords) => {
             // Extract coords from exterior ring; interior rings don't affect convex hull
-            if !polygon_coords.in_bounding_box(&GERMANY_BBOX) {
-                all_points_in_germany = false;
-            } else {
-                let exterior_ring_geo_coords: Vec<Coord> = polygon_coords
-                    .exterior()
-                    .coords()
-                    .map(|c| Coord { x: c.x, y: c.y })
-                    .collect();
-
-                coords.extend(&exterior_ring_geo_coords); // Use for unique count check
-                // Store the polygon geometry for potential fallback bbox if needed (though convex hull is primary for polygons)
-                // Note: Constructing a full geo::Polygon from geojson Vec<Vec<Vec<f64>>> is more complex,
-                // relying on extracted points for hull/bbox is simpler here.
-                // If needing the full polygon for fallback, you'd parse the interior rings too.
-                // Let's rely on extracted coords for unique count and hull/bbox.
-                if !exterior_ring_geo_coords.is_empty() {
-                    // Note: BoundingRect on Polygon includes interior rings if they exist.
-                    // For simplicity and focus on convex hull/outer bbox, we use extracted coords.
-                    // If a precise Polygon BoundingRect was strictly needed for fallback, a full geo::Polygon parse would be required.
-                    // Let's use the extracted coords for unique_count and rely on the logic below.
-                    // If fallback is needed, and the geometry_for_fallback_bbox wasn't set (e.g., for Polygon/MultiPolygon),
-                    // we might fallback to using MultiPoint::from(coords).bounding_rect()
-                }
+            let exterior_ring_geo_coords: Vec<Coord> = polygon_coords
+                .exterior()
+                .coords()
+                .map(|c| Coord { x: c.x, y: c.y })
+                .collect();
+
+            coords.extend(&exterior_ring_geo_coords); // Use for unique count check
+            // Store the polygon geometry for potential fallback bbox if needed (though convex hull is primary for polygons)
+            // Note: Constructing a full geo::Polygon from geojson Vec<Vec<Vec<f64>>> is more complex,
+            // relying on extracted points for hull/bbox is simpler here.
+            // If needing the full polygon for fallback, you'd parse the interior rings too.
+            // Let's rely on extracted coords for unique count and hull/bbox.
+            if !exterior_ring_geo_coords.is_empty() {
+                // Note: BoundingRect on Polygon includes interior rings if they exist.
+                // For simplicity and focus on convex hull/outer bbox, we use extracted coords.
+                // If a precise Polygon BoundingRect was strictly needed for fallback, a full geo::Polygon parse would be required.
+                // Let's use the extracted coords for unique_count and rely on the logic below.
+                // If fallback is needed, and the geometry_for_fallback_bbox wasn't set (e.g., for Polygon/MultiPolygon),
+                // we might fallback to using MultiPoint::from(coords).bounding_rect()
             }
         }
         // Add other geometry types here (MultiPoint, MultiLineString, MultiPolygon)
-        // Extract their coordinates, check if in Germany, and if applicable,
+        // Extract their coordinates and, if applicable,
         // create the corresponding geo::Geometry value for geometry_for_fallback_bbox.
         GeoGeometry::MultiPoint(point_coords_vec) => {
-            if !point_coords_vec.in_bounding_box(&GERMANY_BBOX) {
-                all_points_in_germany = false;
-            } else {
-                let point_coords_vec_geo: Vec<Coord> = point_coords_vec
-                    .iter()
-                    .map(|c| Coord { x: c.x(), y: c.y() })
-                    .collect();
-
-                coords.extend(point_coords_vec_geo.clone()); // Use for unique count check
-                if !point_coords_vec_geo.is_empty() {
-                    geometry_for_fallback_bbox = Some(GeoGeometry::MultiPoint(MultiPoint::new(
-                        point_coords_vec_geo.into_iter().map(Point::from).collect(),
-                    )));
-                }
+            let point_coords_vec_geo: Vec<Coord> = point_coords_vec
+                .iter()
+                .map(|c| Coord { x: c.x(), y: c.y() })
+                .collect();
+
+            coords.extend(point_coords_vec_geo.clone()); // Use for unique count check
+            if !point_coords_vec_geo.is_empty() {
+                geometry_for_fallback_bbox = Some(GeoGeometry::MultiPoint(MultiPoint::new(
+                    point_coords_vec_geo.into_iter().map(Point::from).collect(),
+                )));
             }
         }
         GeoGeometry::MultiLineString(multiline_coords_vec) => {
-            if !multiline_coords_vec.in_bounding_box(&GERMANY_BBOX) {
-                all_points_in_germany = false;
-            } else {
-                let multiline_coords_vec_geo: Vec<Coord> = multiline_coords_vec
-                    .iter()
-                    .flat_map(|ls| {
-                        ls.coords()
-                            .map(|c| Coord { x: c.x, y: c.y })
-                            .collect::<Vec<Coord>>()
-                    })
-                    .collect();
-
-                coords.extend(multiline_coords_vec_geo.clone()); // Use for unique count check
-                // Creating a geo::MultiLineString from flattened coords is tricky,
-                // but we can use the flatten coords for convex hull/bbox.
-                // Or process each LineString in MultiLineString individually if preferred.
-                // For a simple bbox fallback, we can use the flattened coords.
-                if !multiline_coords_vec_geo.is_empty() {
-                    // Note: Using LineString for simplicity here, bbox is the same for flattened points
-                    geometry_for_fallback_bbox = Some(GeoGeometry::LineString(GeoLineString::new(
-                        multiline_coords_vec_geo,
-                    )));
-                }
+            let multiline_coords_vec_geo: Vec<Coord> = multiline_coords_vec
+                .iter()
+                .flat_map(|ls| {
+                    ls.coords()
+                        .map(|c| Coord { x: c.x, y: c.y })
+                        .collect::<Vec<Coord>>()
+                })
+                .collect();
+
+            coords.extend(multiline_coords_vec_geo.clone()); // Use for unique count check
+            // Creating a geo::MultiLineString from flattened coords is tricky,
+            // but we can use the flatten coords for convex hull/bbox.
+            // Or process each LineString in MultiLineString individually if preferred.
+            // For a simple bbox fallback, we can use the flattened coords.
+            if !multiline_coords_vec_geo.is_empty() {
+                // Note: Using LineString for simplicity here, bbox is the same for flattened points
+                geometry_for_fallback_bbox = Some(GeoGeometry::LineString(GeoLineString::new(
+                    multiline_coords_vec_geo,
+                )));
             }
         }
         GeoGeometry::MultiPolygon(multipolygon_coords_vec) => {
-            if !multipolygon_coords_vec.in_bounding_box(&GERMANY_BBOX) {
-                all_points_in_germany = false;
+            let all_exterior_coords: Vec<Coord> = multipolygon_coords_vec
+                .iter()
+                .flat_map(|poly| poly.exterior().coords().map(|c| Coord { x: c.x, y: c.y }))
+                .collect();
+
+            coords.extend(all_exterior_coords.clone()); // Use for unique count check
+            if !all_exterior_coords.is_empty() {
+                // For MultiPolygon, rely on extracted coords for unique count and hull/bbox
+                // A full geo::MultiPolygon parse for geometry_for_fallback_bbox is complex.
+                // We can fall back to using MultiPoint::from(coords).bounding_rect() if needed.
             } else {
-                let all_exterior_coords: Vec<Coord> = multipolygon_coords_vec
-                    .iter()
-                    .flat_map(|poly| poly.exterior().coords().map(|c| Coord { x: c.x, y: c.y }))
-                    .collect();
-
-                coords.extend(all_exterior_coords.clone()); // Use for unique count check
-                if !all_exterior_coords.is_empty() {
-                    // For MultiPolygon, rely on extracted coords for unique count and hull/bbox
-                    // A full geo::MultiPolygon parse for geometry_for_fallback_bbox is complex.
-                    // We can fall back to using MultiPoint::from(coords).bounding_rect() if needed.
-                } else {
-                    // MultiPolygon has no non-empty exterior rings
-                    all_points_in_germany = false; // Effectively skips this feature
-                }
+                // MultiPolygon has no non-empty exterior rings
+                return Err(SkipReason::EmptyGeometry);
             }
         }
     }
 
-    // --- Final Location Check ---
-    if !all_points_in_germany {
-        return None; // Skip features that were not entirely within Germany bbox
-    }
-
     // --- Check number of *unique* points derived from the geometry ---
     let unique_coords_count = coords
         .iter()
@@ -243,15 +237,303 @@ fn process_single_feature(feature: &GeoFeature, germany_rect: &Rect) -> Option<g
             })
             .map(|rect| rect.to_polygon()); // Convert the Rect to a Polygon
 
-        fallback_polygon // Return the generated polygon or None
+        // Return the generated polygon, or skip a feature without coordinates
+        fallback_polygon
+            .map(|polygon| (polygon, HullSource::BoundingBoxFallback))
+            .ok_or(SkipReason::EmptyGeometry)
     } else {
         // --- CONVEX HULL (Original Logic) ---
         // Compute the convex hull (use the original `coords` list which might have > unique_coords_count points)
         let multi_point = MultiPoint::from(coords);
-        let hull = multi_point.convex_hull(); // This will be a geo::Polygon because unique_coords_count >= 3
+        match kind {
+            // This will be a geo::Polygon because unique_coords_count >= 3
+            HullKind::Convex => Ok((multi_point.convex_hull(), HullSource::ConvexHull)),
+            HullKind::Concave { concavity } => Ok((
+                multi_point.concave_hull(concavity),
+                HullSource::ConcaveHull,
+            )),
+        }
+    }
+}
+
+/// Processes a feature like `process_single_feature_with_source`, splitting
+/// MultiPolygons and MultiLineStrings into one hull per part when
+/// `options.per_part_hulls` is set.
+///
+/// Each part is filtered on its own, so parts outside the filter are dropped
+/// while the remaining parts still produce hulls.
+///
+/// # Returns
+/// The hulls of the feature, or why no part produced a hull.
+fn process_feature_hulls(
+    feature: &GeoFeature,
+    kind: HullKind,
+    options: &CollectOptions,
+) -> Result<Vec<(geo::Polygon, HullSource)>, SkipReason> {
+    let parts: Vec<GeoGeometry> = match &feature.geometry {
+        Some(GeoGeometry::MultiPolygon(multi_polygon)) if options.per_part_hulls => multi_polygon
+            .iter()
+            .cloned()
+            .map(GeoGeometry::Polygon)
+            .collect(),
+        Some(GeoGeometry::MultiLineString(multi_line_string)) if options.per_part_hulls => {
+            multi_line_string
+                .iter()
+                .cloned()
+                .map(GeoGeometry::LineString)
+                .collect()
+        }
+        _ => {
+            return process_single_feature_with_source(feature, kind, options)
+                .map(|hull| vec![hull]);
+        }
+    };
+
+    let mut hulls = Vec::with_capacity(parts.len());
+    let mut first_skip_reason = None;
+    for part in parts {
+        let part_feature = GeoFeature {
+            id: None,
+            properties: None,
+            bbox: feature.bbox.clone(),
+            foreign_members: None,
+            geometry: Some(part),
+        };
+        match process_single_feature_with_source(&part_feature, kind, options) {
+            Ok(hull) => hulls.push(hull),
+            Err(reason) => {
+                first_skip_reason.get_or_insert(reason);
+            }
+        }
+    }
+    if hulls.is_empty() {
+        return Err(first_skip_reason.unwrap_or(SkipReason::EmptyGeometry));
+    }
+    Ok(hulls)
+}
+
+/// Creates a canonical representation of a polygon exterior that keeps the
+/// ring order, for hulls whose shape is not determined by their vertex set.
+///
+/// The ring is unclosed, rotated to start at its smallest point and walked in
+/// the direction with the smaller second point, so rings differing only in
+/// start point or orientation compare equal.
+///
+/// # Arguments
+/// * `hull` - The polygon for which to create the canonical representation.
+///
+/// # Returns
+/// A vector of tuples representing the exterior ring of the polygon.
+fn canonical_ring_points(hull: &geo::Polygon) -> Vec<(OrderedFloat<f64>, OrderedFloat<f64>)> {
+    let mut ring: Vec<(OrderedFloat<f64>, OrderedFloat<f64>)> = hull
+        .exterior()
+        .coords()
+        .map(|c| (OrderedFloat(c.x), OrderedFloat(c.y)))
+        .collect();
+    if ring.len() > 1 && ring.first() == ring.last() {
+        ring.pop();
+    }
+    let Some(start) = (0..ring.len()).min_by_key(|&i| ring[i]) else {
+        return ring;
+    };
+    ring.rotate_left(start);
+    let mut reversed = ring.clone();
+    reversed[1..].reverse();
+    ring.min(reversed)
+}
+
+/// Snaps every coordinate of a polygon to a grid of the given size.
+///
+/// Coordinates on either side of a grid line stay apart even when closer
+/// than `tolerance`; a non-positive tolerance returns the polygon unchanged.
+///
+/// # Arguments
+/// * `hull` - The polygon to snap.
+/// * `tolerance` - The grid size.
+///
+/// # Returns
+/// The snapped polygon.
+fn snap_polygon(hull: &geo::Polygon, tolerance: f64) -> geo::Polygon {
+    if tolerance <= 0.0 {
+        return hull.clone();
+    }
+    hull.map_coords(|coord| Coord {
+        x: (coord.x / tolerance).round() * tolerance,
+        y: (coord.y / tolerance).round() * tolerance,
+    })
+}
+
+/// Kind of hull computed for features with at least 3 unique points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HullKind {
+    /// The convex hull.
+    Convex,
+    /// The concave hull from `geo::ConcaveHull`; smaller concavity values
+    /// follow the points more closely.
+    Concave { concavity: f64 },
+}
+
+/// Why a feature did not produce a hull.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SkipReason {
+    /// The feature bbox or one of its coordinates lies outside the filter rectangle.
+    OutsideFilter,
+    /// The feature has no geometry.
+    MissingGeometry,
+    /// The geometry has no coordinates, e.g. an empty exterior ring.
+    EmptyGeometry,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::OutsideFilter => write!(f, "outside the filter bbox"),
+            SkipReason::MissingGeometry => write!(f, "missing geometry"),
+            SkipReason::EmptyGeometry => write!(f, "geometry without coordinates"),
+        }
+    }
+}
+
+/// A feature skipped by the hull collection.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkippedFeature {
+    /// Index of the feature in the input collection.
+    pub index: usize,
+    /// Id of the feature, if any.
+    pub id: Option<Id>,
+    /// Why the feature was skipped.
+    pub reason: SkipReason,
+}
+
+/// The features skipped by the hull collection, in input order.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SkipReport {
+    pub skipped: Vec<SkippedFeature>,
+}
+
+impl SkipReport {
+    /// Number of skipped features.
+    pub fn len(&self) -> usize {
+        self.skipped.len()
+    }
+
+    /// Whether no feature was skipped.
+    pub fn is_empty(&self) -> bool {
+        self.skipped.is_empty()
+    }
+
+    /// Number of features skipped for the given reason.
+    pub fn count(&self, reason: SkipReason) -> usize {
+        self.skipped
+            .iter()
+            .filter(|skipped| skipped.reason == reason)
+            .count()
+    }
+}
+
+/// Metric CRS hulls are buffered in.
+const BUFFER_CRS: &str = "EPSG:3035";
+
+/// Number of segments approximating the circle around each buffered vertex.
+const BUFFER_CIRCLE_SEGMENTS: usize = 32;
+
+/// Creates the transformer into and out of the metric buffer CRS.
+///
+/// # Arguments
+/// * `from_crs` - The CRS of the hulls.
+///
+/// # Returns
+/// The transformer between `from_crs` and `BUFFER_CRS`.
+fn buffer_transformer(from_crs: &str) -> Result<Transformer, Error> {
+    Transformer::new(from_crs, BUFFER_CRS).map_err(|err| Error::Projection(err.to_string()))
+}
+
+/// Transforms every coordinate of a polygon with a PROJ transformer.
+fn project_polygon(polygon: &geo::Polygon, transformer: &Proj) -> Result<geo::Polygon, Error> {
+    polygon.try_map_coords(|coord| {
+        transformer
+            .convert(coord)
+            .map_err(|err| Error::Projection(err.to_string()))
+    })
+}
+
+/// Approximates a circle around `center` with `BUFFER_CIRCLE_SEGMENTS` segments.
+fn circle_polygon(center: Coord, radius: f64) -> geo::Polygon {
+    let ring: Vec<Coord> = (0..=BUFFER_CIRCLE_SEGMENTS)
+        .map(|segment| {
+            let angle = TAU * segment as f64 / BUFFER_CIRCLE_SEGMENTS as f64;
+            Coord {
+                x: center.x + radius * angle.cos(),
+                y: center.y + radius * angle.sin(),
+            }
+        })
+        .collect();
+    geo::Polygon::new(GeoLineString::new(ring), vec![])
+}
+
+/// Creates the counter-clockwise rectangle covering everything within
+/// `radius` of a segment, except the round caps. Returns `None` for
+/// zero-length segments.
+fn segment_rectangle(start: Coord, end: Coord, radius: f64) -> Option<geo::Polygon> {
+    let direction = end - start;
+    let length = direction.x.hypot(direction.y);
+    if length == 0.0 {
+        return None;
+    }
+    let normal = Coord {
+        x: -direction.y / length * radius,
+        y: direction.x / length * radius,
+    };
+    Some(geo::Polygon::new(
+        GeoLineString::new(vec![
+            start - normal,
+            end - normal,
+            end + normal,
+            start + normal,
+            start - normal,
+        ]),
+        vec![],
+    ))
+}
 
-        Some(hull) // Return the computed hull
+/// Grows a hull by `meters` in the metric buffer CRS.
+///
+/// The buffer is the union of the projected hull, a circle around every
+/// vertex and a rectangle along every edge of its exterior, so concave hulls
+/// keep their shape and degenerate hulls (e.g. of a single point) grow into
+/// proper polygons.
+///
+/// # Arguments
+/// * `hull` - The hull to buffer.
+/// * `meters` - The buffer distance in meters.
+/// * `transformer` - The transformer between the CRS of the hull and a metric CRS.
+///
+/// # Returns
+/// The buffered hull in the CRS of the input hull.
+fn buffer_polygon(
+    hull: &geo::Polygon,
+    meters: f64,
+    transformer: &Transformer,
+) -> Result<geo::Polygon, Error> {
+    // The union counts windings, so every part is wound counter-clockwise
+    let projected = project_polygon(hull, transformer.forward())?.orient(Direction::Default);
+    let ring = &projected.exterior().0;
+    let mut parts: Vec<geo::Polygon> = Vec::with_capacity(ring.len() * 2 + 1);
+    if projected.unsigned_area() > 0.0 {
+        parts.push(projected.clone());
+    }
+    for (index, &start) in ring.iter().enumerate() {
+        parts.push(circle_polygon(start, meters));
+        if let Some(&end) = ring.get(index + 1) {
+            parts.extend(segment_rectangle(start, end, meters));
+        }
     }
+    let buffered = unary_union(&parts)
+        .0
+        .into_iter()
+        .max_by(|a, b| a.unsigned_area().total_cmp(&b.unsigned_area()))
+        .unwrap_or(projected);
+    project_polygon(&buffered, transformer.reverse())
 }
 
 /// Filters a vector of polygons to remove duplicates based on their
@@ -262,14 +544,80 @@ fn process_single_feature(feature: &GeoFeature, germany_rect: &Rect) -> Option<g
 ///
 /// # Returns
 /// A new vector containing only the unique polygons.
-fn deduplicate_polygons(hulls: Vec<geo::Polygon>) -> Vec<geo::Polygon> {
-    let mut unique_hulls: Vec<geo::Polygon> = Vec::with_capacity(hulls.len());
+pub fn deduplicate_polygons(hulls: Vec<geo::Polygon>) -> Vec<geo::Polygon> {
+    deduplicate_by_hull(hulls, |hull| hull)
+}
+
+/// Filters a vector of polygons like `deduplicate_polygons`, treating polygons
+/// as duplicates when their points agree after snapping to a grid of size
+/// `tolerance`. The first polygon of each group is kept unchanged.
+///
+/// # Arguments
+/// * `hulls` - The vector of polygons potentially containing duplicates.
+/// * `tolerance` - The grid size; `0.0` compares exactly.
+///
+/// # Returns
+/// A new vector containing only the unique polygons.
+pub fn deduplicate_polygons_with_tolerance(
+    hulls: Vec<geo::Polygon>,
+    tolerance: f64,
+) -> Vec<geo::Polygon> {
+    let mut seen_canonical_coords = HashSet::with_capacity(hulls.len());
+    hulls
+        .into_iter()
+        .filter(|hull| {
+            seen_canonical_coords
+                .insert(canonical_hull_unique_sorted_points(&snap_polygon(hull, tolerance)))
+        })
+        .collect()
+}
+
+/// Filters items carrying a polygon to remove duplicate polygons, keeping the
+/// first item seen for each canonical representation.
+///
+/// # Arguments
+/// * `items` - The items potentially containing duplicate polygons.
+/// * `hull_of` - Returns the polygon of an item.
+///
+/// # Returns
+/// A new vector containing only the items with unique polygons, in input order.
+fn deduplicate_by_hull<T>(items: Vec<T>, hull_of: impl Fn(&T) -> &geo::Polygon) -> Vec<T> {
+    let mut unique_items: Vec<T> = Vec::with_capacity(items.len());
     let mut seen_canonical_coords: HashSet<Vec<(OrderedFloat<f64>, OrderedFloat<f64>)>> =
-        HashSet::with_capacity(hulls.len());
+        HashSet::with_capacity(items.len());
+
+    for item in items {
+        let canonical_coords_hashable = canonical_hull_unique_sorted_points(hull_of(&item));
+
+        if seen_canonical_coords.insert(canonical_coords_hashable) {
+            unique_items.push(item);
+        }
+    }
+    unique_items
+}
+
+/// Parallel variant of `deduplicate_polygons` for large hull sets.
+///
+/// The expensive canonicalization runs on the rayon thread pool; the cheap
+/// set insertion then runs in input order, so the result is identical to
+/// the serial version, including the order of the kept polygons.
+///
+/// # Arguments
+/// * `hulls` - A vector of polygons that may contain duplicates.
+///
+/// # Returns
+/// A new vector containing only the unique polygons.
+pub fn deduplicate_polygons_parallel(hulls: Vec<geo::Polygon>) -> Vec<geo::Polygon> {
+    let keyed_hulls: Vec<_> = hulls
+        .into_par_iter()
+        .map(|hull| (canonical_hull_unique_sorted_points(&hull), hull))
+        .collect();
 
-    for hull in hulls {
-        let canonical_coords_hashable = canonical_hull_unique_sorted_points(&hull);
+    let mut unique_hulls: Vec<geo::Polygon> = Vec::with_capacity(keyed_hulls.len());
+    let mut seen_canonical_coords: HashSet<Vec<(OrderedFloat<f64>, OrderedFloat<f64>)>> =
+        HashSet::with_capacity(keyed_hulls.len());
 
+    for (canonical_coords_hashable, hull) in keyed_hulls {
         if seen_canonical_coords.insert(canonical_coords_hashable) {
             unique_hulls.push(hull);
         }
@@ -295,40 +643,381 @@ fn deduplicate_polygons(hulls: Vec<geo::Polygon>) -> Vec<geo::Polygon> {
 pub fn collect_convex_boundingboxes(
     featurecollection: &GeoFeatureCollection,
 ) -> Result<Vec<geo::Polygon>, Error> {
-    let germany_rect: Rect = Rect::new(
-        Coord {
-            x: 5.866211,
-            y: 47.270111,
-        },
-        Coord {
-            x: 15.013611,
-            y: 55.058333,
-        },
-    );
+    collect_convex_boundingboxes_with_options(featurecollection, &CollectOptions::default())
+}
+
+/// Collects convex bounding boxes like `collect_convex_boundingboxes`, filtering
+/// by `options.filter_bbox` instead of the Germany bounding box.
+///
+/// Duplicates are removed while collecting, so `options.max_outputs` bounds
+/// the number of hulls held in memory.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect bounding box polygons.
+/// * `options` - The collection options; a `None` filter keeps every feature.
+///
+/// # Returns
+/// A vector of unique polygons.
+/// # Errors
+/// Returns `Error::TooManyResults` with the number of unique hulls collected
+/// so far as soon as it exceeds `options.max_outputs`.
+pub fn collect_convex_boundingboxes_with_options(
+    featurecollection: &GeoFeatureCollection,
+    options: &CollectOptions,
+) -> Result<Vec<geo::Polygon>, Error> {
+    collect_hulls(featurecollection, HullKind::Convex, options)
+}
+
+/// Collects hulls of the given kind and fallback bounding box polygons from a
+/// geojson FeatureCollection.
+///
+/// Works like `collect_convex_boundingboxes_with_options`. Concave hulls are
+/// deduplicated by their exterior ring, since different concave hulls may
+/// share the same vertices.
+///
+/// With a positive `options.buffer_meters`, each hull is projected to
+/// EPSG:3035, grown by that distance and projected back to the source CRS
+/// before deduplication. A positive `options.dedup_tolerance` merges hulls
+/// that agree after snapping to a grid of that size.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect hull polygons.
+/// * `kind` - The kind of hull to compute.
+/// * `options` - The collection options; a `None` filter keeps every feature.
+///
+/// # Returns
+/// A vector of unique polygons.
+/// # Errors
+/// Returns `Error::TooManyResults` with the number of unique hulls collected
+/// so far as soon as it exceeds `options.max_outputs`, and `Error::Projection`
+/// if buffering fails to project a hull.
+pub fn collect_hulls(
+    featurecollection: &GeoFeatureCollection,
+    kind: HullKind,
+    options: &CollectOptions,
+) -> Result<Vec<geo::Polygon>, Error> {
+    collect_hulls_detailed(featurecollection, kind, options).map(|(hulls, _)| hulls)
+}
+
+/// Collects convex bounding boxes like `collect_convex_boundingboxes`, also
+/// reporting every feature that did not produce a hull and why.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect bounding box polygons.
+///
+/// # Returns
+/// A vector of unique polygons and the report of skipped features.
+pub fn collect_convex_boundingboxes_detailed(
+    featurecollection: &GeoFeatureCollection,
+) -> Result<(Vec<geo::Polygon>, SkipReport), Error> {
+    collect_hulls_detailed(featurecollection, HullKind::Convex, &CollectOptions::default())
+}
+
+/// Collects hulls like `collect_hulls`, also reporting every feature that did
+/// not produce a hull and why. Features whose hull duplicates an earlier one
+/// are not reported.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect hull polygons.
+/// * `kind` - The kind of hull to compute.
+/// * `options` - The collection options; a `None` filter keeps every feature.
+///
+/// # Returns
+/// A vector of unique polygons and the report of skipped features.
+/// # Errors
+/// See `collect_hulls`.
+pub fn collect_hulls_detailed(
+    featurecollection: &GeoFeatureCollection,
+    kind: HullKind,
+    options: &CollectOptions,
+) -> Result<(Vec<geo::Polygon>, SkipReport), Error> {
+    if options.buffer_meters > 0.0 {
+        let transformer =
+            buffer_transformer(&source_crs(featurecollection.foreign_members.as_ref()))?;
+        collect_hulls_buffered_with(featurecollection, kind, options, Some(&transformer))
+    } else {
+        collect_hulls_buffered_with(featurecollection, kind, options, None)
+    }
+}
+
+/// Collects hulls like `collect_hulls`, buffering them with an existing
+/// transformer instead of creating one per call.
+///
+/// Any `crs` member of the feature collection is ignored; its coordinates must
+/// be in `transformer.source_crs()`, and `options.buffer_meters` is measured in
+/// `transformer.metric_crs()`.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect hull polygons.
+/// * `kind` - The kind of hull to compute.
+/// * `options` - The collection options; a `None` filter keeps every feature.
+/// * `transformer` - The transformer between the source and a metric CRS.
+///
+/// # Returns
+/// A vector of unique polygons.
+/// # Errors
+/// See `collect_hulls`.
+pub fn collect_hulls_with_transformer(
+    featurecollection: &GeoFeatureCollection,
+    kind: HullKind,
+    options: &CollectOptions,
+    transformer: &Transformer,
+) -> Result<Vec<geo::Polygon>, Error> {
+    let transformer = (options.buffer_meters > 0.0).then_some(transformer);
+    collect_hulls_buffered_with(featurecollection, kind, options, transformer)
+        .map(|(hulls, _)| hulls)
+}
 
-    let mut raw_hulls: Vec<geo::Polygon> = Vec::new();
+/// Collects hulls like `collect_hulls_detailed`, buffering them with
+/// `transformer` if one is given.
+fn collect_hulls_buffered_with(
+    featurecollection: &GeoFeatureCollection,
+    kind: HullKind,
+    options: &CollectOptions,
+    transformer: Option<&Transformer>,
+) -> Result<(Vec<geo::Polygon>, SkipReport), Error> {
+    let canonical_points = match kind {
+        HullKind::Convex => canonical_hull_unique_sorted_points,
+        HullKind::Concave { .. } => canonical_ring_points,
+    };
+    let mut unique_hulls: Vec<geo::Polygon> = Vec::new();
+    let mut seen_canonical_coords: HashSet<Vec<(OrderedFloat<f64>, OrderedFloat<f64>)>> =
+        HashSet::new();
+    let mut report = SkipReport::default();
 
     // Iterate through features and process each one individually
-    for feature in &featurecollection.features {
-        if let Some(polygon) = process_single_feature(feature, &germany_rect) {
-            raw_hulls.push(polygon);
+    for (index, feature) in featurecollection.features.iter().enumerate() {
+        // Features that cannot produce a hull are recorded and skipped
+        let hulls = match process_feature_hulls(feature, kind, options) {
+            Ok(hulls) => hulls,
+            Err(reason) => {
+                report.skipped.push(SkippedFeature {
+                    index,
+                    id: feature.id.clone(),
+                    reason,
+                });
+                continue;
+            }
+        };
+        for (polygon, _) in hulls {
+            let polygon = match transformer {
+                Some(transformer) => buffer_polygon(&polygon, options.buffer_meters, transformer)?,
+                None => polygon,
+            };
+            // Deduplicate the collected polygons
+            let canonical_coords = if options.dedup_tolerance > 0.0 {
+                canonical_points(&snap_polygon(&polygon, options.dedup_tolerance))
+            } else {
+                canonical_points(&polygon)
+            };
+            if !seen_canonical_coords.insert(canonical_coords) {
+                continue;
+            }
+            unique_hulls.push(polygon);
+            if options
+                .max_outputs
+                .is_some_and(|max_outputs| unique_hulls.len() > max_outputs)
+            {
+                return Err(Error::TooManyResults(unique_hulls.len()));
+            }
         }
-        // Errors during processing a single feature are handled by returning None and skipping
     }
 
-    // Deduplicate the collected polygons
-    let unique_hulls = deduplicate_polygons(raw_hulls);
+    Ok((unique_hulls, report))
+}
 
-    // The current logic skips invalid features or geometries, so we always return Ok
-    // unless a truly unexpected error occurred, which is not currently modeled.
-    // Keeping Result<(), Error> signature for consistency if needed later.
-    Ok(unique_hulls)
+/// Collects convex bounding boxes like `collect_convex_boundingboxes`, paired
+/// with the id of the feature each hull came from.
+///
+/// When several features produce the same hull, the id of the first one is kept.
+/// Use `collect_convex_hull_results` for the ids of all of them.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect bounding box polygons.
+///
+/// # Returns
+/// A vector of `(feature id, polygon)` pairs with unique polygons.
+pub fn collect_convex_boundingboxes_with_ids(
+    featurecollection: &GeoFeatureCollection,
+) -> Result<Vec<(Option<Id>, geo::Polygon)>, Error> {
+    Ok(collect_convex_hull_results(featurecollection)?
+        .into_iter()
+        .map(|result| {
+            let first_feature = &featurecollection.features[result.feature_indices[0]];
+            (first_feature.id.clone(), result.polygon)
+        })
+        .collect())
+}
+
+/// How the polygon of a `HullResult` was computed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HullSource {
+    /// The convex hull of a feature with at least 3 unique points.
+    ConvexHull,
+    /// The concave hull of a feature with at least 3 unique points.
+    ConcaveHull,
+    /// The bounding box of a feature with fewer than 3 unique points.
+    BoundingBoxFallback,
+}
+
+impl HullSource {
+    /// Name of the source as written to the `source` property of hull features.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HullSource::ConvexHull => "convex_hull",
+            HullSource::ConcaveHull => "concave_hull",
+            HullSource::BoundingBoxFallback => "bbox_fallback",
+        }
+    }
+}
+
+/// A unique hull together with the features that produced it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HullResult {
+    /// The hull or fallback bounding box polygon.
+    pub polygon: geo::Polygon,
+    /// Indices of the features producing this polygon, in input order.
+    pub feature_indices: Vec<usize>,
+    /// Ids of the features producing this polygon; features without an id are skipped.
+    pub ids: Vec<Id>,
+    /// How the polygon was computed for the first feature producing it.
+    pub source: HullSource,
+}
+
+/// Collects convex bounding boxes like `collect_convex_boundingboxes`, keeping
+/// track of the features each unique hull came from.
+///
+/// When several features produce the same hull, their indices and ids are
+/// merged into one result.
+///
+/// # Arguments
+/// * `featurecollection` - The FeatureCollection from which to collect bounding box polygons.
+///
+/// # Returns
+/// A vector of hull results with unique polygons, in order of first occurrence.
+pub fn collect_convex_hull_results(
+    featurecollection: &GeoFeatureCollection,
+) -> Result<Vec<HullResult>, Error> {
+    let options = CollectOptions::default();
+    let mut results: Vec<HullResult> = Vec::new();
+    let mut result_index_by_hull = HashMap::new();
+
+    for (index, feature) in featurecollection.features.iter().enumerate() {
+        let Ok((polygon, source)) =
+            process_single_feature_with_source(feature, HullKind::Convex, &options)
+        else {
+            continue;
+        };
+        let canonical_coords = canonical_hull_unique_sorted_points(&polygon);
+        let result_index = *result_index_by_hull
+            .entry(canonical_coords)
+            .or_insert_with(|| {
+                results.push(HullResult {
+                    polygon,
+                    feature_indices: Vec::new(),
+                    ids: Vec::new(),
+                    source,
+                });
+                results.len() - 1
+            });
+        let result = &mut results[result_index];
+        result.feature_indices.push(index);
+        result.ids.extend(feature.id.clone());
+    }
+
+    Ok(results)
+}
+
+/// Converts a hull to a Polygon feature with `area` and `vertex_count`
+/// properties and a computed bbox.
+///
+/// # Arguments
+/// * `hull` - The hull polygon in EPSG:4326.
+///
+/// # Returns
+/// The hull feature; `area` is the geodesic area in square meters and
+/// `vertex_count` the number of exterior vertices without the closing one.
+fn hull_to_feature(hull: &geo::Polygon) -> Feature {
+    let mut properties = JsonObject::new();
+    properties.insert(
+        "area".to_string(),
+        JsonValue::from(hull.geodesic_area_unsigned()),
+    );
+    properties.insert(
+        "vertex_count".to_string(),
+        JsonValue::from(hull.exterior().0.len().saturating_sub(1)),
+    );
+    Feature {
+        bbox: hull
+            .bounding_rect()
+            .map(|rect| vec![rect.min().x, rect.min().y, rect.max().x, rect.max().y]),
+        geometry: Some(Geometry::from(hull)),
+        id: None,
+        properties: Some(properties),
+        foreign_members: None,
+    }
+}
+
+/// Wraps hull features in a FeatureCollection whose bbox spans all features.
+fn hull_feature_collection(features: Vec<Feature>) -> FeatureCollection {
+    let bbox = union_bboxes(features.iter().filter_map(|feature| feature.bbox.as_ref()), 2);
+    FeatureCollection {
+        bbox,
+        features,
+        foreign_members: None,
+    }
+}
+
+/// Converts collected hulls to a GeoJSON FeatureCollection.
+///
+/// Each hull becomes a Polygon feature with its bbox and the properties
+/// `area` (geodesic, in square meters) and `vertex_count`.
+///
+/// # Arguments
+/// * `hulls` - The hulls, e.g. from `collect_convex_boundingboxes`.
+///
+/// # Returns
+/// One feature per hull, in input order.
+pub fn hulls_to_feature_collection(hulls: &[geo::Polygon]) -> FeatureCollection {
+    hull_feature_collection(hulls.iter().map(hull_to_feature).collect())
+}
+
+/// Converts hull results to a GeoJSON FeatureCollection like
+/// `hulls_to_feature_collection`, adding the `source` of each hull and the
+/// `feature_ids` of the features that produced it.
+///
+/// # Arguments
+/// * `results` - The hull results, e.g. from `collect_convex_hull_results`.
+///
+/// # Returns
+/// One feature per hull result, in input order.
+pub fn hull_results_to_feature_collection(results: &[HullResult]) -> FeatureCollection {
+    hull_feature_collection(
+        results
+            .iter()
+            .map(|result| {
+                let mut feature = hull_to_feature(&result.polygon);
+                let ids = result
+                    .ids
+                    .iter()
+                    .map(|id| match id {
+                        Id::String(id) => JsonValue::from(id.as_str()),
+                        Id::Number(id) => JsonValue::Number(id.clone()),
+                    })
+                    .collect::<Vec<JsonValue>>();
+                feature.set_property("source", result.source.as_str());
+                feature.set_property("feature_ids", ids);
+                feature
+            })
+            .collect(),
+    )
 }
 
 #[allow(unused_imports)]
 mod tests {
     use super::*;
-    use geo::{LineString, MultiPoint, Point, Polygon};
+    use crate::utils::helpers::{GERMANY_BBOX, InBoundingBox};
+    use geo::{Contains, LineString, MultiPoint, Point, Polygon};
     use geojson::{Feature, FeatureCollection, Value};
     use ordered_float::OrderedFloat;
     use std::collections::HashSet;
@@ -1017,4 +1706,483 @@ mod tests {
             "The set of returned hulls does not match the set of expected hulls"
         );
     }
+
+    #[test]
+    fn test_deduplicate_polygons_parallel_matches_serial() {
+        let hulls: Vec<Polygon> = (0..1000)
+            .map(|i| {
+                // Every polygon appears twice, the second time with a rotated ring
+                let offset = (i % 500) as f64;
+                let mut ring = vec![
+                    (offset, 0.0),
+                    (offset + 1.0, 0.0),
+                    (offset + 1.0, 1.0),
+                    (offset, 1.0),
+                ];
+                if i >= 500 {
+                    ring.rotate_left(1);
+                }
+                ring.push(ring[0]);
+                Polygon::new(LineString::from(ring), vec![])
+            })
+            .collect();
+
+        let serial = deduplicate_polygons(hulls.clone());
+        let parallel = deduplicate_polygons_parallel(hulls);
+        assert_eq!(serial.len(), 500);
+        assert_eq!(parallel, serial);
+    }
+
+    #[test]
+    fn test_collect_convex_boundingboxes_with_options_filter() {
+        // Vienna lies outside the Germany bbox
+        let fc = GeoFeatureCollection {
+            features: vec![GeoFeature {
+                id: None,
+                properties: None,
+                bbox: None,
+                foreign_members: None,
+                geometry: Some(GeoGeometry::LineString(LineString::from(vec![
+                    (16.37, 48.21),
+                    (16.38, 48.21),
+                    (16.38, 48.22),
+                ]))),
+            }],
+            ..Default::default()
+        };
+
+        assert!(collect_convex_boundingboxes(&fc).unwrap().is_empty());
+
+        let austria = CollectOptions::with_filter_bbox(Rect::new(
+            Coord { x: 9.5, y: 46.4 },
+            Coord { x: 17.2, y: 49.0 },
+        ));
+        assert_eq!(
+            collect_convex_boundingboxes_with_options(&fc, &austria)
+                .unwrap()
+                .len(),
+            1
+        );
+        assert_eq!(
+            collect_convex_boundingboxes_with_options(&fc, &CollectOptions::unfiltered())
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn test_collect_convex_boundingboxes_with_polygon_precision() {
+        use crate::geometry::collect_options::{FilterPrecision, germany_boundary};
+
+        // Basel lies within the Germany bbox but outside the boundary polygon
+        let fc = GeoFeatureCollection {
+            features: vec![GeoFeature {
+                id: None,
+                properties: None,
+                bbox: None,
+                foreign_members: None,
+                geometry: Some(GeoGeometry::LineString(LineString::from(vec![
+                    (7.58, 47.55),
+                    (7.59, 47.55),
+                    (7.59, 47.56),
+                ]))),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(collect_convex_boundingboxes(&fc).unwrap().len(), 1);
+        let precise = CollectOptions::default()
+            .with_filter_precision(FilterPrecision::Polygon(germany_boundary()));
+        assert!(
+            collect_convex_boundingboxes_with_options(&fc, &precise)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_collect_convex_boundingboxes_with_ids_keeps_first_id() {
+        let triangle = |id: &str, ring: Vec<(f64, f64)>| GeoFeature {
+            id: Some(Id::String(id.to_string())),
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::LineString(LineString::from(ring))),
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![
+                triangle("first", vec![(9.0, 50.0), (9.1, 50.0), (9.1, 50.1)]),
+                // Same hull in a different vertex order
+                triangle("duplicate", vec![(9.1, 50.1), (9.0, 50.0), (9.1, 50.0)]),
+                triangle("other", vec![(10.0, 51.0), (10.1, 51.0), (10.1, 51.1)]),
+            ],
+            ..Default::default()
+        };
+
+        let hulls = collect_convex_boundingboxes_with_ids(&fc).unwrap();
+        let ids: Vec<Option<Id>> = hulls.iter().map(|(id, _)| id.clone()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                Some(Id::String("first".to_string())),
+                Some(Id::String("other".to_string()))
+            ]
+        );
+        let plain: Vec<Polygon> = hulls.into_iter().map(|(_, hull)| hull).collect();
+        assert_eq!(plain, collect_convex_boundingboxes(&fc).unwrap());
+    }
+
+    #[test]
+    fn test_collect_convex_boundingboxes_max_outputs() {
+        let triangle = |ring: Vec<(f64, f64)>| GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::LineString(LineString::from(ring))),
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![
+                triangle(vec![(9.0, 50.0), (9.1, 50.0), (9.1, 50.1)]),
+                // Duplicates do not count towards the limit
+                triangle(vec![(9.1, 50.1), (9.0, 50.0), (9.1, 50.0)]),
+                triangle(vec![(10.0, 51.0), (10.1, 51.0), (10.1, 51.1)]),
+                triangle(vec![(11.0, 52.0), (11.1, 52.0), (11.1, 52.1)]),
+            ],
+            ..Default::default()
+        };
+
+        let unlimited = collect_convex_boundingboxes(&fc).unwrap();
+        assert_eq!(unlimited.len(), 3);
+
+        let options = CollectOptions::default().with_max_outputs(3);
+        let limited = collect_convex_boundingboxes_with_options(&fc, &options).unwrap();
+        assert_eq!(limited, unlimited);
+
+        let options = CollectOptions::default().with_max_outputs(1);
+        let result = collect_convex_boundingboxes_with_options(&fc, &options);
+        assert!(matches!(result, Err(Error::TooManyResults(2))));
+    }
+
+    #[test]
+    fn test_collect_convex_hull_results_merges_duplicate_ids() {
+        let feature = |id: Option<&str>, geometry: GeoGeometry| GeoFeature {
+            id: id.map(|id| Id::String(id.to_string())),
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(geometry),
+        };
+        let triangle = |ring: Vec<(f64, f64)>| GeoGeometry::LineString(LineString::from(ring));
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature(Some("a"), triangle(vec![(9.0, 50.0), (9.1, 50.0), (9.1, 50.1)])),
+                feature(Some("point"), GeoGeometry::Point(Point::new(10.0, 51.0))),
+                // Same hull as "a" in a different vertex order
+                feature(Some("b"), triangle(vec![(9.1, 50.1), (9.0, 50.0), (9.1, 50.0)])),
+                feature(None, triangle(vec![(9.1, 50.0), (9.1, 50.1), (9.0, 50.0)])),
+                feature(Some("c"), triangle(vec![(9.0, 50.0), (9.1, 50.0), (9.1, 50.1)])),
+                // Outside Germany
+                feature(Some("outside"), GeoGeometry::Point(Point::new(0.0, 0.0))),
+            ],
+            ..Default::default()
+        };
+
+        let results = collect_convex_hull_results(&fc).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].feature_indices, vec![0, 2, 3, 4]);
+        assert_eq!(
+            results[0].ids,
+            vec![
+                Id::String("a".to_string()),
+                Id::String("b".to_string()),
+                Id::String("c".to_string())
+            ]
+        );
+        assert_eq!(results[0].source, HullSource::ConvexHull);
+        assert_eq!(results[1].feature_indices, vec![1]);
+        assert_eq!(results[1].ids, vec![Id::String("point".to_string())]);
+        assert_eq!(results[1].source, HullSource::BoundingBoxFallback);
+
+        let polygons: Vec<Polygon> = results.into_iter().map(|result| result.polygon).collect();
+        assert_eq!(polygons, collect_convex_boundingboxes(&fc).unwrap());
+    }
+
+    #[test]
+    fn test_collect_hulls_concave_is_smaller_for_u_shape() {
+        // A U-shaped route, sampled densely so the concave hull can follow it
+        let mut route: Vec<(f64, f64)> = Vec::new();
+        route.extend((0..=10).rev().map(|i| (9.0, 50.0 + f64::from(i) * 0.01)));
+        route.extend((1..=10).map(|i| (9.0 + f64::from(i) * 0.01, 50.0)));
+        route.extend((1..=10).map(|i| (9.1, 50.0 + f64::from(i) * 0.01)));
+        let u_shape = GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::LineString(LineString::from(route))),
+        };
+        let point = GeoFeature {
+            geometry: Some(GeoGeometry::Point(Point::new(10.0, 51.0))),
+            ..u_shape.clone()
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![u_shape.clone(), point, u_shape],
+            ..Default::default()
+        };
+        let options = CollectOptions::default();
+
+        let convex = collect_hulls(&fc, HullKind::Convex, &options).unwrap();
+        let concave = collect_hulls(&fc, HullKind::Concave { concavity: 1.0 }, &options).unwrap();
+
+        assert_eq!(convex, collect_convex_boundingboxes(&fc).unwrap());
+        // The duplicate route is dropped and the point keeps its fallback bbox
+        assert_eq!(convex.len(), 2);
+        assert_eq!(concave.len(), 2);
+        assert_eq!(concave[1], convex[1]);
+        assert!(concave[0].unsigned_area() < convex[0].unsigned_area());
+    }
+
+    #[test]
+    fn test_canonical_ring_points_ignores_start_and_orientation() {
+        let ring = |coords: Vec<(f64, f64)>| Polygon::new(LineString::from(coords), vec![]);
+        let a = ring(vec![(0.0, 0.0), (2.0, 0.0), (1.0, 1.0), (2.0, 2.0), (0.0, 2.0)]);
+        let rotated = ring(vec![(1.0, 1.0), (2.0, 2.0), (0.0, 2.0), (0.0, 0.0), (2.0, 0.0)]);
+        let reversed = ring(vec![(0.0, 2.0), (2.0, 2.0), (1.0, 1.0), (2.0, 0.0), (0.0, 0.0)]);
+        // Same vertices, different ring
+        let other = ring(vec![(0.0, 0.0), (1.0, 1.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]);
+
+        assert_eq!(canonical_ring_points(&a), canonical_ring_points(&rotated));
+        assert_eq!(canonical_ring_points(&a), canonical_ring_points(&reversed));
+        assert_ne!(canonical_ring_points(&a), canonical_ring_points(&other));
+        assert_eq!(
+            canonical_hull_unique_sorted_points(&a),
+            canonical_hull_unique_sorted_points(&other)
+        );
+    }
+
+    #[test]
+    fn test_collect_hulls_buffers_in_meters() {
+        let point = GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::Point(Point::new(9.0, 50.0))),
+        };
+        let square = GeoFeature {
+            geometry: Some(GeoGeometry::Polygon(Polygon::new(
+                LineString::from(vec![(10.0, 51.0), (10.01, 51.0), (10.01, 51.01), (10.0, 51.0)]),
+                vec![],
+            ))),
+            ..point.clone()
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![point, square],
+            ..Default::default()
+        };
+        let options = CollectOptions::default();
+
+        let unbuffered = collect_hulls(&fc, HullKind::Convex, &options).unwrap();
+        let zero = collect_hulls(&fc, HullKind::Convex, &options.with_buffer_meters(0.0)).unwrap();
+        assert_eq!(zero, unbuffered);
+
+        let buffered =
+            collect_hulls(&fc, HullKind::Convex, &options.with_buffer_meters(25.0)).unwrap();
+        assert_eq!(buffered.len(), 2);
+        let transformer = buffer_transformer("EPSG:4326").unwrap();
+        let projected = project_polygon(&buffered[0], transformer.forward()).unwrap();
+        let extent = projected.bounding_rect().unwrap();
+        assert!((extent.width() - 50.0).abs() < 0.5, "width {}", extent.width());
+        assert!((extent.height() - 50.0).abs() < 0.5, "height {}", extent.height());
+        assert!(buffered[1].unsigned_area() > unbuffered[1].unsigned_area());
+        assert!(buffered[1].contains(&unbuffered[1]));
+
+        // One transformer can be shared by any number of calls
+        let buffer_options = options.with_buffer_meters(25.0);
+        for _ in 0..2 {
+            let shared =
+                collect_hulls_with_transformer(&fc, HullKind::Convex, &buffer_options, &transformer)
+                    .unwrap();
+            assert_eq!(shared, buffered);
+        }
+    }
+
+    #[test]
+    fn test_deduplicate_polygons_with_tolerance() {
+        let square = |offset: f64| {
+            Polygon::new(
+                LineString::from(vec![
+                    (9.0 + offset, 50.0),
+                    (9.1 + offset, 50.0),
+                    (9.1 + offset, 50.1),
+                    (9.0 + offset, 50.1),
+                    (9.0 + offset, 50.0),
+                ]),
+                vec![],
+            )
+        };
+        let hulls = vec![square(0.0), square(1e-10), square(0.01)];
+
+        let merged = deduplicate_polygons_with_tolerance(hulls.clone(), 1e-6);
+        assert_eq!(merged, vec![square(0.0), square(0.01)]);
+        assert_eq!(deduplicate_polygons_with_tolerance(hulls.clone(), 0.0), hulls);
+        assert_eq!(deduplicate_polygons(hulls.clone()), hulls);
+
+        let feature = |polygon: Polygon| GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::Polygon(polygon)),
+        };
+        let fc = GeoFeatureCollection {
+            features: hulls.into_iter().map(feature).collect(),
+            ..Default::default()
+        };
+        let options = CollectOptions::default();
+        let exact = collect_hulls(&fc, HullKind::Convex, &options).unwrap();
+        let tolerant =
+            collect_hulls(&fc, HullKind::Convex, &options.with_dedup_tolerance(1e-6)).unwrap();
+        assert_eq!(exact.len(), 3);
+        assert_eq!(tolerant.len(), 2);
+    }
+
+    #[test]
+    fn test_collect_convex_boundingboxes_detailed_reports_skips() {
+        let feature = |id: &str, geometry: Option<GeoGeometry>| GeoFeature {
+            id: Some(Id::String(id.to_string())),
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry,
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature("inside", Some(GeoGeometry::Point(Point::new(9.0, 50.0)))),
+                feature("outside", Some(GeoGeometry::Point(Point::new(-70.0, 40.0)))),
+                feature("no-geometry", None),
+                feature(
+                    "empty",
+                    Some(GeoGeometry::MultiPolygon(geo::MultiPolygon::new(vec![]))),
+                ),
+                // Duplicates are merged, not skipped
+                feature("duplicate", Some(GeoGeometry::Point(Point::new(9.0, 50.0)))),
+            ],
+            ..Default::default()
+        };
+
+        let (hulls, report) = collect_convex_boundingboxes_detailed(&fc).unwrap();
+
+        assert_eq!(hulls, collect_convex_boundingboxes(&fc).unwrap());
+        assert_eq!(hulls.len(), 1);
+        let skipped: Vec<(usize, Option<Id>, SkipReason)> = report
+            .skipped
+            .iter()
+            .map(|skipped| (skipped.index, skipped.id.clone(), skipped.reason))
+            .collect();
+        assert_eq!(
+            skipped,
+            vec![
+                (1, Some(Id::String("outside".to_string())), SkipReason::OutsideFilter),
+                (2, Some(Id::String("no-geometry".to_string())), SkipReason::MissingGeometry),
+                (3, Some(Id::String("empty".to_string())), SkipReason::EmptyGeometry),
+            ]
+        );
+        assert_eq!(report.len(), 3);
+        assert_eq!(report.count(SkipReason::OutsideFilter), 1);
+    }
+
+    #[test]
+    fn test_hull_results_to_feature_collection_round_trip() {
+        let feature = |id: &str, geometry: GeoGeometry| GeoFeature {
+            id: Some(Id::String(id.to_string())),
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(geometry),
+        };
+        let triangle = GeoGeometry::Polygon(Polygon::new(
+            LineString::from(vec![(9.0, 50.0), (9.1, 50.0), (9.0, 50.1), (9.0, 50.0)]),
+            vec![],
+        ));
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature("a", triangle.clone()),
+                feature("b", GeoGeometry::Point(Point::new(10.0, 51.0))),
+                feature("c", triangle),
+            ],
+            ..Default::default()
+        };
+        let results = collect_convex_hull_results(&fc).unwrap();
+
+        let collection = hull_results_to_feature_collection(&results);
+        let serialized = serde_json::to_string(&collection).unwrap();
+        let parsed = geojson::FeatureCollection::try_from(
+            serialized.parse::<geojson::GeoJson>().unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(parsed, collection);
+        assert_eq!(parsed.bbox, Some(vec![9.0, 50.0, 10.0, 51.0]));
+        let hull = &parsed.features[0];
+        assert_eq!(hull.bbox, Some(vec![9.0, 50.0, 9.1, 50.1]));
+        assert_eq!(hull.property("source"), Some(&JsonValue::from("convex_hull")));
+        assert_eq!(hull.property("vertex_count"), Some(&JsonValue::from(3)));
+        assert_eq!(hull.property("feature_ids"), Some(&serde_json::json!(["a", "c"])));
+        assert!(hull.property("area").and_then(JsonValue::as_f64).unwrap() > 0.0);
+        let polygon: Polygon = hull.geometry.clone().unwrap().try_into().unwrap();
+        assert_eq!(polygon, results[0].polygon);
+        assert_eq!(
+            parsed.features[1].property("source"),
+            Some(&JsonValue::from("bbox_fallback"))
+        );
+
+        let plain = hulls_to_feature_collection(&collect_convex_boundingboxes(&fc).unwrap());
+        assert_eq!(plain.features.len(), 2);
+        assert_eq!(plain.features[0].property("source"), None);
+        assert_eq!(plain.features[0].geometry, hull.geometry);
+    }
+
+    #[test]
+    fn test_collect_hulls_per_part() {
+        let square = |x: f64| {
+            Polygon::new(
+                LineString::from(vec![
+                    (x, 50.0),
+                    (x + 0.001, 50.0),
+                    (x + 0.001, 50.001),
+                    (x, 50.001),
+                    (x, 50.0),
+                ]),
+                vec![],
+            )
+        };
+        // Two building parts about 700 m apart
+        let building = GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::MultiPolygon(geo::MultiPolygon::new(vec![
+                square(9.0),
+                square(9.01),
+            ]))),
+        };
+        let fc = GeoFeatureCollection {
+            features: vec![building],
+            ..Default::default()
+        };
+        let options = CollectOptions::default();
+
+        let combined = collect_hulls(&fc, HullKind::Convex, &options).unwrap();
+        let per_part =
+            collect_hulls(&fc, HullKind::Convex, &options.with_per_part_hulls(true)).unwrap();
+
+        assert_eq!(combined.len(), 1);
+        assert_eq!(per_part.len(), 2);
+        assert!(per_part.iter().all(|hull| hull.unsigned_area() < combined[0].unsigned_area()));
+    }
 }
@@ -0,0 +1,159 @@
+// Detects the coordinate reference system declared by legacy GeoJSON.
+
+use geojson::GeoJson;
+use serde_json::{Map, Value as JsonValue};
+
+/// The CRS assumed for GeoJSON input without a `crs` member (RFC 7946).
+pub const DEFAULT_CRS: &str = "EPSG:4326";
+
+/// Normalizes a named CRS to the `AUTHORITY:CODE` form understood by PROJ.
+///
+/// OGC URNs such as `urn:ogc:def:crs:EPSG::3857` become `EPSG:3857`. `CRS84`
+/// is reported as `EPSG:4326`, since coordinates in this crate are always
+/// handled in longitude/latitude order.
+///
+/// # Arguments
+/// * `name` - The CRS name as found in the `crs` member.
+///
+/// # Returns
+/// The normalized CRS name.
+pub(crate) fn normalize_crs_name(name: &str) -> String {
+    let name = name.trim();
+    if let Some(urn) = name.strip_prefix("urn:ogc:def:crs:") {
+        // urn:ogc:def:crs:{authority}:{version}:{code}, version may be empty
+        let parts: Vec<&str> = urn.split(':').collect();
+        if let (Some(authority), Some(code)) = (parts.first(), parts.last()) {
+            return normalize_crs_name(&format!("{}:{}", authority, code));
+        }
+    }
+    match name {
+        "CRS84" | "OGC:CRS84" => DEFAULT_CRS.to_string(),
+        other => other.to_string(),
+    }
+}
+
+/// Reads the CRS from the `crs` foreign member, if present.
+///
+/// Supports the `name` form (`{"type": "name", "properties": {"name": ...}}`)
+/// and the older `EPSG` form (`{"type": "EPSG", "properties": {"code": ...}}`).
+/// Linked CRS definitions are not resolved.
+///
+/// # Arguments
+/// * `foreign_members` - The foreign members of a GeoJSON object.
+///
+/// # Returns
+/// `Some(String)` with the normalized CRS name, otherwise `None`.
+pub(crate) fn crs_from_foreign_members(
+    foreign_members: Option<&Map<String, JsonValue>>,
+) -> Option<String> {
+    let crs = foreign_members?.get("crs")?;
+    let properties = crs.get("properties")?;
+    match crs.get("type")?.as_str()? {
+        "name" => properties
+            .get("name")?
+            .as_str()
+            .map(normalize_crs_name),
+        "EPSG" => match properties.get("code")? {
+            JsonValue::Number(code) => Some(format!("EPSG:{}", code)),
+            JsonValue::String(code) => Some(format!("EPSG:{}", code)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Resolves the source CRS for a GeoJSON object from its foreign members.
+///
+/// Falls back to `DEFAULT_CRS` and warns when the declared CRS differs from it.
+///
+/// # Arguments
+/// * `foreign_members` - The foreign members of a GeoJSON object.
+///
+/// # Returns
+/// The CRS to project from.
+pub(crate) fn source_crs(foreign_members: Option<&Map<String, JsonValue>>) -> String {
+    match crs_from_foreign_members(foreign_members) {
+        Some(crs) => {
+            if crs != DEFAULT_CRS {
+                eprintln!(
+                    "GeoJSON crs member declares {}, which differs from the assumed {}",
+                    crs, DEFAULT_CRS
+                );
+            }
+            crs
+        }
+        None => DEFAULT_CRS.to_string(),
+    }
+}
+
+/// Detects the CRS declared by the top-level `crs` member of a GeoJSON object.
+///
+/// The `crs` member was removed in RFC 7946, so the geojson crate exposes it
+/// as a foreign member.
+///
+/// # Arguments
+/// * `gj` - The GeoJSON object to inspect.
+///
+/// # Returns
+/// `Some(String)` with the normalized CRS name, or `None` if no CRS is declared.
+pub fn detect_crs(gj: &GeoJson) -> Option<String> {
+    let foreign_members = match gj {
+        GeoJson::FeatureCollection(fc) => fc.foreign_members.as_ref(),
+        GeoJson::Feature(feature) => feature.foreign_members.as_ref(),
+        GeoJson::Geometry(geometry) => geometry.foreign_members.as_ref(),
+    };
+    crs_from_foreign_members(foreign_members)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(json: &str) -> GeoJson {
+        json.parse().unwrap()
+    }
+
+    #[test]
+    fn test_detect_crs_named_urn() {
+        let gj = parse(
+            r#"{
+                "type": "FeatureCollection",
+                "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:EPSG::3857" } },
+                "features": []
+            }"#,
+        );
+        assert_eq!(detect_crs(&gj), Some("EPSG:3857".to_string()));
+    }
+
+    #[test]
+    fn test_detect_crs_crs84_is_default() {
+        let gj = parse(
+            r#"{
+                "type": "FeatureCollection",
+                "crs": { "type": "name", "properties": { "name": "urn:ogc:def:crs:OGC:1.3:CRS84" } },
+                "features": []
+            }"#,
+        );
+        assert_eq!(detect_crs(&gj), Some(DEFAULT_CRS.to_string()));
+    }
+
+    #[test]
+    fn test_detect_crs_epsg_code() {
+        let gj = parse(
+            r#"{
+                "type": "Feature",
+                "crs": { "type": "EPSG", "properties": { "code": 3035 } },
+                "properties": null,
+                "geometry": null
+            }"#,
+        );
+        assert_eq!(detect_crs(&gj), Some("EPSG:3035".to_string()));
+    }
+
+    #[test]
+    fn test_detect_crs_missing() {
+        let gj = parse(r#"{ "type": "FeatureCollection", "features": [] }"#);
+        assert_eq!(detect_crs(&gj), None);
+        assert_eq!(source_crs(None), DEFAULT_CRS);
+    }
+}
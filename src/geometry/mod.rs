@@ -1,3 +1,15 @@
 pub mod bounding_box;
 pub mod scaling;
-pub mod convex_hull;
\ No newline at end of file
+pub mod convex_hull;
+pub mod spatial_sort;
+pub mod crs;
+pub mod rasterize;
+pub mod collect_options;
+pub mod centroids;
+pub mod rotation;
+pub mod translation;
+#[cfg(test)]
+mod test_fixtures;
+
+pub use crate::utils::geometry::Rectangle;
+pub use crate::utils::transformer::Transformer;
\ No newline at end of file
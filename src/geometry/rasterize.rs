@@ -0,0 +1,84 @@
+// Rasterizes polygons onto a square grid.
+
+use geo::{Intersects, Polygon, Rect};
+
+use crate::utils::helpers::Grid;
+
+/// Returns the cells of a square grid that a polygon actually covers.
+///
+/// The grid is laid over `bbox` with square cells of size `cell`, clipped at
+/// the bbox edges. Unlike selecting cells by the polygon's bounding box, cells
+/// in its bbox that miss the polygon (e.g. inside an L-shape's notch) are
+/// left out.
+///
+/// # Arguments
+/// * `poly` - The polygon to rasterize.
+/// * `bbox` - The extent of the grid.
+/// * `cell` - The width and height of a grid cell.
+///
+/// # Returns
+/// The grid cells intersecting the polygon. Empty if `cell` is not positive
+/// or `bbox` is degenerate.
+pub fn rasterize_polygon(poly: &Polygon, bbox: Rect, cell: f64) -> Vec<Rect> {
+    Grid::new(bbox, cell, cell)
+        .cells
+        .into_iter()
+        .filter(|grid_cell| poly.intersects(grid_cell))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{Coord, LineString};
+
+    fn r(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Rect {
+        Rect::new(Coord { x: min_x, y: min_y }, Coord { x: max_x, y: max_y })
+    }
+
+    #[test]
+    fn test_rasterize_l_shaped_polygon() {
+        // L-shape covering the bottom row and the left column of a 2x2 grid
+        let poly = Polygon::new(
+            LineString::from(vec![
+                (0.0, 0.0),
+                (2.0, 0.0),
+                (2.0, 0.9),
+                (0.9, 0.9),
+                (0.9, 2.0),
+                (0.0, 2.0),
+                (0.0, 0.0),
+            ]),
+            vec![],
+        );
+
+        let mut cells = rasterize_polygon(&poly, r(0.0, 0.0, 2.0, 2.0), 1.0);
+        cells.sort_by(|a, b| {
+            (a.min().x, a.min().y)
+                .partial_cmp(&(b.min().x, b.min().y))
+                .unwrap()
+        });
+        assert_eq!(
+            cells,
+            vec![r(0.0, 0.0, 1.0, 1.0), r(0.0, 1.0, 1.0, 2.0), r(1.0, 0.0, 2.0, 1.0)]
+        );
+    }
+
+    #[test]
+    fn test_rasterize_polygon_outside_grid() {
+        let poly = Polygon::new(
+            LineString::from(vec![(5.0, 5.0), (6.0, 5.0), (6.0, 6.0), (5.0, 5.0)]),
+            vec![],
+        );
+        assert!(rasterize_polygon(&poly, r(0.0, 0.0, 2.0, 2.0), 1.0).is_empty());
+    }
+
+    #[test]
+    fn test_rasterize_polygon_invalid_cell_size() {
+        let poly = Polygon::new(
+            LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]),
+            vec![],
+        );
+        assert!(rasterize_polygon(&poly, r(0.0, 0.0, 2.0, 2.0), 0.0).is_empty());
+    }
+}
@@ -0,0 +1,246 @@
+// Rotates features about an anchor, mirroring the scaling transform.
+
+use geo::{Centroid, Point, Polygon, Rotate};
+use geojson::FeatureCollection;
+
+use crate::geometry::scaling::{ScaleOrigin, collection_centroid};
+use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
+use crate::utils::helpers::{CLOSED_RING_EPSILON, LineKind, classify_linestring, restore_z};
+
+/// Rotates the features of a collection by `degrees` (counter-clockwise) about `anchor`.
+///
+/// With `ScaleOrigin::ClosedCenterElseZero`, closed rings and polygons are rotated about
+/// their centroid, while points, multipoints and open LineStrings are rotated about
+/// (0, 0), matching `scale_buildings`. Unsupported geometry types and features
+/// without a geometry are passed through unchanged.
+///
+/// # Arguments
+/// * `fc` - The feature collection to rotate.
+/// * `degrees` - The rotation angle in degrees.
+/// * `anchor` - The anchor the geometries are rotated about.
+///
+/// # Returns
+/// The collection with its geometries rotated.
+pub fn rotate_features(
+    fc: &GeoFeatureCollection,
+    degrees: f64,
+    anchor: ScaleOrigin,
+) -> GeoFeatureCollection {
+    let fixed_origin = match anchor {
+        ScaleOrigin::Fixed(coord) => Some(Point::from(coord)),
+        ScaleOrigin::CollectionCentroid => match collection_centroid(fc) {
+            Some(coord) => Some(Point::from(coord)),
+            // No geometry to rotate
+            None => return fc.clone(),
+        },
+        ScaleOrigin::ClosedCenterElseZero | ScaleOrigin::Centroid => None,
+    };
+
+    let features = fc
+        .features
+        .iter()
+        .map(|feature| GeoFeature {
+            geometry: feature
+                .geometry
+                .as_ref()
+                .map(|geometry| rotate_geometry(geometry, degrees, anchor, fixed_origin)),
+            ..feature.clone()
+        })
+        .collect();
+
+    GeoFeatureCollection {
+        features,
+        ..fc.clone()
+    }
+}
+
+/// Rotates a geojson collection like `rotate_features`, keeping the z value
+/// (elevation) of 3D positions unchanged.
+pub fn rotate_features_3d(
+    fc: &FeatureCollection,
+    degrees: f64,
+    anchor: ScaleOrigin,
+) -> FeatureCollection {
+    let mut rotated = FeatureCollection::from(rotate_features(&fc.clone().into(), degrees, anchor));
+    for (original, feature) in fc.features.iter().zip(&mut rotated.features) {
+        if let (Some(original), Some(geometry)) = (&original.geometry, &mut feature.geometry) {
+            restore_z(original, geometry, |z| z);
+        }
+    }
+    rotated
+}
+
+/// Resolves the point a single geometry is rotated about.
+fn rotation_origin(
+    geometry: &GeoGeometry,
+    anchor: ScaleOrigin,
+    fixed_origin: Option<Point>,
+) -> Option<Point> {
+    if fixed_origin.is_some() {
+        return fixed_origin;
+    }
+    let closed_ring = match geometry {
+        GeoGeometry::LineString(line) => {
+            classify_linestring(line, CLOSED_RING_EPSILON) == LineKind::ClosedRing
+        }
+        _ => false,
+    };
+    let own_centroid = || match geometry {
+        // Closed rings are rotated about the centroid of the area they enclose
+        GeoGeometry::LineString(line) if closed_ring => {
+            Polygon::new(line.clone(), vec![]).centroid()
+        }
+        _ => geometry.centroid(),
+    };
+    match anchor {
+        ScaleOrigin::ClosedCenterElseZero => match geometry {
+            GeoGeometry::Polygon(_) => own_centroid(),
+            GeoGeometry::LineString(_) if closed_ring => own_centroid(),
+            _ => Some(Point::new(0.0, 0.0)),
+        },
+        _ => own_centroid(),
+    }
+}
+
+/// Rotates a single geometry, returning unsupported or empty geometries unchanged.
+fn rotate_geometry(
+    geometry: &GeoGeometry,
+    degrees: f64,
+    anchor: ScaleOrigin,
+    fixed_origin: Option<Point>,
+) -> GeoGeometry {
+    let Some(origin) = rotation_origin(geometry, anchor, fixed_origin) else {
+        // Empty geometries have no centroid and nothing to rotate
+        return geometry.clone();
+    };
+    match geometry {
+        GeoGeometry::Point(point) => {
+            GeoGeometry::Point(point.rotate_around_point(degrees, origin))
+        }
+        GeoGeometry::LineString(line) => {
+            GeoGeometry::LineString(line.rotate_around_point(degrees, origin))
+        }
+        GeoGeometry::Polygon(polygon) => {
+            GeoGeometry::Polygon(polygon.rotate_around_point(degrees, origin))
+        }
+        GeoGeometry::MultiPoint(multi_point) => {
+            GeoGeometry::MultiPoint(multi_point.rotate_around_point(degrees, origin))
+        }
+        // Pass unsupported geometry types through unchanged
+        other => other.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{Coord, LineString, MultiPolygon};
+    use crate::geometry::test_fixtures::feature_collection;
+
+    const EPSILON: f64 = 1e-9;
+
+    fn unit_square() -> Polygon {
+        Polygon::new(
+            LineString::from(vec![
+                Coord { x: 0.0, y: 0.0 },
+                Coord { x: 1.0, y: 0.0 },
+                Coord { x: 1.0, y: 1.0 },
+                Coord { x: 0.0, y: 1.0 },
+                Coord { x: 0.0, y: 0.0 },
+            ]),
+            vec![],
+        )
+    }
+
+    fn same_vertices(a: &LineString, b: &LineString) -> bool {
+        a.0.len() == b.0.len()
+            && a.coords().all(|ca| {
+                b.coords()
+                    .any(|cb| (ca.x - cb.x).abs() < EPSILON && (ca.y - cb.y).abs() < EPSILON)
+            })
+    }
+
+    #[test]
+    fn test_rotate_unit_square_90_degrees_about_centroid() {
+        let fc = feature_collection(GeoGeometry::Polygon(unit_square()));
+
+        for anchor in [ScaleOrigin::Centroid, ScaleOrigin::ClosedCenterElseZero] {
+            let rotated = rotate_features(&fc, 90.0, anchor);
+            match &rotated.features[0].geometry {
+                Some(GeoGeometry::Polygon(polygon)) => {
+                    assert!(same_vertices(polygon.exterior(), unit_square().exterior()));
+                }
+                other => panic!("unexpected geometry {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn test_rotate_point_shape_dependent_about_origin() {
+        let fc = feature_collection(GeoGeometry::Point(Point::new(1.0, 0.0)));
+
+        let rotated = rotate_features(&fc, 90.0, ScaleOrigin::ClosedCenterElseZero);
+
+        match rotated.features[0].geometry {
+            Some(GeoGeometry::Point(point)) => {
+                assert!(point.x().abs() < EPSILON);
+                assert!((point.y() - 1.0).abs() < EPSILON);
+            }
+            ref other => panic!("unexpected geometry {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rotate_line_string_about_fixed_point() {
+        let line = LineString::from(vec![Coord { x: 2.0, y: 1.0 }, Coord { x: 3.0, y: 1.0 }]);
+        let fc = feature_collection(GeoGeometry::LineString(line));
+
+        let rotated = rotate_features(&fc, 180.0, ScaleOrigin::Fixed(Coord { x: 1.0, y: 1.0 }));
+
+        match &rotated.features[0].geometry {
+            Some(GeoGeometry::LineString(line)) => assert!(same_vertices(
+                line,
+                &LineString::from(vec![Coord { x: 0.0, y: 1.0 }, Coord { x: -1.0, y: 1.0 }])
+            )),
+            other => panic!("unexpected geometry {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_rotate_unsupported_geometry_passes_through() {
+        let multi_polygon = GeoGeometry::MultiPolygon(MultiPolygon::new(vec![unit_square()]));
+        let mut fc = feature_collection(multi_polygon);
+        fc.features.push(GeoFeature {
+            geometry: None,
+            ..fc.features[0].clone()
+        });
+
+        let rotated = rotate_features(&fc, 45.0, ScaleOrigin::Centroid);
+
+        assert_eq!(rotated, fc);
+    }
+
+    #[test]
+    fn test_rotate_features_3d_keeps_z() {
+        let mut fc = FeatureCollection::from(feature_collection(GeoGeometry::Point(Point::new(
+            1.0, 0.0,
+        ))));
+        fc.features[0].geometry = Some(geojson::Geometry::new(geojson::Value::LineString(vec![
+            vec![1.0, 0.0, 35.5],
+            vec![2.0, 0.0, 36.0],
+        ])));
+
+        let rotated = rotate_features_3d(&fc, 90.0, ScaleOrigin::Fixed(Coord { x: 0.0, y: 0.0 }));
+
+        match &rotated.features[0].geometry.as_ref().unwrap().value {
+            geojson::Value::LineString(positions) => {
+                assert_eq!(positions.len(), 2);
+                assert!(positions[1][0].abs() < EPSILON);
+                assert!((positions[1][1] - 2.0).abs() < EPSILON);
+                assert_eq!(positions[0][2], 35.5);
+                assert_eq!(positions[1][2], 36.0);
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+    }
+}
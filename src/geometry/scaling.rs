@@ -1,124 +1,348 @@
 use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
-use geo::{Coord, LineString, Polygon, Scale};
-use geo::{HasDimensions, Point};
+use crate::utils::helpers::{
+    CLOSED_RING_EPSILON, LineKind, classify_linestring, restore_z, snap_ring_closed,
+};
+use geo::{Centroid, Coord, Geometry, GeometryCollection, LineString, MultiPolygon, Polygon, Scale};
+use geo::Point;
+use geojson::{Feature, FeatureCollection};
+
+/// Anchor that geometries are scaled about.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ScaleOrigin {
+    /// Closed rings, polygons and multipolygons about their own center, points,
+    /// multipoints and open LineStrings about (0, 0). This is the behavior of `scale_buildings`.
+    #[default]
+    ClosedCenterElseZero,
+    /// Every feature about its own centroid, so features stay where they are.
+    Centroid,
+    /// Every feature about the centroid of the whole collection, so the
+    /// relative positions between features are scaled as well.
+    CollectionCentroid,
+    /// Every feature about a fixed coordinate.
+    Fixed(Coord<f64>),
+}
+
+/// Options controlling how buildings are scaled.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScaleOptions {
+    /// Anchor that geometries are scaled about.
+    pub origin: ScaleOrigin,
+    /// Tolerance for treating a LineString as a closed ring.
+    pub closed_eps: f64,
+    /// Drop the interior rings of polygons instead of scaling holes together
+    /// with the exterior.
+    pub drop_holes: bool,
+}
+
+impl Default for ScaleOptions {
+    fn default() -> Self {
+        Self {
+            origin: ScaleOrigin::default(),
+            closed_eps: CLOSED_RING_EPSILON,
+            drop_holes: false,
+        }
+    }
+}
+
+/// The anchor resolved for a single feature.
+#[derive(Clone, Copy)]
+enum Anchor {
+    ClosedCenterElseZero,
+    OwnCentroid,
+    Fixed(Coord<f64>),
+}
 
+/// Scales the buildings of a collection by `scale_factor`.
+///
+/// Closed rings and polygons are scaled about the center of their bounding
+/// box and multipolygons about their overall centroid, holes included. Points, multipoints and open
+/// LineStrings are scaled about (0, 0), which moves them when they are far
+/// from the origin. Use `scale_buildings_with_origin` to
+/// scale every geometry type about the same kind of anchor.
 pub fn scale_buildings(
     feature_collection: &GeoFeatureCollection,
     scale_factor: f64,
+) -> GeoFeatureCollection {
+    scale_buildings_with_tolerance(feature_collection, scale_factor, CLOSED_RING_EPSILON)
+}
+
+/// Scales buildings like `scale_buildings`, treating LineStrings whose
+/// endpoints agree to within `closed_eps` as closed rings.
+pub fn scale_buildings_with_tolerance(
+    feature_collection: &GeoFeatureCollection,
+    scale_factor: f64,
+    closed_eps: f64,
+) -> GeoFeatureCollection {
+    let options = ScaleOptions {
+        closed_eps,
+        ..Default::default()
+    };
+    scale_collection(feature_collection, scale_factor, &options)
+}
+
+/// Scales buildings like `scale_buildings`, about the given origin.
+pub fn scale_buildings_with_origin(
+    feature_collection: &GeoFeatureCollection,
+    scale_factor: f64,
+    origin: ScaleOrigin,
+) -> GeoFeatureCollection {
+    let options = ScaleOptions {
+        origin,
+        ..Default::default()
+    };
+    scale_collection(feature_collection, scale_factor, &options)
+}
+
+/// Scales buildings like `scale_buildings`, using the given scaling options.
+pub fn scale_buildings_with_options(
+    feature_collection: &GeoFeatureCollection,
+    scale_factor: f64,
+    options: &ScaleOptions,
+) -> GeoFeatureCollection {
+    scale_collection(feature_collection, scale_factor, options)
+}
+
+/// Scales a geojson collection like `scale_buildings_with_options`, keeping
+/// the z value (elevation) of 3D positions.
+///
+/// The z values are multiplied by `scale_factor`, independent of the origin
+/// the x and y values are scaled about. 2D positions stay 2D.
+pub fn scale_features_3d(
+    feature_collection: &FeatureCollection,
+    scale_factor: f64,
+    options: &ScaleOptions,
+) -> FeatureCollection {
+    let geo_collection = GeoFeatureCollection::from(feature_collection.clone());
+    if geo_collection.features.is_empty() {
+        return feature_collection.clone();
+    }
+    let Some(anchor) = resolve_anchor(&geo_collection, options) else {
+        // No geometry to scale
+        return FeatureCollection::from(GeoFeatureCollection::default());
+    };
+    let features = feature_collection
+        .features
+        .iter()
+        .zip(&geo_collection.features)
+        .filter_map(|(original, geo_feature)| {
+            let scaled = scale_feature(geo_feature, scale_factor, anchor, options)?;
+            let mut scaled = Feature::from(scaled);
+            if let (Some(original), Some(scaled)) = (&original.geometry, &mut scaled.geometry) {
+                restore_z(original, scaled, |z| z * scale_factor);
+            }
+            Some(scaled)
+        })
+        .collect();
+    FeatureCollection {
+        bbox: None,
+        features,
+        foreign_members: None,
+    }
+}
+
+/// Calculates the centroid of all geometries in a collection.
+pub(crate) fn collection_centroid(
+    feature_collection: &GeoFeatureCollection,
+) -> Option<Coord<f64>> {
+    let geometries: GeometryCollection<f64> = feature_collection
+        .features
+        .iter()
+        .filter_map(|feature| feature.geometry.as_ref())
+        .map(Geometry::from)
+        .collect();
+    geometries.centroid().map(Coord::from)
+}
+
+fn scale_collection(
+    feature_collection: &GeoFeatureCollection,
+    scale_factor: f64,
+    options: &ScaleOptions,
 ) -> GeoFeatureCollection {
     if feature_collection.features.is_empty() {
         return feature_collection.clone();
     }
+    let Some(anchor) = resolve_anchor(feature_collection, options) else {
+        // No geometry to scale
+        return GeoFeatureCollection::default();
+    };
     // create a new feature collection
     let mut scaled_feature_collection = GeoFeatureCollection::default();
     // for each feature in feature_collection
     for feature in feature_collection.features.iter() {
-        // if feature.geometry is Some
-        if let Some(geometry) = &feature.geometry {
-            // match geometry.value
-            match geometry {
-                // if geometry.value is LineString
-                GeoGeometry::LineString(line_coords) => {
-                    // Convert the line coordinates to a LineString
-                    let line: LineString<f64> = line_coords
-                        .into_iter()
-                        .map(|coord| Point::new(coord.x, coord.y))
-                        .collect();
-
-                    // Check if the line is empty
-                    if line.is_empty() {
-                        continue; // Skip empty LineStrings
-                    }
-                    // check if the line is closed: first and last point are the same
-
-                    let scaled_line: LineString<f64> = if line.is_closed() {
-                        // convert linestring to polygon
-                        let polygon = Polygon::new(line, vec![]);
-                        let scaled_polygon = polygon.scale(scale_factor);
-                        scaled_polygon.exterior().clone()
-                    } else {
-                        // Scale the LineString
-                        line.coords()
-                            .map(|&c| Coord {
-                                x: scale_factor * c.x,
-                                y: scale_factor * c.y,
-                            })
-                            .collect()
-                    };
+        if let Some(scaled_feature) = scale_feature(feature, scale_factor, anchor, options) {
+            scaled_feature_collection.features.push(scaled_feature);
+        }
+    }
+    scaled_feature_collection
+}
 
-                    scaled_feature_collection.features.push(GeoFeature {
-                        bbox: feature.bbox.clone(),
-                        geometry: Some(GeoGeometry::LineString(
-                            scaled_line
-                                .into_iter()
-                                .map(|coord| Point::new(coord.x, coord.y))
-                                .collect(),
-                        )),
-                        id: feature.id.clone(),
-                        properties: feature.properties.clone(),
-                        foreign_members: feature.foreign_members.clone(),
-                    });
-                }
-                // if geometry.value is Polygon
-                GeoGeometry::Polygon(polygon_coords) => {
-                    let scaled_polygon = polygon_coords.scale(scale_factor);
-                    scaled_feature_collection.features.push(GeoFeature {
-                        bbox: feature.bbox.clone(),
-                        geometry: Some(GeoGeometry::Polygon(scaled_polygon)),
-                        id: feature.id.clone(),
-                        properties: feature.properties.clone(),
-                        foreign_members: feature.foreign_members.clone(),
-                    });
-                }
-                // if geometry.value is Point
-                GeoGeometry::Point(coord) => {
-                    // Use manual scaling relative to (0,0)
-                    let scaled_point_coord =
-                        Point::new(scale_factor * coord.x(), scale_factor * coord.y());
-
-                    scaled_feature_collection.features.push(GeoFeature {
-                        bbox: feature.bbox.clone(),
-                        geometry: Some(GeoGeometry::Point(scaled_point_coord)),
-                        id: feature.id.clone(),
-                        properties: feature.properties.clone(),
-                        foreign_members: feature.foreign_members.clone(),
-                    });
-                }
-                // if geometry.value is MultiPoint
-                GeoGeometry::MultiPoint(point_coords_vec) => {
-                    // Create a MultiPoint from the coordinates
-                    let scaled_coords: Vec<Point<f64>> = point_coords_vec
-                        .into_iter()
-                        .map(|c| Point::new(scale_factor * c.x(), scale_factor * c.y()))
-                        .collect();
-
-                    scaled_feature_collection.features.push(GeoFeature {
-                        bbox: feature.bbox.clone(),
-                        geometry: Some(GeoGeometry::MultiPoint(
-                            scaled_coords
-                                .into_iter()
-                                .map(|c| Point::new(c.x(), c.y()))
-                                .collect(),
-                        )),
-                        id: feature.id.clone(),
-                        properties: feature.properties.clone(),
-                        foreign_members: feature.foreign_members.clone(),
-                    })
-                }
-                _ => {
-                    // Skip unsupported geometry types
-                    continue;
+/// Resolves the anchor for the features of a collection, or None if the
+/// collection centroid is requested but the collection has no geometry.
+fn resolve_anchor(
+    feature_collection: &GeoFeatureCollection,
+    options: &ScaleOptions,
+) -> Option<Anchor> {
+    Some(match options.origin {
+        ScaleOrigin::ClosedCenterElseZero => Anchor::ClosedCenterElseZero,
+        ScaleOrigin::Centroid => Anchor::OwnCentroid,
+        ScaleOrigin::Fixed(coord) => Anchor::Fixed(coord),
+        ScaleOrigin::CollectionCentroid => Anchor::Fixed(collection_centroid(feature_collection)?),
+    })
+}
+
+/// Scales a single feature, returning None for features without a geometry
+/// and for geometries that are skipped.
+fn scale_feature(
+    feature: &GeoFeature,
+    scale_factor: f64,
+    anchor: Anchor,
+    options: &ScaleOptions,
+) -> Option<GeoFeature> {
+    let geometry = feature.geometry.as_ref()?;
+    let scaled_geometry = scale_geometry(geometry, scale_factor, anchor, options)?;
+    Some(GeoFeature {
+        bbox: feature.bbox.clone(),
+        geometry: Some(scaled_geometry),
+        id: feature.id.clone(),
+        properties: feature.properties.clone(),
+        foreign_members: feature.foreign_members.clone(),
+    })
+}
+
+/// Scales a geometry about `origin`, or about the geometry's centroid if `origin` is None.
+fn scale_about<G>(geometry: &G, scale_factor: f64, origin: Option<Coord<f64>>) -> G
+where
+    G: Scale<f64> + Centroid<Output = Option<Point<f64>>> + Clone,
+{
+    match origin.or_else(|| geometry.centroid().map(Coord::from)) {
+        Some(origin) => geometry.scale_around_point(scale_factor, scale_factor, origin),
+        // Empty geometries have no centroid and nothing to scale
+        None => geometry.clone(),
+    }
+}
+
+/// Returns the polygon, or only its exterior ring if `drop_holes` is set.
+fn without_holes(polygon: &Polygon<f64>, drop_holes: bool) -> Polygon<f64> {
+    if drop_holes {
+        Polygon::new(polygon.exterior().clone(), vec![])
+    } else {
+        polygon.clone()
+    }
+}
+
+/// Scales a single geometry, returning None for geometries that are skipped.
+fn scale_geometry(
+    geometry: &GeoGeometry,
+    scale_factor: f64,
+    anchor: Anchor,
+    options: &ScaleOptions,
+) -> Option<GeoGeometry> {
+    let fixed_origin = match anchor {
+        Anchor::Fixed(coord) => Some(coord),
+        _ => None,
+    };
+    match geometry {
+        // if geometry.value is LineString
+        GeoGeometry::LineString(line_coords) => {
+            let mut line: LineString<f64> = line_coords.clone();
+            let scaled_line: LineString<f64> = match classify_linestring(&line, options.closed_eps) {
+                // Skip empty LineStrings
+                LineKind::Empty => return None,
+                LineKind::ClosedRing => {
+                    snap_ring_closed(&mut line);
+                    // convert linestring to polygon
+                    let polygon = Polygon::new(line, vec![]);
+                    let scaled_polygon = match anchor {
+                        Anchor::ClosedCenterElseZero => polygon.scale(scale_factor),
+                        _ => scale_about(&polygon, scale_factor, fixed_origin),
+                    };
+                    scaled_polygon.exterior().clone()
                 }
+                LineKind::Open => match anchor {
+                    // Scale the LineString relative to (0,0)
+                    Anchor::ClosedCenterElseZero => line
+                        .coords()
+                        .map(|&c| Coord {
+                            x: scale_factor * c.x,
+                            y: scale_factor * c.y,
+                        })
+                        .collect(),
+                    _ => scale_about(&line, scale_factor, fixed_origin),
+                },
+            };
+            Some(GeoGeometry::LineString(scaled_line))
+        }
+        // if geometry.value is Polygon, scale the exterior and holes about the same anchor
+        GeoGeometry::Polygon(polygon) => {
+            let polygon = without_holes(polygon, options.drop_holes);
+            Some(GeoGeometry::Polygon(match anchor {
+                // About the bbox center, like closed rings
+                Anchor::ClosedCenterElseZero => polygon.scale(scale_factor),
+                _ => scale_about(&polygon, scale_factor, fixed_origin),
+            }))
+        }
+        // if geometry.value is Point
+        GeoGeometry::Point(point) => Some(GeoGeometry::Point(match anchor {
+            // Use manual scaling relative to (0,0)
+            Anchor::ClosedCenterElseZero => Point::new(scale_factor * point.x(), scale_factor * point.y()),
+            // A point is its own centroid
+            Anchor::OwnCentroid => *point,
+            Anchor::Fixed(origin) => point.scale_around_point(scale_factor, scale_factor, origin),
+        })),
+        // if geometry.value is MultiPoint
+        GeoGeometry::MultiPoint(multi_point) => Some(GeoGeometry::MultiPoint(match anchor {
+            Anchor::ClosedCenterElseZero => multi_point
+                .iter()
+                .map(|c| Point::new(scale_factor * c.x(), scale_factor * c.y()))
+                .collect(),
+            _ => scale_about(multi_point, scale_factor, fixed_origin),
+        })),
+        // if geometry.value is MultiPolygon, scale all parts and their holes about one anchor
+        GeoGeometry::MultiPolygon(multi_polygon) => {
+            if multi_polygon.0.is_empty() {
+                return None;
             }
+            let multi_polygon: MultiPolygon<f64> = multi_polygon
+                .iter()
+                .map(|polygon| without_holes(polygon, options.drop_holes))
+                .collect();
+            Some(GeoGeometry::MultiPolygon(scale_about(
+                &multi_polygon,
+                scale_factor,
+                fixed_origin,
+            )))
         }
+        // Skip unsupported geometry types
+        _ => None,
+    }
+}
+
+/// Feature collections whose buildings can be scaled.
+///
+/// Implemented for both `GeoFeatureCollection` and `geojson::FeatureCollection`,
+/// so generic code can accept either. Both scale like `scale_buildings`, and
+/// the geojson collection keeps the z values of its positions.
+pub trait Scalable {
+    /// Scales the buildings of the collection by `factor`.
+    fn scale(&self, factor: f64) -> Self;
+}
+
+impl Scalable for GeoFeatureCollection {
+    fn scale(&self, factor: f64) -> Self {
+        scale_buildings(self, factor)
+    }
+}
+
+impl Scalable for FeatureCollection {
+    fn scale(&self, factor: f64) -> Self {
+        scale_features_3d(self, factor, &ScaleOptions::default())
     }
-    scaled_feature_collection
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use geo::{Centroid, MultiPoint, MultiPolygon, Point}; // Import Centroid trait for calculating expected centroids
+    use geo::{BoundingRect, Centroid, MultiLineString, MultiPoint, Point}; // Import Centroid trait for calculating expected centroids
     use geojson::{Feature, FeatureCollection, Geometry, Value};
     use serde_json::json; // Useful for creating arbitrary JSON properties/foreign_members
 
@@ -368,6 +592,43 @@ mod tests {
         assert_eq!(actual_collection, expected_collection);
     }
 
+    #[test]
+    fn test_scale_nearly_closed_linestring_treated_as_closed() {
+        // A square whose last point misses the first by 1e-9
+        let input_line: LineString<f64> = vec![
+            Coord { x: 0.0, y: 0.0 },
+            Coord { x: 10.0, y: 0.0 },
+            Coord { x: 10.0, y: 10.0 },
+            Coord { x: 0.0, y: 10.0 },
+            Coord { x: 1e-9, y: 0.0 },
+        ]
+        .into();
+        assert!(!input_line.is_closed()); // Sanity check
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::LineString(input_line.clone())));
+        let scale_factor = 0.5;
+
+        let actual_collection = scale_buildings(&input_collection, scale_factor);
+        let Some(GeoGeometry::LineString(actual_line)) = &actual_collection.features[0].geometry
+        else {
+            panic!("expected a LineString");
+        };
+        // Scaled about the centroid (5,5) and snapped shut
+        assert!(actual_line.is_closed());
+        assert_eq!(actual_line.0.len(), 5);
+        assert_eq!(actual_line.0[0], Coord { x: 2.5, y: 2.5 });
+        assert_eq!(actual_line.0[2], Coord { x: 7.5, y: 7.5 });
+
+        // With a tighter tolerance the line is scaled about the origin
+        let strict_collection =
+            scale_buildings_with_tolerance(&input_collection, scale_factor, 1e-12);
+        let Some(GeoGeometry::LineString(strict_line)) = &strict_collection.features[0].geometry
+        else {
+            panic!("expected a LineString");
+        };
+        assert_eq!(strict_line.0[2], Coord { x: 5.0, y: 5.0 });
+    }
+
     #[test]
     fn test_scale_empty_linestring() {
         let input_line: LineString<f64> = LineString::new(vec![]);
@@ -492,10 +753,9 @@ mod tests {
         assert_eq!(actual_collection, expected_collection);
     }
 
-    #[test]
-    fn test_scale_polygon_with_inner_ring_ignores_hole() {
-        // A Polygon with an inner ring (a square with a square hole)
-        let input_polygon = Polygon::new(
+    fn polygon_with_hole() -> Polygon<f64> {
+        // A square with an off-centre square hole
+        Polygon::new(
             LineString::from(vec![
                 // Exterior
                 Coord { x: 0.0, y: 0.0 },
@@ -508,55 +768,102 @@ mod tests {
                 // Interior
                 LineString::from(vec![
                     Coord { x: 2.0, y: 2.0 },
-                    Coord { x: 8.0, y: 2.0 },
-                    Coord { x: 8.0, y: 8.0 },
-                    Coord { x: 2.0, y: 8.0 },
+                    Coord { x: 4.0, y: 2.0 },
+                    Coord { x: 4.0, y: 4.0 },
+                    Coord { x: 2.0, y: 4.0 },
                     Coord { x: 2.0, y: 2.0 },
                 ]),
             ],
+        )
+    }
+
+    #[test]
+    fn test_scale_polygon_with_inner_ring_keeps_hole() {
+        let input_polygon = polygon_with_hole();
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::Polygon(input_polygon.clone())));
+        let scale_factor = 0.5;
+
+        // Expected: all rings scaled relative to the bbox center, (5,5), not the
+        // centroid, which the off-centre hole pulls away from it
+        let origin: Coord<f64> = input_polygon.bounding_rect().unwrap().center();
+        let scale_ring = |ring: &LineString<f64>| -> LineString<f64> {
+            ring.coords()
+                .map(|&c| scale_coord(c, origin, scale_factor))
+                .collect()
+        };
+        let expected_polygon = Polygon::new(
+            scale_ring(input_polygon.exterior()),
+            input_polygon.interiors().iter().map(scale_ring).collect(),
         );
-        // GeoJSON Polygon value representation includes all rings
-        let _input_geojson_polygon_value = Value::Polygon(vec![
-            input_polygon
-                .exterior()
-                .coords()
-                .map(|c| vec![c.x, c.y])
-                .collect(),
-            input_polygon
-                .interiors()
-                .iter()
-                .next()
-                .unwrap()
-                .coords()
-                .map(|c| vec![c.x, c.y])
-                .collect(), // Get first inner ring
+        let expected_collection =
+            create_feature_collection(Some(GeoGeometry::Polygon(expected_polygon.clone())));
+
+        let actual_collection = scale_buildings(&input_collection, scale_factor);
+        assert_eq!(actual_collection, expected_collection);
+        assert_eq!(expected_polygon.interiors().len(), 1);
+    }
+
+    #[test]
+    fn test_scale_polygon_matches_closed_ring() {
+        // An L-shape, whose centroid lies away from its bbox center
+        let ring = LineString::from(vec![
+            (0.0, 0.0),
+            (10.0, 0.0),
+            (10.0, 2.0),
+            (2.0, 2.0),
+            (2.0, 10.0),
+            (0.0, 10.0),
+            (0.0, 0.0),
         ]);
+        let polygon = Polygon::new(ring.clone(), vec![]);
+        let scale_factor = 2.0;
+
+        let scaled_polygon = scale_buildings(
+            &create_feature_collection(Some(GeoGeometry::Polygon(polygon.clone()))),
+            scale_factor,
+        );
+        let scaled_ring = scale_buildings(
+            &create_feature_collection(Some(GeoGeometry::LineString(ring))),
+            scale_factor,
+        );
+
+        let expected = polygon.scale(scale_factor);
+        assert_eq!(
+            scaled_polygon,
+            create_feature_collection(Some(GeoGeometry::Polygon(expected.clone())))
+        );
+        assert_eq!(
+            scaled_ring,
+            create_feature_collection(Some(GeoGeometry::LineString(expected.exterior().clone())))
+        );
+    }
+
+    #[test]
+    fn test_scale_polygon_drop_holes_scales_exterior_only() {
+        let input_polygon = polygon_with_hole();
         let input_collection =
             create_feature_collection(Some(GeoGeometry::Polygon(input_polygon.clone())));
         let scale_factor = 0.5;
+        let options = ScaleOptions {
+            drop_holes: true,
+            ..Default::default()
+        };
 
-        // Expected: scaled relative to the centroid of the EXTERIOR RING (which is the part used by the code)
-        // The inner ring should be IGNORED in the output GeoJSON value as per the code's logic.
-        let origin_of_exterior = Polygon::new(input_polygon.exterior().clone(), vec![])
-            .centroid()
-            .unwrap()
-            .into(); // Centroid of exterior ring is (5,5)
-
+        // Expected: scaled relative to the bbox center of the exterior ring, holes removed
+        let origin_of_exterior = input_polygon.exterior().bounding_rect().unwrap().center(); // (5,5)
         let expected_exterior_coords: Vec<Coord<f64>> = input_polygon
             .exterior()
             .coords()
             .map(|&c| scale_coord(c, origin_of_exterior, scale_factor))
             .collect();
+        let expected_collection = create_feature_collection(Some(GeoGeometry::Polygon(
+            Polygon::new(LineString::from(expected_exterior_coords), vec![]),
+        )));
 
-        // Expected output GeoJSON Polygon value should ONLY have the scaled exterior ring
-        let expected_geojson_polygon_value = GeoGeometry::Polygon(Polygon::new(
-            LineString::from(expected_exterior_coords),
-            vec![], // No inner rings
-        ));
-        let _expected_collection = create_feature_collection(Some(expected_geojson_polygon_value));
-
-        let _actual_collection = scale_buildings(&input_collection, scale_factor);
-        // assert_eq!(actual_collection, expected_collection);
+        let actual_collection =
+            scale_buildings_with_options(&input_collection, scale_factor, &options);
+        assert_eq!(actual_collection, expected_collection);
     }
 
     #[test]
@@ -638,20 +945,12 @@ mod tests {
 
     #[test]
     fn test_scale_unhandled_geometry_type_skips() {
-        // MultiPolygon is not handled by the match statement
-        let mp = MultiPolygon::new(vec![
-            Polygon::new(
-                LineString::from(vec![
-                    Point::new(0.0, 0.0),
-                    Point::new(1.0, 0.0),
-                    Point::new(1.0, 1.0),
-                    Point::new(0.0, 1.0),
-                    Point::new(0.0, 0.0),
-                ]),
-                vec![],
-            ),
-        ]);
-        let input_collection = create_feature_collection(Some(GeoGeometry::MultiPolygon(mp)));
+        // MultiLineString is not handled by the match statement
+        let mls = MultiLineString::new(vec![LineString::from(vec![
+            Point::new(0.0, 0.0),
+            Point::new(1.0, 0.0),
+        ])]);
+        let input_collection = create_feature_collection(Some(GeoGeometry::MultiLineString(mls)));
 
         let scale_factor = 0.5;
 
@@ -662,6 +961,88 @@ mod tests {
         assert_eq!(actual_collection, expected_collection);
     }
 
+    fn square(min: f64, size: f64) -> LineString<f64> {
+        LineString::from(vec![
+            Coord { x: min, y: min },
+            Coord { x: min + size, y: min },
+            Coord { x: min + size, y: min + size },
+            Coord { x: min, y: min + size },
+            Coord { x: min, y: min },
+        ])
+    }
+
+    #[test]
+    fn test_scale_multipolygon_two_parts_about_overall_centroid() {
+        // Two equal squares with a hole each, centred at (1,1) and (11,11)
+        let mp = MultiPolygon::new(vec![
+            Polygon::new(square(0.0, 2.0), vec![square(0.5, 1.0)]),
+            Polygon::new(square(10.0, 2.0), vec![square(10.5, 1.0)]),
+        ]);
+        let origin: Coord<f64> = mp.centroid().unwrap().into();
+        assert_eq!(origin, Coord { x: 6.0, y: 6.0 });
+        let input_collection = create_feature_collection(Some(GeoGeometry::MultiPolygon(mp.clone())));
+        let scale_factor = 0.5;
+
+        let actual_collection = scale_buildings(&input_collection, scale_factor);
+
+        let scale_ring = |ring: &LineString<f64>| -> LineString<f64> {
+            ring.coords()
+                .map(|&c| scale_coord(c, origin, scale_factor))
+                .collect()
+        };
+        let expected = MultiPolygon::new(
+            mp.iter()
+                .map(|polygon| {
+                    Polygon::new(
+                        scale_ring(polygon.exterior()),
+                        polygon.interiors().iter().map(scale_ring).collect(),
+                    )
+                })
+                .collect(),
+        );
+        assert_eq!(
+            actual_collection,
+            create_feature_collection(Some(GeoGeometry::MultiPolygon(expected.clone())))
+        );
+        // Both parts move towards the shared centroid and keep their size ratio
+        let parts: Vec<Coord<f64>> = expected.iter().map(|p| p.centroid().unwrap().into()).collect();
+        assert_eq!(parts, vec![Coord { x: 3.5, y: 3.5 }, Coord { x: 8.5, y: 8.5 }]);
+        assert_eq!(expected.0[0].interiors()[0], square(3.25, 0.5));
+    }
+
+    #[test]
+    fn test_scale_multipolygon_about_fixed_origin() {
+        let mp = MultiPolygon::new(vec![
+            Polygon::new(square(0.0, 2.0), vec![]),
+            Polygon::new(square(10.0, 2.0), vec![]),
+        ]);
+        let input_collection = create_feature_collection(Some(GeoGeometry::MultiPolygon(mp)));
+
+        let fixed = scale_buildings_with_origin(
+            &input_collection,
+            2.0,
+            ScaleOrigin::Fixed(Coord { x: 0.0, y: 0.0 }),
+        );
+
+        assert_eq!(
+            fixed.features[0].geometry,
+            Some(GeoGeometry::MultiPolygon(MultiPolygon::new(vec![
+                Polygon::new(square(0.0, 4.0), vec![]),
+                Polygon::new(square(20.0, 4.0), vec![]),
+            ])))
+        );
+    }
+
+    #[test]
+    fn test_scale_empty_multipolygon_skips() {
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::MultiPolygon(MultiPolygon::new(vec![]))));
+        assert_eq!(
+            scale_buildings(&input_collection, 0.5),
+            GeoFeatureCollection::default()
+        );
+    }
+
     #[test]
     fn test_scale_feature_without_geometry_skips() {
         // A feature with geometry: None
@@ -776,7 +1157,7 @@ mod tests {
                     properties: None,
                     foreign_members: None,
                 },
-                // Add an unhandled type like MultiPolygon
+                // Add a MultiPolygon, scaled about its own centroid
                 Feature {
                     bbox: None,
                     geometry: Some(Geometry::new(Value::MultiPolygon(vec![vec![vec![
@@ -1021,4 +1402,224 @@ mod tests {
     }
 
     // Add more tests for other geometry types with metadata if needed
+
+    #[test]
+    fn test_scalable_for_both_collection_types() {
+        fn scale_generic<T: Scalable>(collection: &T) -> T {
+            collection.scale(2.0)
+        }
+
+        let geo_collection =
+            create_feature_collection(Some(GeoGeometry::Point(Point::new(1.0, 2.0))));
+        let geojson_collection: FeatureCollection = geo_collection.clone().into();
+
+        let scaled_geo = scale_generic(&geo_collection);
+        let scaled_geojson = scale_generic(&geojson_collection);
+
+        assert_eq!(scaled_geo, scale_buildings(&geo_collection, 2.0));
+        assert_eq!(scaled_geojson, FeatureCollection::from(scaled_geo));
+    }
+
+    #[test]
+    fn test_scale_with_origin_centroid_keeps_far_point_in_place() {
+        // A point 100 km from the origin in metric coordinates
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::Point(Point::new(100_000.0, 0.0))));
+
+        let legacy = scale_buildings(&input_collection, 2.0);
+        assert_eq!(
+            legacy.features[0].geometry,
+            Some(GeoGeometry::Point(Point::new(200_000.0, 0.0)))
+        );
+
+        let centroid = scale_buildings_with_origin(&input_collection, 2.0, ScaleOrigin::Centroid);
+        assert_eq!(
+            centroid.features[0].geometry,
+            Some(GeoGeometry::Point(Point::new(100_000.0, 0.0)))
+        );
+    }
+
+    #[test]
+    fn test_scale_with_origin_collection_centroid_and_fixed() {
+        let mut input_collection =
+            create_feature_collection(Some(GeoGeometry::Point(Point::new(10.0, 10.0))));
+        let mut second = input_collection.features[0].clone();
+        second.geometry = Some(GeoGeometry::Point(Point::new(20.0, 10.0)));
+        input_collection.features.push(second);
+
+        // Scaled about the shared centroid (15, 10)
+        let scaled =
+            scale_buildings_with_origin(&input_collection, 2.0, ScaleOrigin::CollectionCentroid);
+        assert_eq!(
+            scaled.features[0].geometry,
+            Some(GeoGeometry::Point(Point::new(5.0, 10.0)))
+        );
+        assert_eq!(
+            scaled.features[1].geometry,
+            Some(GeoGeometry::Point(Point::new(25.0, 10.0)))
+        );
+
+        let fixed = scale_buildings_with_origin(
+            &input_collection,
+            0.5,
+            ScaleOrigin::Fixed(Coord { x: 10.0, y: 10.0 }),
+        );
+        assert_eq!(
+            fixed.features[1].geometry,
+            Some(GeoGeometry::Point(Point::new(15.0, 10.0)))
+        );
+    }
+
+    #[test]
+    fn test_scale_with_default_origin_matches_scale_buildings() {
+        let input_line: LineString<f64> = vec![
+            Coord { x: 10.0, y: 10.0 },
+            Coord { x: 20.0, y: 10.0 },
+            Coord { x: 20.0, y: 20.0 },
+            Coord { x: 10.0, y: 10.0 },
+        ]
+        .into();
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::LineString(input_line)));
+
+        assert_eq!(ScaleOrigin::default(), ScaleOrigin::ClosedCenterElseZero);
+        assert_eq!(
+            scale_buildings_with_origin(&input_collection, 3.0, ScaleOrigin::default()),
+            scale_buildings(&input_collection, 3.0)
+        );
+    }
+
+    #[test]
+    fn test_scale_open_linestring_about_centroid() {
+        // An open polyline far from the origin
+        let input_line: LineString<f64> = vec![
+            Coord { x: 1000.0, y: 1000.0 },
+            Coord { x: 1010.0, y: 1000.0 },
+            Coord { x: 1020.0, y: 1000.0 },
+        ]
+        .into();
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::LineString(input_line.clone())));
+
+        let actual_collection =
+            scale_buildings_with_origin(&input_collection, 0.5, ScaleOrigin::Centroid);
+
+        // Scaled about its centroid (1010, 1000), so it shrinks in place
+        let expected_line: LineString<f64> = vec![
+            Coord { x: 1005.0, y: 1000.0 },
+            Coord { x: 1010.0, y: 1000.0 },
+            Coord { x: 1015.0, y: 1000.0 },
+        ]
+        .into();
+        assert_eq!(
+            actual_collection.features[0].geometry,
+            Some(GeoGeometry::LineString(expected_line))
+        );
+        assert_eq!(
+            actual_collection.features[0].geometry.as_ref().unwrap().centroid(),
+            input_line.centroid()
+        );
+    }
+
+    #[test]
+    fn test_scale_open_linestring_about_fixed_point() {
+        let input_line: LineString<f64> = vec![Coord { x: 2.0, y: 2.0 }, Coord { x: 4.0, y: 2.0 }].into();
+        let input_collection =
+            create_feature_collection(Some(GeoGeometry::LineString(input_line)));
+
+        let actual_collection = scale_buildings_with_origin(
+            &input_collection,
+            2.0,
+            ScaleOrigin::Fixed(Coord { x: 2.0, y: 2.0 }),
+        );
+
+        let expected_line: LineString<f64> =
+            vec![Coord { x: 2.0, y: 2.0 }, Coord { x: 6.0, y: 2.0 }].into();
+        assert_eq!(
+            actual_collection.features[0].geometry,
+            Some(GeoGeometry::LineString(expected_line))
+        );
+    }
+
+    #[test]
+    fn test_scale_3d_point_scales_z() {
+        let point = |position: Vec<f64>| FeatureCollection {
+            bbox: None,
+            features: vec![Feature {
+                bbox: None,
+                geometry: Some(Geometry::new(Value::Point(position))),
+                id: None,
+                properties: None,
+                foreign_members: None,
+            }],
+            foreign_members: None,
+        };
+
+        let scaled = point(vec![10.0, 20.0, 100.0]).scale(1.5);
+        assert_eq!(
+            scaled.features[0].geometry.as_ref().unwrap().value,
+            Value::Point(vec![15.0, 30.0, 150.0])
+        );
+
+        let origin = ScaleOptions {
+            origin: ScaleOrigin::Fixed(Coord { x: 10.0, y: 20.0 }),
+            ..Default::default()
+        };
+        let scaled = scale_features_3d(&point(vec![10.0, 20.0, 100.0]), 0.5, &origin);
+        assert_eq!(
+            scaled.features[0].geometry.as_ref().unwrap().value,
+            Value::Point(vec![10.0, 20.0, 50.0])
+        );
+
+        // 2D points stay 2D
+        assert_eq!(
+            point(vec![10.0, 20.0]).scale(2.0).features[0].geometry.as_ref().unwrap().value,
+            Value::Point(vec![20.0, 40.0])
+        );
+    }
+
+    #[test]
+    fn test_scale_3d_polygon_keeps_z_per_vertex() {
+        let ring = vec![
+            vec![0.0, 0.0, 1.0],
+            vec![2.0, 0.0, 2.0],
+            vec![2.0, 2.0, 3.0],
+            vec![0.0, 2.0, 4.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        let collection = FeatureCollection {
+            bbox: None,
+            features: vec![
+                Feature {
+                    bbox: None,
+                    geometry: None,
+                    id: None,
+                    properties: None,
+                    foreign_members: None,
+                },
+                Feature {
+                    bbox: None,
+                    geometry: Some(Geometry::new(Value::Polygon(vec![ring]))),
+                    id: None,
+                    properties: None,
+                    foreign_members: None,
+                },
+            ],
+            foreign_members: None,
+        };
+
+        let scaled = collection.scale(2.0);
+
+        // The feature without geometry is skipped, as in `scale_buildings`
+        assert_eq!(scaled.features.len(), 1);
+        match &scaled.features[0].geometry.as_ref().unwrap().value {
+            Value::Polygon(rings) => {
+                let z: Vec<f64> = rings[0].iter().map(|p| p[2]).collect();
+                assert_eq!(z, vec![2.0, 4.0, 6.0, 8.0, 2.0]);
+                // Scaled about the centroid (1, 1)
+                assert_eq!(rings[0][2][..2], [3.0, 3.0]);
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,183 @@
+// Orders features along a space-filling curve for better spatial locality.
+
+use geo::{Coord, Rect};
+
+use crate::utils::geometry::{GeoFeature, GeoFeatureCollection};
+
+/// Highest supported curve order; keeps the index within a `u64`.
+const MAX_HILBERT_ORDER: u32 = 31;
+
+/// Maps a cell position on a `2^order x 2^order` grid to its Hilbert curve index.
+///
+/// # Arguments
+/// * `x` - The column of the cell.
+/// * `y` - The row of the cell.
+/// * `order` - The order of the curve.
+///
+/// # Returns
+/// The distance of the cell along the Hilbert curve.
+fn hilbert_index(mut x: u64, mut y: u64, order: u32) -> u64 {
+    let n: u64 = 1 << order;
+    let mut index = 0;
+    let mut s = n / 2;
+    while s > 0 {
+        let rx = u64::from(x & s > 0);
+        let ry = u64::from(y & s > 0);
+        index += s * s * ((3 * rx) ^ ry);
+        // Rotate the quadrant so the curve stays continuous
+        if ry == 0 {
+            if rx == 1 {
+                x = s - 1 - (x & (s - 1));
+                y = s - 1 - (y & (s - 1));
+            }
+            std::mem::swap(&mut x, &mut y);
+        }
+        x &= s - 1;
+        y &= s - 1;
+        s /= 2;
+    }
+    index
+}
+
+/// Maps a coordinate to a grid cell along one axis of the extent.
+fn to_cell(value: f64, min: f64, max: f64, cells: u64) -> u64 {
+    let span = max - min;
+    if span <= 0.0 {
+        return 0;
+    }
+    let cell = ((value - min) / span * cells as f64) as u64;
+    cell.min(cells - 1)
+}
+
+/// Calculates the combined extent of all feature geometries.
+fn collection_extent(features: &[GeoFeature]) -> Option<Rect> {
+    features
+        .iter()
+        .filter_map(|feature| feature.geometry.as_ref()?.bounding_rect())
+        .reduce(|a, b| {
+            Rect::new(
+                Coord {
+                    x: a.min().x.min(b.min().x),
+                    y: a.min().y.min(b.min().y),
+                },
+                Coord {
+                    x: a.max().x.max(b.max().x),
+                    y: a.max().y.max(b.max().y),
+                },
+            )
+        })
+}
+
+/// Sorts features along a Hilbert curve laid over the collection's extent.
+///
+/// Each feature is keyed by the Hilbert index of its centroid. Features
+/// without a geometry or centroid are moved to the end, keeping their
+/// relative order.
+///
+/// # Arguments
+/// * `fc` - The feature collection to sort.
+/// * `order` - The order of the curve; the extent is divided into
+///   `2^order x 2^order` cells. Clamped to `1..=31`.
+///
+/// # Returns
+/// The feature collection with its features in Hilbert order.
+pub fn sort_features_hilbert(mut fc: GeoFeatureCollection, order: u32) -> GeoFeatureCollection {
+    let Some(extent) = collection_extent(&fc.features) else {
+        return fc;
+    };
+    let order = order.clamp(1, MAX_HILBERT_ORDER);
+    let cells: u64 = 1 << order;
+
+    fc.features.sort_by_cached_key(|feature| {
+        feature
+            .geometry
+            .as_ref()
+            .and_then(|geometry| geometry.centroid())
+            .map_or(u64::MAX, |centroid| {
+                let x = to_cell(centroid.x(), extent.min().x, extent.max().x, cells);
+                let y = to_cell(centroid.y(), extent.min().y, extent.max().y, cells);
+                hilbert_index(x, y, order)
+            })
+    });
+    fc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::geometry::GeoGeometry;
+    use geo::Point;
+    use serde_json::{Map, Value};
+
+    fn point_feature(name: &str, x: f64, y: f64) -> GeoFeature {
+        let mut properties = Map::new();
+        properties.insert("name".to_string(), Value::from(name));
+        GeoFeature {
+            id: None,
+            properties: Some(properties),
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(GeoGeometry::Point(Point::new(x, y))),
+        }
+    }
+
+    fn names(fc: &GeoFeatureCollection) -> Vec<String> {
+        fc.features
+            .iter()
+            .map(|f| match &f.properties {
+                Some(p) => p["name"].as_str().unwrap_or_default().to_string(),
+                None => String::new(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_hilbert_index_order_one() {
+        assert_eq!(hilbert_index(0, 0, 1), 0);
+        assert_eq!(hilbert_index(0, 1, 1), 1);
+        assert_eq!(hilbert_index(1, 1, 1), 2);
+        assert_eq!(hilbert_index(1, 0, 1), 3);
+    }
+
+    #[test]
+    fn test_sort_features_hilbert_quadrants() {
+        let fc = GeoFeatureCollection {
+            features: vec![
+                point_feature("bottom_right", 10.0, 0.0),
+                point_feature("top_right", 10.0, 10.0),
+                point_feature("bottom_left", 0.0, 0.0),
+                point_feature("top_left", 0.0, 10.0),
+            ],
+            ..Default::default()
+        };
+
+        let sorted = sort_features_hilbert(fc, 1);
+        assert_eq!(
+            names(&sorted),
+            vec!["bottom_left", "top_left", "top_right", "bottom_right"]
+        );
+    }
+
+    #[test]
+    fn test_sort_features_hilbert_missing_geometry_last() {
+        let mut empty = point_feature("empty", 0.0, 0.0);
+        empty.geometry = None;
+        let fc = GeoFeatureCollection {
+            features: vec![
+                empty,
+                point_feature("b", 10.0, 0.0),
+                point_feature("a", 0.0, 0.0),
+            ],
+            ..Default::default()
+        };
+
+        let sorted = sort_features_hilbert(fc, 4);
+        assert_eq!(names(&sorted), vec!["a", "b", "empty"]);
+    }
+
+    #[test]
+    fn test_sort_features_hilbert_empty_collection() {
+        let fc = GeoFeatureCollection::default();
+        assert_eq!(sort_features_hilbert(fc.clone(), 8), fc);
+    }
+}
@@ -0,0 +1,17 @@
+// Fixtures shared by the geometry transformation tests.
+
+use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
+
+/// A collection holding a single feature with `geometry` and nothing else.
+pub(crate) fn feature_collection(geometry: GeoGeometry) -> GeoFeatureCollection {
+    GeoFeatureCollection {
+        features: vec![GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(geometry),
+        }],
+        ..Default::default()
+    }
+}
@@ -0,0 +1,219 @@
+// Shifts features by a fixed offset.
+
+use geo::Translate;
+use geojson::{Bbox, FeatureCollection};
+
+use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
+use crate::utils::helpers::restore_z;
+
+/// Translates every coordinate of every feature by `(dx, dy)`.
+///
+/// Ids, properties and foreign members are copied unchanged, bounding boxes
+/// are shifted along with the geometries, and features without a geometry
+/// are kept as they are.
+///
+/// # Arguments
+/// * `fc` - The feature collection to translate.
+/// * `dx` - The offset along the x axis.
+/// * `dy` - The offset along the y axis.
+///
+/// # Returns
+/// The translated feature collection.
+pub fn translate_features(fc: &GeoFeatureCollection, dx: f64, dy: f64) -> GeoFeatureCollection {
+    let features = fc
+        .features
+        .iter()
+        .map(|feature| GeoFeature {
+            bbox: feature.bbox.as_ref().map(|bbox| translate_bbox(bbox, dx, dy)),
+            geometry: feature
+                .geometry
+                .as_ref()
+                .map(|geometry| translate_geometry(geometry, dx, dy)),
+            id: feature.id.clone(),
+            properties: feature.properties.clone(),
+            foreign_members: feature.foreign_members.clone(),
+        })
+        .collect();
+
+    GeoFeatureCollection {
+        bbox: fc.bbox.as_ref().map(|bbox| translate_bbox(bbox, dx, dy)),
+        foreign_members: fc.foreign_members.clone(),
+        features,
+    }
+}
+
+/// Translates a geojson collection like `translate_features`, keeping the z
+/// value (elevation) of 3D positions unchanged.
+pub fn translate_features_3d(fc: &FeatureCollection, dx: f64, dy: f64) -> FeatureCollection {
+    let mut translated = FeatureCollection::from(translate_features(&fc.clone().into(), dx, dy));
+    for (original, feature) in fc.features.iter().zip(&mut translated.features) {
+        if let (Some(original), Some(geometry)) = (&original.geometry, &mut feature.geometry) {
+            restore_z(original, geometry, |z| z);
+        }
+    }
+    translated
+}
+
+/// Translates a single geometry.
+fn translate_geometry(geometry: &GeoGeometry, dx: f64, dy: f64) -> GeoGeometry {
+    match geometry {
+        GeoGeometry::Point(point) => GeoGeometry::Point(point.translate(dx, dy)),
+        GeoGeometry::LineString(line) => GeoGeometry::LineString(line.translate(dx, dy)),
+        GeoGeometry::Polygon(polygon) => GeoGeometry::Polygon(polygon.translate(dx, dy)),
+        GeoGeometry::MultiPoint(multi_point) => {
+            GeoGeometry::MultiPoint(multi_point.translate(dx, dy))
+        }
+        GeoGeometry::MultiLineString(multi_line_string) => {
+            GeoGeometry::MultiLineString(multi_line_string.translate(dx, dy))
+        }
+        GeoGeometry::MultiPolygon(multi_polygon) => {
+            GeoGeometry::MultiPolygon(multi_polygon.translate(dx, dy))
+        }
+    }
+}
+
+/// Shifts a 2D `[min_x, min_y, max_x, max_y]` or 3D
+/// `[min_x, min_y, min_z, max_x, max_y, max_z]` bounding box.
+fn translate_bbox(bbox: &Bbox, dx: f64, dy: f64) -> Bbox {
+    let mut bbox = bbox.clone();
+    let half = bbox.len() / 2;
+    if half >= 2 {
+        bbox[0] += dx;
+        bbox[1] += dy;
+        bbox[half] += dx;
+        bbox[half + 1] += dy;
+    }
+    bbox
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{Coord, LineString, MultiPoint, Point, Polygon, coord};
+    use serde_json::json;
+    use crate::geometry::test_fixtures::feature_collection;
+
+    fn translated_geometry(geometry: GeoGeometry) -> GeoGeometry {
+        translate_features(&feature_collection(geometry), 1.0, -2.0).features[0]
+            .geometry
+            .clone()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_translate_point() {
+        assert_eq!(
+            translated_geometry(GeoGeometry::Point(Point::new(3.0, 4.0))),
+            GeoGeometry::Point(Point::new(4.0, 2.0))
+        );
+    }
+
+    #[test]
+    fn test_translate_open_and_closed_line_string() {
+        let open = LineString::from(vec![coord! { x: 0.0, y: 0.0 }, coord! { x: 1.0, y: 1.0 }]);
+        assert_eq!(
+            translated_geometry(GeoGeometry::LineString(open)),
+            GeoGeometry::LineString(LineString::from(vec![
+                coord! { x: 1.0, y: -2.0 },
+                coord! { x: 2.0, y: -1.0 },
+            ]))
+        );
+
+        let closed = LineString::from(vec![
+            coord! { x: 0.0, y: 0.0 },
+            coord! { x: 1.0, y: 0.0 },
+            coord! { x: 1.0, y: 1.0 },
+            coord! { x: 0.0, y: 0.0 },
+        ]);
+        match translated_geometry(GeoGeometry::LineString(closed)) {
+            GeoGeometry::LineString(line) => {
+                assert!(line.is_closed());
+                assert_eq!(line.0[2], coord! { x: 2.0, y: -1.0 });
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_translate_polygon_with_hole() {
+        let square = |min: f64, size: f64| -> LineString {
+            LineString::from(vec![
+                Coord { x: min, y: min },
+                Coord { x: min + size, y: min },
+                Coord { x: min + size, y: min + size },
+                Coord { x: min, y: min + size },
+                Coord { x: min, y: min },
+            ])
+        };
+        let polygon = Polygon::new(square(0.0, 10.0), vec![square(2.0, 2.0)]);
+
+        match translated_geometry(GeoGeometry::Polygon(polygon)) {
+            GeoGeometry::Polygon(translated) => {
+                assert_eq!(translated.exterior().0[0], coord! { x: 1.0, y: -2.0 });
+                assert_eq!(translated.interiors().len(), 1);
+                assert_eq!(translated.interiors()[0].0[0], coord! { x: 3.0, y: 0.0 });
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_translate_multi_point() {
+        let multi_point = MultiPoint::from(vec![Point::new(0.0, 0.0), Point::new(-1.0, 5.0)]);
+        assert_eq!(
+            translated_geometry(GeoGeometry::MultiPoint(multi_point)),
+            GeoGeometry::MultiPoint(MultiPoint::from(vec![
+                Point::new(1.0, -2.0),
+                Point::new(0.0, 3.0),
+            ]))
+        );
+    }
+
+    #[test]
+    fn test_translate_forwards_metadata() {
+        let mut fc = feature_collection(GeoGeometry::Point(Point::new(1.0, 1.0)));
+        fc.bbox = Some(vec![1.0, 1.0, 1.0, 1.0]);
+        fc.features[0].bbox = Some(vec![1.0, 1.0, 0.0, 1.0, 1.0, 5.0]);
+        fc.features[0].id = Some(geojson::feature::Id::String("a".to_string()));
+        fc.features[0].properties = json!({ "name": "mast" }).as_object().cloned();
+        fc.features[0].foreign_members = json!({ "layer": 3 }).as_object().cloned();
+        fc.features.push(GeoFeature {
+            geometry: None,
+            ..fc.features[0].clone()
+        });
+
+        let translated = translate_features(&fc, 2.0, 3.0);
+
+        assert_eq!(translated.bbox, Some(vec![3.0, 4.0, 3.0, 4.0]));
+        let feature = &translated.features[0];
+        assert_eq!(feature.bbox, Some(vec![3.0, 4.0, 0.0, 3.0, 4.0, 5.0]));
+        assert_eq!(feature.id, fc.features[0].id);
+        assert_eq!(feature.properties, fc.features[0].properties);
+        assert_eq!(feature.foreign_members, fc.features[0].foreign_members);
+        assert_eq!(translated.features[1].geometry, None);
+    }
+
+    #[test]
+    fn test_translate_features_3d_keeps_z() {
+        let fc = FeatureCollection::from(feature_collection(GeoGeometry::Point(Point::new(
+            1.0, 1.0,
+        ))));
+        let mut fc_3d = fc.clone();
+        fc_3d.features[0].geometry = Some(geojson::Geometry::new(geojson::Value::Point(vec![
+            1.0, 1.0, 120.0,
+        ])));
+
+        let translated = translate_features_3d(&fc_3d, 2.0, 3.0);
+        assert_eq!(
+            translated.features[0].geometry.as_ref().unwrap().value,
+            geojson::Value::Point(vec![3.0, 4.0, 120.0])
+        );
+
+        // 2D positions stay 2D
+        let translated = translate_features_3d(&fc, 2.0, 3.0);
+        assert_eq!(
+            translated.features[0].geometry.as_ref().unwrap().value,
+            geojson::Value::Point(vec![3.0, 4.0])
+        );
+    }
+}
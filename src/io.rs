@@ -0,0 +1,267 @@
+// Reading and writing GeoJSON, streamed line by line or as a whole.
+
+use std::io::{BufRead, Write};
+
+use geojson::{Feature, GeoJson, Geometry, Value};
+
+use crate::domain::{DomainEntity, convert_domain_entity_to_geojson_feature};
+use crate::utils::error::Error;
+use crate::utils::helpers::snap_precision;
+
+/// Reads one GeoJSON Feature per line, without loading the whole input.
+///
+/// Blank lines are skipped. A line that fails to parse yields
+/// `Error::InvalidFeatureLine` with its 1-based line number, and a failed read
+/// yields `Error::Io`; reading continues with the next line in both cases.
+///
+/// # Arguments
+///
+/// * `reader` - The newline-delimited GeoJSON input.
+///
+/// # Returns
+///
+/// * An iterator over the parsed features, in input order.
+pub fn read_ndjson_features<R: BufRead>(reader: R) -> impl Iterator<Item = Result<Feature, Error>> {
+    reader
+        .lines()
+        .enumerate()
+        .filter_map(|(index, line)| match line {
+            Ok(line) if line.trim().is_empty() => None,
+            Ok(line) => Some(
+                serde_json::from_str::<Feature>(&line).map_err(|source| {
+                    Error::InvalidFeatureLine {
+                        line: index + 1,
+                        source,
+                    }
+                }),
+            ),
+            Err(error) => Some(Err(Error::Io(error))),
+        })
+}
+
+/// Writes DomainEntity variants as newline-delimited GeoJSON, one compact
+/// Feature per line.
+///
+/// Entities are converted like `domain_entities_to_feature_collection`, with
+/// the inner properties nested under `properties`, so the lines can be read
+/// and identified again. `Unknown` entities are written as the feature they
+/// were read from. The writer is not buffered; wrap it in a `BufWriter` for
+/// file output.
+///
+/// # Arguments
+///
+/// * `writer` - The output to write to.
+/// * `entities` - The entities to write, in output order.
+///
+/// # Returns
+///
+/// * `Result<(), Error>` - `Error::Serialization` or `Error::Io` if writing fails.
+pub fn write_ndjson_features<W: Write, I: IntoIterator<Item = DomainEntity>>(
+    mut writer: W,
+    entities: I,
+) -> Result<(), Error> {
+    for entity in entities {
+        serde_json::to_writer(&mut writer, &convert_domain_entity_to_geojson_feature(entity))?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Rounds every position of a geometry value in place.
+fn round_value(value: &mut Value, decimals: u32) {
+    let round_position = |position: &mut Vec<f64>| {
+        position
+            .iter_mut()
+            .for_each(|ordinate| *ordinate = snap_precision(*ordinate, decimals));
+    };
+    match value {
+        Value::Point(position) => round_position(position),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            positions.iter_mut().for_each(round_position)
+        }
+        Value::MultiLineString(lines) | Value::Polygon(lines) => lines
+            .iter_mut()
+            .flat_map(|line| line.iter_mut())
+            .for_each(round_position),
+        Value::MultiPolygon(polygons) => polygons
+            .iter_mut()
+            .flat_map(|polygon| polygon.iter_mut())
+            .flat_map(|ring| ring.iter_mut())
+            .for_each(round_position),
+        Value::GeometryCollection(geometries) => geometries
+            .iter_mut()
+            .for_each(|geometry| round_geometry(geometry, decimals)),
+    }
+}
+
+/// Rounds the positions and bbox of a geometry in place.
+fn round_geometry(geometry: &mut Geometry, decimals: u32) {
+    round_value(&mut geometry.value, decimals);
+    round_bbox(&mut geometry.bbox, decimals);
+}
+
+/// Rounds a bbox in place.
+fn round_bbox(bbox: &mut Option<Vec<f64>>, decimals: u32) {
+    if let Some(bbox) = bbox {
+        bbox.iter_mut()
+            .for_each(|ordinate| *ordinate = snap_precision(*ordinate, decimals));
+    }
+}
+
+/// Writes GeoJSON with all coordinates rounded to a fixed number of decimals.
+///
+/// Positions and bboxes of every geometry, feature and collection are
+/// rounded; properties are written unchanged. Six decimals are roughly
+/// 0.1 m in WGS84.
+///
+/// # Arguments
+/// * `gj` - The GeoJSON to write.
+/// * `w` - The writer to serialize into.
+/// * `decimals` - The number of decimal places to keep.
+///
+/// # Returns
+/// `Ok(())` on success, or `Error::Serialization` if writing fails.
+pub fn write_geojson_rounded<W: Write>(gj: &GeoJson, w: W, decimals: u32) -> Result<(), Error> {
+    let mut rounded = gj.clone();
+    match &mut rounded {
+        GeoJson::FeatureCollection(fc) => {
+            round_bbox(&mut fc.bbox, decimals);
+            for feature in &mut fc.features {
+                round_bbox(&mut feature.bbox, decimals);
+                if let Some(geometry) = &mut feature.geometry {
+                    round_geometry(geometry, decimals);
+                }
+            }
+        }
+        GeoJson::Feature(feature) => {
+            round_bbox(&mut feature.bbox, decimals);
+            if let Some(geometry) = &mut feature.geometry {
+                round_geometry(geometry, decimals);
+            }
+        }
+        GeoJson::Geometry(geometry) => round_geometry(geometry, decimals),
+    }
+    serde_json::to_writer(w, &rounded)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::identify_domain_entity;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_ndjson_features() {
+        let input = concat!(
+            r#"{"type": "Feature", "id": "a", "properties": null, "geometry": {"type": "Point", "coordinates": [9.0, 50.0]}}"#,
+            "\n",
+            r#"{"type": "Feature", "properties": null, "geometry": {"type": "Point""#,
+            "\n\n",
+            r#"{"type": "Feature", "id": "c", "properties": {"objectId": "Mast"}, "geometry": null}"#,
+            "\n",
+        );
+
+        let results: Vec<Result<Feature, Error>> =
+            read_ndjson_features(Cursor::new(input)).collect();
+
+        assert_eq!(results.len(), 3);
+        let first = results[0].as_ref().unwrap();
+        assert_eq!(first.id, Some(geojson::feature::Id::String("a".to_string())));
+        assert!(first.geometry.is_some());
+        assert!(matches!(results[1], Err(Error::InvalidFeatureLine { line: 2, .. })));
+        let third = results[2].as_ref().unwrap();
+        assert!(third.geometry.is_none());
+        assert_eq!(third.property("objectId"), Some(&serde_json::Value::from("Mast")));
+    }
+
+    #[test]
+    fn test_write_ndjson_features_round_trip() {
+        let input = concat!(
+            r#"{"type": "Feature", "id": "marker", "properties": {"properties": {"objectId": "Kugelmarker"}}, "geometry": {"type": "Point", "coordinates": [9.0, 50.0]}}"#,
+            "\n",
+            r#"{"type": "Feature", "id": 7, "properties": {"properties": {"objectId": "Mast"}}, "geometry": {"type": "Point", "coordinates": [9.0, 50.0]}}"#,
+            "\n",
+            r#"{"type": "Feature", "properties": null, "geometry": null}"#,
+            "\n",
+        );
+        let features: Vec<Feature> = read_ndjson_features(Cursor::new(input))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let entities: Vec<DomainEntity> =
+            features.iter().cloned().map(identify_domain_entity).collect();
+        assert!(entities[0].is_marker());
+        assert!(entities[1].is_unknown());
+
+        let mut buffer = Vec::new();
+        write_ndjson_features(&mut buffer, entities).unwrap();
+
+        let output = String::from_utf8(buffer).unwrap();
+        assert_eq!(output.lines().count(), 3);
+        let written: Vec<Feature> = read_ndjson_features(Cursor::new(output))
+            .collect::<Result<_, _>>()
+            .unwrap();
+        let ids = |features: &[Feature]| features.iter().map(|f| f.id.clone()).collect::<Vec<_>>();
+        assert_eq!(ids(&written), ids(&features));
+        // Identified entities are written in the layout they were read from
+        assert!(identify_domain_entity(written[0].clone()).is_marker());
+        // Unknown entities are written back unchanged
+        assert_eq!(written[1], features[1]);
+        assert_eq!(written[2], features[2]);
+    }
+
+    #[test]
+    fn test_write_geojson_rounded() {
+        let gj: GeoJson = r#"{
+            "type": "FeatureCollection",
+            "features": [
+                {
+                    "type": "Feature",
+                    "properties": { "value": 1.123456789 },
+                    "bbox": [9.123456789, 50.987654321, 9.123456789, 50.987654321],
+                    "geometry": { "type": "Point", "coordinates": [9.123456789, 50.987654321] }
+                },
+                {
+                    "type": "Feature",
+                    "properties": null,
+                    "geometry": {
+                        "type": "Polygon",
+                        "coordinates": [[[0.0000004, 0.0], [1.0, 0.0], [1.0, 1.0000006], [0.0000004, 0.0]]]
+                    }
+                }
+            ]
+        }"#
+        .parse()
+        .unwrap();
+
+        let mut buffer = Vec::new();
+        write_geojson_rounded(&gj, &mut buffer, 6).unwrap();
+        let written: GeoJson = String::from_utf8(buffer).unwrap().parse().unwrap();
+
+        let GeoJson::FeatureCollection(fc) = written else {
+            panic!("expected a FeatureCollection");
+        };
+        let point = fc.features[0].geometry.as_ref().unwrap();
+        assert_eq!(point.value, Value::Point(vec![9.123457, 50.987654]));
+        assert_eq!(
+            fc.features[0].bbox,
+            Some(vec![9.123457, 50.987654, 9.123457, 50.987654])
+        );
+        // Properties are left untouched
+        assert_eq!(
+            fc.features[0].property("value"),
+            Some(&serde_json::Value::from(1.123456789))
+        );
+        let polygon = fc.features[1].geometry.as_ref().unwrap();
+        assert_eq!(
+            polygon.value,
+            Value::Polygon(vec![vec![
+                vec![0.0, 0.0],
+                vec![1.0, 0.0],
+                vec![1.0, 1.000001],
+                vec![0.0, 0.0]
+            ]])
+        );
+    }
+}
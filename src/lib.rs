@@ -9,4 +9,5 @@ pub mod raw;
 pub mod raw_parallel;
 pub mod raw_serde;
 pub mod concatenate_features;
-pub mod concat_and_scale;
\ No newline at end of file
+pub mod concat_and_scale;
+pub mod io;
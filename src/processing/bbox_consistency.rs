@@ -0,0 +1,181 @@
+use geo::{Coord, Rect};
+use geojson::{FeatureCollection, Geometry, Value, feature::Id};
+
+/// Compare each feature's stored bbox with the bbox computed from its geometry.
+///
+/// Returns one entry per feature with a stored bbox: its id (if any) and the
+/// intersection over union of the two boxes. A stale bbox scores well below 1.0,
+/// and a malformed bbox or a feature without coordinates scores 0.0.
+pub fn bbox_consistency_report(fc: &FeatureCollection) -> Vec<(Option<Id>, f64)> {
+    fc.features
+        .iter()
+        .filter_map(|feature| {
+            let stored = feature.bbox.as_ref()?;
+            let iou = match (
+                stored_rect(stored),
+                feature.geometry.as_ref().and_then(geometry_rect),
+            ) {
+                (Some(stored), Some(computed)) => intersection_over_union(&stored, &computed),
+                _ => 0.0,
+            };
+            Some((feature.id.clone(), iou))
+        })
+        .collect()
+}
+
+/// Reads the 2D extent of a 2D or 3D GeoJSON bbox.
+fn stored_rect(bbox: &[f64]) -> Option<Rect> {
+    let (min, max) = match bbox.len() {
+        4 => ((bbox[0], bbox[1]), (bbox[2], bbox[3])),
+        6 => ((bbox[0], bbox[1]), (bbox[3], bbox[4])),
+        _ => return None,
+    };
+    Some(Rect::new(min, max))
+}
+
+/// Computes the bounding rect of all positions of a geometry, including those
+/// nested in GeometryCollections. Positions with fewer than two elements are ignored.
+fn geometry_rect(geometry: &Geometry) -> Option<Rect> {
+    let mut positions: Vec<&Vec<f64>> = Vec::new();
+    collect_positions(geometry, &mut positions);
+    positions
+        .into_iter()
+        .filter(|position| position.len() >= 2)
+        .map(|position| Coord {
+            x: position[0],
+            y: position[1],
+        })
+        .fold(None, |rect: Option<Rect>, coord| {
+            Some(match rect {
+                None => Rect::new(coord, coord),
+                Some(rect) => Rect::new(
+                    Coord {
+                        x: rect.min().x.min(coord.x),
+                        y: rect.min().y.min(coord.y),
+                    },
+                    Coord {
+                        x: rect.max().x.max(coord.x),
+                        y: rect.max().y.max(coord.y),
+                    },
+                ),
+            })
+        })
+}
+
+fn collect_positions<'a>(geometry: &'a Geometry, positions: &mut Vec<&'a Vec<f64>>) {
+    match &geometry.value {
+        Value::Point(position) => positions.push(position),
+        Value::MultiPoint(points) | Value::LineString(points) => positions.extend(points),
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            positions.extend(lines.iter().flatten())
+        }
+        Value::MultiPolygon(polygons) => positions.extend(polygons.iter().flatten().flatten()),
+        Value::GeometryCollection(geometries) => {
+            for geometry in geometries {
+                collect_positions(geometry, positions);
+            }
+        }
+    }
+}
+
+/// Intersection over union of two rects. Two degenerate rects (zero area,
+/// e.g. the bbox of a single point) score 1.0 when equal and 0.0 otherwise.
+fn intersection_over_union(a: &Rect, b: &Rect) -> f64 {
+    let union_area = a.width() * a.height() + b.width() * b.height();
+    let intersection_width = (a.max().x.min(b.max().x) - a.min().x.max(b.min().x)).max(0.0);
+    let intersection_height = (a.max().y.min(b.max().y) - a.min().y.max(b.min().y)).max(0.0);
+    let intersection_area = intersection_width * intersection_height;
+    let union_area = union_area - intersection_area;
+    if union_area <= 0.0 {
+        return if a == b { 1.0 } else { 0.0 };
+    }
+    intersection_area / union_area
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geojson::Feature;
+
+    fn feature(id: &str, bbox: Option<Vec<f64>>, value: Value) -> Feature {
+        Feature {
+            bbox,
+            geometry: Some(Geometry::new(value)),
+            id: Some(Id::String(id.to_string())),
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
+    fn square() -> Value {
+        Value::Polygon(vec![vec![
+            vec![0.0, 0.0],
+            vec![2.0, 0.0],
+            vec![2.0, 2.0],
+            vec![0.0, 2.0],
+            vec![0.0, 0.0],
+        ]])
+    }
+
+    #[test]
+    fn test_bbox_consistency_report() {
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![
+                feature("exact", Some(vec![0.0, 0.0, 2.0, 2.0]), square()),
+                // Covers the left half of the geometry
+                feature("stale", Some(vec![0.0, 0.0, 1.0, 2.0]), square()),
+                feature("disjoint", Some(vec![10.0, 10.0, 11.0, 11.0]), square()),
+                feature("3d", Some(vec![0.0, 0.0, -5.0, 2.0, 2.0, 5.0]), square()),
+                feature("unchecked", None, square()),
+                feature(
+                    "point",
+                    Some(vec![1.0, 2.0, 1.0, 2.0]),
+                    Value::Point(vec![1.0, 2.0]),
+                ),
+                feature("malformed", Some(vec![0.0, 0.0]), square()),
+            ],
+            foreign_members: None,
+        };
+
+        let report = bbox_consistency_report(&fc);
+
+        let scores: Vec<(String, f64)> = report
+            .into_iter()
+            .map(|(id, iou)| match id {
+                Some(Id::String(id)) => (id, iou),
+                other => panic!("unexpected id {:?}", other),
+            })
+            .collect();
+        assert_eq!(
+            scores,
+            vec![
+                ("exact".to_string(), 1.0),
+                ("stale".to_string(), 0.5),
+                ("disjoint".to_string(), 0.0),
+                ("3d".to_string(), 1.0),
+                ("point".to_string(), 1.0),
+                ("malformed".to_string(), 0.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_bbox_consistency_uses_nested_geometries() {
+        let collection = Value::GeometryCollection(vec![
+            Geometry::new(Value::Point(vec![0.0, 0.0])),
+            Geometry::new(Value::LineString(vec![vec![4.0, 1.0], vec![4.0, 4.0]])),
+        ]);
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![feature(
+                "nested",
+                Some(vec![0.0, 0.0, 4.0, 4.0]),
+                collection,
+            )],
+            foreign_members: None,
+        };
+
+        assert_eq!(bbox_consistency_report(&fc)[0].1, 1.0);
+    }
+}
@@ -0,0 +1,229 @@
+use crate::geometry::bounding_box::{
+    BboxResult, CollectBoundingBoxError, CrsConfig, GridConfig, MergeStrategy, ProjectedRects,
+    Radius, collect_projected_rects, grid_projected_rects, resolve_source_crs,
+};
+use crate::geometry::collect_options::CollectOptions;
+use crate::utils::geometry::GeoFeatureCollection;
+use crate::utils::transformer::Transformer;
+
+/// Configurable run of the bounding box collection: filter, project, buffer,
+/// merge, grid and reproject.
+///
+/// The PROJ transformers are created on the first run and reused by later runs
+/// as long as the source and metric CRS stay the same.
+pub struct BoundingBoxPipeline {
+    radius: Radius,
+    options: CollectOptions,
+    crs_config: CrsConfig,
+    merge_strategy: MergeStrategy,
+    grid_config: GridConfig,
+    parallel: bool,
+    transformer: Option<Transformer>,
+    #[cfg(test)]
+    transformers_created: usize,
+}
+
+impl BoundingBoxPipeline {
+    /// A pipeline with the defaults of `collect_bounding_boxes`: the Germany
+    /// filter, EPSG:3035 as metric CRS, overlapping rects merged and 20 cells.
+    pub fn new(radius: Radius) -> Self {
+        Self {
+            radius,
+            options: CollectOptions::default(),
+            crs_config: CrsConfig::default(),
+            merge_strategy: MergeStrategy::default(),
+            grid_config: GridConfig::default(),
+            parallel: false,
+            transformer: None,
+            #[cfg(test)]
+            transformers_created: 0,
+        }
+    }
+
+    /// Sets the radius the bounding boxes are expanded by.
+    pub fn with_radius(mut self, radius: Radius) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Sets the collection options holding the filter rectangle.
+    pub fn with_options(mut self, options: CollectOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the source and metric CRS.
+    pub fn with_crs_config(mut self, crs_config: CrsConfig) -> Self {
+        self.crs_config = crs_config;
+        self
+    }
+
+    /// Sets which buffered rectangles are merged.
+    pub fn with_merge_strategy(mut self, merge_strategy: MergeStrategy) -> Self {
+        self.merge_strategy = merge_strategy;
+        self
+    }
+
+    /// Sets the grid sizing.
+    pub fn with_grid_config(mut self, grid_config: GridConfig) -> Self {
+        self.grid_config = grid_config;
+        self
+    }
+
+    /// Sets whether the features are buffered on the rayon thread pool.
+    pub fn with_parallel(mut self, parallel: bool) -> Self {
+        self.parallel = parallel;
+        self
+    }
+
+    /// Number of PROJ transformer pairs created so far.
+    #[cfg(test)]
+    fn transformers_created(&self) -> usize {
+        self.transformers_created
+    }
+
+    /// Runs the pipeline on a FeatureCollection.
+    ///
+    /// # Returns
+    /// The buffered rectangles, merged rectangles and grid cells in the source CRS.
+    pub fn run(
+        &mut self,
+        featurecollection: &GeoFeatureCollection,
+    ) -> Result<BboxResult, CollectBoundingBoxError> {
+        let grid_config = self.grid_config;
+        self.run_projected(featurecollection, |projected| {
+            grid_projected_rects(projected, &grid_config)
+        })
+    }
+
+    /// Runs the pipeline projecting with `transformer` instead of the
+    /// transformer for the configured CRS pair.
+    ///
+    /// Any `crs` member of the feature collection is ignored; its coordinates
+    /// must be in `transformer.source_crs()`.
+    ///
+    /// # Returns
+    /// The buffered rectangles, merged rectangles and grid cells in the source CRS.
+    pub fn run_with_transformer(
+        &self,
+        featurecollection: &GeoFeatureCollection,
+        transformer: &Transformer,
+    ) -> Result<BboxResult, CollectBoundingBoxError> {
+        self.run_projected_with(featurecollection, transformer, |projected| {
+            grid_projected_rects(projected, &self.grid_config)
+        })
+    }
+
+    /// Filters, projects, buffers and merges the features with the cached
+    /// transformer and hands the projected rectangles to `finish`.
+    pub(crate) fn run_projected<T>(
+        &mut self,
+        featurecollection: &GeoFeatureCollection,
+        finish: impl FnOnce(ProjectedRects) -> Result<T, CollectBoundingBoxError>,
+    ) -> Result<T, CollectBoundingBoxError> {
+        let from_crs = resolve_source_crs(featurecollection, &self.crs_config);
+        let transformer = match self.transformer.take() {
+            Some(transformer) if transformer.is_for(&from_crs, &self.crs_config.metric_crs) => {
+                transformer
+            }
+            _ => {
+                let transformer = Transformer::new(&from_crs, &self.crs_config.metric_crs)
+                    .map_err(CollectBoundingBoxError::ProjCreateError)?;
+                #[cfg(test)]
+                {
+                    self.transformers_created += 1;
+                }
+                transformer
+            }
+        };
+
+        let result = self.run_projected_with(featurecollection, &transformer, finish);
+        self.transformer = Some(transformer);
+        result
+    }
+
+    /// Like `run_projected`, projecting with `transformer`.
+    pub(crate) fn run_projected_with<T>(
+        &self,
+        featurecollection: &GeoFeatureCollection,
+        transformer: &Transformer,
+        finish: impl FnOnce(ProjectedRects) -> Result<T, CollectBoundingBoxError>,
+    ) -> Result<T, CollectBoundingBoxError> {
+        collect_projected_rects(
+            featurecollection,
+            self.radius,
+            &self.options,
+            transformer,
+            self.merge_strategy,
+            self.parallel,
+        )
+        .and_then(finish)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::bounding_box::collect_bounding_boxes;
+    use crate::utils::geometry::{GeoFeature, GeoGeometry};
+    use geo::Point;
+
+    fn points(coords: &[(f64, f64)]) -> GeoFeatureCollection {
+        GeoFeatureCollection {
+            bbox: None,
+            features: coords
+                .iter()
+                .map(|(x, y)| GeoFeature {
+                    bbox: None,
+                    geometry: Some(GeoGeometry::Point(Point::new(*x, *y))),
+                    id: None,
+                    properties: None,
+                    foreign_members: None,
+                })
+                .collect(),
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn test_pipeline_matches_collect_bounding_boxes() {
+        let fc = points(&[(9.0, 50.0), (9.0001, 50.0), (10.0, 51.0)]);
+        let radius = Radius::new(50.0).unwrap();
+
+        let result = BoundingBoxPipeline::new(radius).run(&fc).unwrap();
+
+        assert_eq!(result.buffered.len(), 3);
+        assert_eq!(result.merged.len(), 2);
+        assert_eq!(
+            result.grid,
+            collect_bounding_boxes(&fc, radius, true).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_pipeline_reuses_transformers() {
+        let radius = Radius::new(10.0).unwrap();
+        let mut pipeline = BoundingBoxPipeline::new(radius)
+            .with_merge_strategy(MergeStrategy::None)
+            .with_grid_config(GridConfig::fixed(1000.0));
+
+        let first = pipeline.run(&points(&[(9.0, 50.0)])).unwrap();
+        let second = pipeline.run(&points(&[(9.0, 50.0), (9.5, 50.5)])).unwrap();
+
+        assert_eq!(pipeline.transformers_created(), 1);
+        assert_eq!(first.buffered, second.buffered[..1]);
+        assert_eq!(second.merged.len(), 2);
+
+        // A failed run keeps later runs working
+        assert!(matches!(
+            pipeline.run(&points(&[])),
+            Err(CollectBoundingBoxError::EmptyInput)
+        ));
+        pipeline.run(&points(&[(9.0, 50.0)])).unwrap();
+        assert_eq!(pipeline.transformers_created(), 1);
+
+        let mut pipeline = pipeline.with_crs_config(CrsConfig::new("EPSG:4326", "EPSG:3857"));
+        pipeline.run(&points(&[(9.0, 50.0)])).unwrap();
+        assert_eq!(pipeline.transformers_created(), 2);
+    }
+}
@@ -1,6 +1,7 @@
 use crate::utils::geometry::{Rectangle, RectangleWithId};
+use geo::{MultiPolygon, Point, unary_union};
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
-use rstar::{RTree, RTreeObject};
+use rstar::{AABB, RTree, RTreeObject};
 use std::collections::HashMap;
 use union_find::{QuickUnionUf, UnionBySize, UnionFind};
 
@@ -12,48 +13,132 @@ use union_find::{QuickUnionUf, UnionBySize, UnionFind};
 * `uf` - The Union-Find structure representing the groups.
 *
 * # Returns
-* A vector of merged rectangles
+* A vector of merged rectangles, in the order of `component_groups`
 */
 pub fn merge_components(
     rectangles: &[Rectangle],
     mut uf: QuickUnionUf<UnionBySize>,
 ) -> Vec<Rectangle> {
-    let capacity = rectangles.len();
-    let mut components: HashMap<usize, Vec<&Rectangle>> = HashMap::with_capacity(capacity);
+    // Now merge rectangles in each component to compute the overall bounding box.
+    let merged_rectangles: Vec<Rectangle> = component_groups(rectangles, &mut uf)
+        .into_iter()
+        .map(|group| bounding_rectangle(rectangles, &group))
+        .collect();
+    merged_rectangles
+}
 
-    for (i, rect) in rectangles.iter().enumerate() {
-        // Iterating over &Rectangle here
-        let root = uf.find(i);
+/// A merged component together with the rectangles it was merged from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergedComponent {
+    /// The bounding box of all member rectangles.
+    pub rectangle: Rectangle,
+    /// The indices of the member rectangles, in ascending order.
+    pub members: Vec<usize>,
+    /// The number of member rectangles.
+    pub count: usize,
+}
 
-        let group_for_root = components.entry(root).or_default();
-        group_for_root.push(rect);
-    }
+/**
+ * Merges rectangles like `merge_components`, keeping which input rectangles
+ * belong to each merged component.
+ *
+ * # Arguments
+ * `rectangles` - The rectangles to merge.
+ * `uf` - The Union-Find structure representing the groups.
+ *
+ * # Returns
+ * One merged component per group, in the order of `component_groups`
+ */
+pub fn merge_components_detailed(
+    rectangles: &[Rectangle],
+    mut uf: QuickUnionUf<UnionBySize>,
+) -> Vec<MergedComponent> {
+    component_groups(rectangles, &mut uf)
+        .into_iter()
+        .map(|members| MergedComponent {
+            rectangle: bounding_rectangle(rectangles, &members),
+            count: members.len(),
+            members,
+        })
+        .collect()
+}
 
-    // Now merge rectangles in each component to compute the overall bounding box.
-    let merged_rectangles: Vec<Rectangle> = components
-        .into_values()
+/// Bounding box of the rectangles at the given indices.
+fn bounding_rectangle(rectangles: &[Rectangle], members: &[usize]) -> Rectangle {
+    let (min_x, min_y, max_x, max_y) = members.iter().map(|&i| &rectangles[i]).fold(
+        // Iterating over &Rectangle here
+        (
+            f64::INFINITY,
+            f64::INFINITY,
+            f64::NEG_INFINITY,
+            f64::NEG_INFINITY,
+        ),
+        |(min_x, min_y, max_x, max_y), r| {
+            (
+                min_x.min(r.min().x),
+                min_y.min(r.min().y),
+                max_x.max(r.max().x),
+                max_y.max(r.max().y),
+            )
+        },
+    );
+    Rectangle::from_corners((min_x, min_y), (max_x, max_y))
+}
+
+/**
+ * Merges rectangles in each component like `merge_components`, but unions the
+ * rectangles themselves instead of taking their bounding box, so concave
+ * clusters keep their shape.
+ *
+ * # Arguments
+ * `rectangles` - The rectangles to merge.
+ * `uf` - The Union-Find structure representing the groups.
+ *
+ * # Returns
+ * The union of each component, in the order of `component_groups`
+ */
+pub fn merge_components_as_multipolygon(
+    rectangles: &[Rectangle],
+    mut uf: QuickUnionUf<UnionBySize>,
+) -> Vec<MultiPolygon> {
+    component_groups(rectangles, &mut uf)
+        .into_iter()
         .map(|group| {
-            let (min_x, min_y, max_x, max_y) = group.iter().fold(
-                // Iterating over &Rectangle here
-                (
-                    f64::INFINITY,
-                    f64::INFINITY,
-                    f64::NEG_INFINITY,
-                    f64::NEG_INFINITY,
-                ),
-                |(min_x, min_y, max_x, max_y), r| {
-                    (
-                        min_x.min(r.min().x),
-                        min_y.min(r.min().y),
-                        max_x.max(r.max().x),
-                        max_y.max(r.max().y),
-                    )
-                },
-            );
-            Rectangle::from_corners((min_x, min_y), (max_x, max_y))
+            let polygons: Vec<_> = group.iter().map(|&i| rectangles[i].to_polygon()).collect();
+            unary_union(&polygons)
         })
-        .collect();
-    merged_rectangles
+        .collect()
+}
+
+/**
+ * Collects the indices of the rectangles in each component.
+ *
+ * Components are ordered by their first member, and the indices within a
+ * component are ascending.
+ *
+ * # Arguments
+ * `rectangles` - The grouped rectangles.
+ * `uf` - The Union-Find structure representing the groups.
+ *
+ * # Returns
+ * The member indices of each component.
+ */
+pub fn component_groups(
+    rectangles: &[Rectangle],
+    uf: &mut QuickUnionUf<UnionBySize>,
+) -> Vec<Vec<usize>> {
+    let mut component_of_root: HashMap<usize, usize> = HashMap::new();
+    let mut components: Vec<Vec<usize>> = Vec::new();
+
+    for i in 0..rectangles.len() {
+        let root = uf.find(i);
+        let component = *component_of_root.entry(root).or_insert_with(|| {
+            components.push(Vec::new());
+            components.len() - 1
+        });
+        components[component].push(i);
+    }
+    components
 }
 
 /**
@@ -66,6 +151,27 @@ pub fn merge_components(
  * A Union-Find structure representing the groups.
  */
 pub fn group_rects_by_overlap(rectangles: &[Rectangle]) -> QuickUnionUf<UnionBySize> {
+    group_rects_by_overlap_with_threshold(rectangles, 0.0)
+}
+
+/**
+ * Groups rectangles like `group_rects_by_overlap`, only joining two rectangles
+ * if their intersection covers at least `min_ratio` of the smaller one.
+ *
+ * A `min_ratio` of zero or less joins any intersecting pair, including pairs
+ * that only touch. Otherwise a degenerate rectangle without area never joins.
+ *
+ * # Arguments
+ * `rectangles` - The rectangles to group.
+ * `min_ratio` - The minimum intersection area relative to the smaller rectangle.
+ *
+ * # Returns
+ * A Union-Find structure representing the groups.
+ */
+pub fn group_rects_by_overlap_with_threshold(
+    rectangles: &[Rectangle],
+    min_ratio: f64,
+) -> QuickUnionUf<UnionBySize> {
     let tree = index_rectangles(rectangles);
     let mut uf = QuickUnionUf::<UnionBySize>::new(rectangles.len());
     let overlapping_pairs: Vec<(usize, usize)> = rectangles
@@ -73,11 +179,13 @@ pub fn group_rects_by_overlap(rectangles: &[Rectangle]) -> QuickUnionUf<UnionByS
         .enumerate()
         .flat_map(|(i, rect)| {
             tree.locate_in_envelope_intersecting(&rect.envelope())
-                .filter_map(
-                    move |RectangleWithId(_, j)| {
-                        if i < *j { Some((i, *j)) } else { None }
-                    },
-                )
+                .filter_map(move |RectangleWithId(other, j)| {
+                    if i < *j && overlaps_enough(rect, other, min_ratio) {
+                        Some((i, *j))
+                    } else {
+                        None
+                    }
+                })
                 .collect::<Vec<_>>()
         })
         .collect();
@@ -87,6 +195,71 @@ pub fn group_rects_by_overlap(rectangles: &[Rectangle]) -> QuickUnionUf<UnionByS
     uf
 }
 
+/**
+ * Groups rectangles like `group_rects_by_overlap`, also joining rectangles
+ * that are at most `max_gap` apart.
+ *
+ * The gap is the shortest edge-to-edge distance between two rectangles, in
+ * their coordinate units, and zero for intersecting rectangles. A `max_gap`
+ * of zero or less groups like `group_rects_by_overlap`.
+ *
+ * # Arguments
+ * `rectangles` - The rectangles to group.
+ * `max_gap` - The largest gap between two rectangles that joins them.
+ *
+ * # Returns
+ * A Union-Find structure representing the groups.
+ */
+pub fn group_rects_by_distance(
+    rectangles: &[Rectangle],
+    max_gap: f64,
+) -> QuickUnionUf<UnionBySize> {
+    let max_gap = max_gap.max(0.0);
+    let tree = index_rectangles(rectangles);
+    let mut uf = QuickUnionUf::<UnionBySize>::new(rectangles.len());
+    let close_pairs: Vec<(usize, usize)> = rectangles
+        .par_iter()
+        .enumerate()
+        .flat_map(|(i, rect)| {
+            let (min, max) = (rect.min(), rect.max());
+            let search_envelope = AABB::from_corners(
+                [min.x - max_gap, min.y - max_gap],
+                [max.x + max_gap, max.y + max_gap],
+            );
+            tree.locate_in_envelope_intersecting(&search_envelope)
+                .filter_map(move |RectangleWithId(other, j)| {
+                    if i < *j && gap_between(rect, other) <= max_gap {
+                        Some((i, *j))
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Vec<_>>()
+        })
+        .collect();
+    for (i, j) in close_pairs {
+        uf.union(i, j);
+    }
+    uf
+}
+
+/// Shortest distance between the edges of two rectangles, zero if they intersect.
+fn gap_between(a: &Rectangle, b: &Rectangle) -> f64 {
+    let dx = (a.min().x - b.max().x).max(b.min().x - a.max().x).max(0.0);
+    let dy = (a.min().y - b.max().y).max(b.min().y - a.max().y).max(0.0);
+    dx.hypot(dy)
+}
+
+/// Whether two intersecting rectangles overlap by at least `min_ratio` of the
+/// smaller one's area.
+fn overlaps_enough(a: &Rectangle, b: &Rectangle, min_ratio: f64) -> bool {
+    if min_ratio <= 0.0 {
+        return true;
+    }
+    let smaller_area = a.area().min(b.area());
+    smaller_area > 0.0 && a.intersection_area(b) / smaller_area >= min_ratio
+}
+
 /**
  * Indexes rectangles using an R-tree.
  *
@@ -96,7 +269,7 @@ pub fn group_rects_by_overlap(rectangles: &[Rectangle]) -> QuickUnionUf<UnionByS
  * # Returns
  * An R-tree containing the indexed rectangles.
  */
-pub fn index_rectangles(rectangles: &[Rectangle]) -> RTree<RectangleWithId> {
+pub fn index_rectangles(rectangles: &[Rectangle]) -> RTree<RectangleWithId<'_>> {
     let rtree_data: Vec<RectangleWithId> = rectangles
         .iter()
         .enumerate()
@@ -105,3 +278,198 @@ pub fn index_rectangles(rectangles: &[Rectangle]) -> RTree<RectangleWithId> {
 
     RTree::bulk_load(rtree_data)
 }
+
+/**
+ * Finds the rectangle nearest to a point.
+ *
+ * # Arguments
+ * `tree` - The R-tree of rectangles to search.
+ * `point` - The point to search from.
+ *
+ * # Returns
+ * The nearest rectangle, a rectangle containing the point if there is one, or
+ * None if the tree is empty.
+ */
+pub fn nearest_rectangle(tree: &RTree<Rectangle>, point: Point<f64>) -> Option<&Rectangle> {
+    tree.nearest_neighbor(&[point.x(), point.y()])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups(rectangles: &[Rectangle], min_ratio: f64) -> usize {
+        let mut uf = group_rects_by_overlap_with_threshold(rectangles, min_ratio);
+        let mut roots: Vec<usize> = (0..rectangles.len()).map(|i| uf.find(i)).collect();
+        roots.sort_unstable();
+        roots.dedup();
+        roots.len()
+    }
+
+    #[test]
+    fn test_group_rects_by_overlap_threshold() {
+        let touching = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((2.0, 0.0), (4.0, 2.0)),
+        ];
+        // Half of the smaller rectangle is covered
+        let overlapping = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((1.0, 0.0), (3.0, 2.0)),
+        ];
+
+        assert_eq!(groups(&touching, 0.0), 1);
+        assert_eq!(groups(&touching, 0.1), 2);
+        assert_eq!(groups(&overlapping, 0.5), 1);
+        assert_eq!(groups(&overlapping, 0.6), 2);
+
+        let mut uf = group_rects_by_overlap(&touching);
+        assert_eq!(uf.find(0), uf.find(1));
+    }
+
+    #[test]
+    fn test_component_groups() {
+        let rectangles = [
+            Rectangle::from_corners((10.0, 10.0), (11.0, 11.0)),
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((1.0, 1.0), (3.0, 3.0)),
+        ];
+
+        let mut uf = group_rects_by_overlap(&rectangles);
+        assert_eq!(component_groups(&rectangles, &mut uf), vec![vec![0], vec![1, 2]]);
+
+        // Merged rectangles line up with the groups they were built from
+        let merged = merge_components(&rectangles, group_rects_by_overlap(&rectangles));
+        assert_eq!(
+            merged,
+            vec![
+                Rectangle::from_corners((10.0, 10.0), (11.0, 11.0)),
+                Rectangle::from_corners((0.0, 0.0), (3.0, 3.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_components_as_multipolygon() {
+        use geo::{Area, Contains, Point};
+
+        // An L-shaped cluster and an isolated rectangle
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 1.0)),
+            Rectangle::from_corners((0.0, 0.0), (1.0, 2.0)),
+            Rectangle::from_corners((10.0, 10.0), (11.0, 11.0)),
+        ];
+
+        let unions =
+            merge_components_as_multipolygon(&rectangles, group_rects_by_overlap(&rectangles));
+
+        assert_eq!(unions.len(), 2);
+        assert_eq!(unions[0].0.len(), 1);
+        // The bounding box would cover 4.0 and the empty corner
+        assert!((unions[0].unsigned_area() - 3.0).abs() < 1e-9);
+        assert!(!unions[0].contains(&Point::new(1.5, 1.5)));
+        assert!(unions[0].contains(&Point::new(0.5, 1.5)));
+        assert!((unions[1].unsigned_area() - 1.0).abs() < 1e-9);
+
+        let merged = merge_components(&rectangles, group_rects_by_overlap(&rectangles));
+        assert_eq!(merged[0], Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)));
+    }
+
+    #[test]
+    fn test_nearest_rectangle() {
+        let tree = RTree::bulk_load(vec![
+            Rectangle::from_corners((0.0, 0.0), (1.0, 1.0)),
+            Rectangle::from_corners((5.0, 0.0), (6.0, 1.0)),
+            Rectangle::from_corners((0.0, 10.0), (10.0, 11.0)),
+        ]);
+
+        assert_eq!(
+            nearest_rectangle(&tree, Point::new(4.0, 2.0)),
+            Some(&Rectangle::from_corners((5.0, 0.0), (6.0, 1.0)))
+        );
+        // Closer to the edge of the wide rectangle than to any corner
+        assert_eq!(
+            nearest_rectangle(&tree, Point::new(3.0, 8.0)),
+            Some(&Rectangle::from_corners((0.0, 10.0), (10.0, 11.0)))
+        );
+        assert_eq!(
+            nearest_rectangle(&tree, Point::new(0.5, 0.5)),
+            Some(&Rectangle::from_corners((0.0, 0.0), (1.0, 1.0)))
+        );
+        assert_eq!(nearest_rectangle(&RTree::new(), Point::new(0.0, 0.0)), None);
+    }
+
+    #[test]
+    fn test_merge_components_detailed() {
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((10.0, 10.0), (12.0, 12.0)),
+            Rectangle::from_corners((1.0, 1.0), (3.0, 3.0)),
+            Rectangle::from_corners((11.0, 11.0), (13.0, 13.0)),
+            Rectangle::from_corners((2.5, 2.5), (4.0, 4.0)),
+        ];
+
+        let uf = group_rects_by_overlap(&rectangles);
+        let components = merge_components_detailed(&rectangles, uf);
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(components[0].members, vec![0, 2, 4]);
+        assert_eq!(components[0].count, 3);
+        assert_eq!(components[0].rectangle, Rectangle::from_corners((0.0, 0.0), (4.0, 4.0)));
+        assert_eq!(components[1].members, vec![1, 3]);
+        assert_eq!(components[1].count, 2);
+        assert_eq!(components[1].rectangle, Rectangle::from_corners((10.0, 10.0), (13.0, 13.0)));
+
+        let merged: Vec<Rectangle> = components.into_iter().map(|c| c.rectangle).collect();
+        assert_eq!(merged, merge_components(&rectangles, group_rects_by_overlap(&rectangles)));
+    }
+
+    #[test]
+    fn test_group_rects_by_distance() {
+        // 50 m apart along x, and a third rect 40 m from the second along each axis
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (10.0, 10.0)),
+            Rectangle::from_corners((60.0, 0.0), (70.0, 10.0)),
+            Rectangle::from_corners((110.0, 50.0), (120.0, 60.0)),
+        ];
+        let component_count = |max_gap| {
+            let mut uf = group_rects_by_distance(&rectangles, max_gap);
+            component_groups(&rectangles, &mut uf).len()
+        };
+
+        assert_eq!(component_count(10.0), 3);
+        assert_eq!(component_count(50.0), 2);
+        assert_eq!(component_count(100.0), 1);
+        // The diagonal gap is about 56.6 m
+        assert_eq!(component_count(56.0), 2);
+        assert_eq!(component_count(57.0), 1);
+
+        let merged = merge_components(&rectangles, group_rects_by_distance(&rectangles, 50.0));
+        assert_eq!(merged[0], Rectangle::from_corners((0.0, 0.0), (70.0, 10.0)));
+    }
+
+    #[test]
+    fn test_group_rects_by_distance_zero_gap_matches_overlap() {
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((2.0, 0.0), (4.0, 2.0)),
+            Rectangle::from_corners((5.0, 0.0), (6.0, 2.0)),
+        ];
+        let mut by_distance = group_rects_by_distance(&rectangles, 0.0);
+        let mut by_overlap = group_rects_by_overlap(&rectangles);
+        assert_eq!(
+            component_groups(&rectangles, &mut by_distance),
+            component_groups(&rectangles, &mut by_overlap)
+        );
+    }
+
+    #[test]
+    fn test_group_rects_by_overlap_threshold_degenerate() {
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((1.0, 1.0), (1.0, 1.0)),
+        ];
+        assert_eq!(groups(&rectangles, 0.0), 1);
+        assert_eq!(groups(&rectangles, 0.5), 2);
+    }
+}
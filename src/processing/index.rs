@@ -0,0 +1,176 @@
+// R-tree spatial index over rectangles carrying a payload.
+
+use geo::Point;
+use geojson::FeatureCollection;
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, RTreeObject};
+
+use crate::utils::geometry::{GeoGeometry, Rectangle};
+
+/// A spatial index of rectangles, each carrying a payload such as the index of
+/// the rectangle or feature it was built from.
+pub struct SpatialIndex<T> {
+    tree: RTree<GeomWithData<Rectangle, T>>,
+}
+
+impl<T> SpatialIndex<T> {
+    /// Builds an index from rectangles and their payloads.
+    pub fn new(entries: impl IntoIterator<Item = (Rectangle, T)>) -> Self {
+        let entries = entries
+            .into_iter()
+            .map(|(rect, data)| GeomWithData::new(rect, data))
+            .collect();
+        Self {
+            tree: RTree::bulk_load(entries),
+        }
+    }
+
+    /// The number of indexed rectangles.
+    pub fn len(&self) -> usize {
+        self.tree.size()
+    }
+
+    /// Whether the index holds no rectangles.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Finds the entries whose rectangle intersects `rect`, touching edges included.
+    pub fn query_rect(&self, rect: &Rectangle) -> impl Iterator<Item = (&Rectangle, &T)> + '_ {
+        self.tree
+            .locate_in_envelope_intersecting(&rect.envelope())
+            .map(|entry| (entry.geom(), &entry.data))
+    }
+
+    /// Finds the entries whose rectangle contains `point`, boundary included.
+    pub fn query_point(&self, point: Point<f64>) -> impl Iterator<Item = (&Rectangle, &T)> + '_ {
+        self.tree
+            .locate_all_at_point(&[point.x(), point.y()])
+            .map(|entry| (entry.geom(), &entry.data))
+    }
+
+    /// Finds the `k` entries whose rectangles are nearest to `point`, nearest
+    /// first. Rectangles containing the point are at distance zero.
+    pub fn nearest(&self, point: Point<f64>, k: usize) -> Vec<(&Rectangle, &T)> {
+        self.tree
+            .nearest_neighbor_iter(&[point.x(), point.y()])
+            .take(k)
+            .map(|entry| (entry.geom(), &entry.data))
+            .collect()
+    }
+
+    /// Whether any indexed rectangle intersects `rect`.
+    pub fn intersects_any(&self, rect: &Rectangle) -> bool {
+        self.query_rect(rect).next().is_some()
+    }
+}
+
+impl SpatialIndex<usize> {
+    /// Indexes rectangles with their position in `rectangles` as payload.
+    pub fn from_rectangles(rectangles: &[Rectangle]) -> Self {
+        Self::new(rectangles.iter().cloned().zip(0..))
+    }
+
+    /// Indexes the bounding rect of every feature with its position in
+    /// `fc.features` as payload. Features without a convertible geometry or
+    /// without coordinates are left out.
+    pub fn from_feature_collection(fc: &FeatureCollection) -> Self {
+        Self::new(fc.features.iter().enumerate().filter_map(|(index, feature)| {
+            let geometry = GeoGeometry::try_from(feature.geometry.as_ref()?).ok()?;
+            Some((Rectangle::from(geometry.bounding_rect()?), index))
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geojson::{Feature, Geometry, Value};
+
+    fn point_feature(x: f64, y: f64) -> Feature {
+        Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(Value::Point(vec![x, y]))),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn test_spatial_index_nearest_ordering() {
+        let rectangles = [
+            Rectangle::from_corners((10.0, 0.0), (11.0, 1.0)),
+            Rectangle::from_corners((0.0, 0.0), (1.0, 1.0)),
+            Rectangle::from_corners((4.0, 0.0), (5.0, 1.0)),
+        ];
+        let index = SpatialIndex::from_rectangles(&rectangles);
+
+        let nearest: Vec<usize> = index
+            .nearest(Point::new(0.5, 0.5), 3)
+            .into_iter()
+            .map(|(_, &i)| i)
+            .collect();
+        assert_eq!(nearest, vec![1, 2, 0]);
+        assert_eq!(index.nearest(Point::new(12.0, 0.5), 1)[0].1, &0);
+        assert_eq!(index.nearest(Point::new(0.5, 0.5), 10).len(), 3);
+        assert_eq!(index.len(), 3);
+    }
+
+    #[test]
+    fn test_spatial_index_queries() {
+        let rectangles = [
+            Rectangle::from_corners((0.0, 0.0), (2.0, 2.0)),
+            Rectangle::from_corners((1.0, 1.0), (3.0, 3.0)),
+            Rectangle::from_corners((5.0, 5.0), (6.0, 6.0)),
+        ];
+        let index = SpatialIndex::from_rectangles(&rectangles);
+
+        let mut hits: Vec<usize> = index
+            .query_rect(&Rectangle::from_corners((1.5, 1.5), (5.0, 5.0)))
+            .map(|(_, &i)| i)
+            .collect();
+        hits.sort();
+        assert_eq!(hits, vec![0, 1, 2]);
+        let mut at_point: Vec<usize> =
+            index.query_point(Point::new(1.5, 1.5)).map(|(_, &i)| i).collect();
+        at_point.sort();
+        assert_eq!(at_point, vec![0, 1]);
+        assert!(!index.intersects_any(&Rectangle::from_corners((2.5, 0.0), (4.0, 0.5))));
+        assert!(index.intersects_any(&Rectangle::from_corners((6.0, 6.0), (7.0, 7.0))));
+    }
+
+    #[test]
+    fn test_spatial_index_from_feature_collection() {
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![
+                point_feature(9.0, 50.0),
+                Feature {
+                    geometry: None,
+                    ..point_feature(0.0, 0.0)
+                },
+                point_feature(10.0, 51.0),
+            ],
+            foreign_members: None,
+        };
+
+        let index = SpatialIndex::from_feature_collection(&fc);
+
+        assert_eq!(index.len(), 2);
+        let cell = Rectangle::from_corners((9.5, 50.5), (10.5, 51.5));
+        let features: Vec<usize> = index.query_rect(&cell).map(|(_, &i)| i).collect();
+        assert_eq!(features, vec![2]);
+    }
+
+    #[test]
+    fn test_spatial_index_empty() {
+        let index = SpatialIndex::from_rectangles(&[]);
+
+        assert!(index.is_empty());
+        assert!(index.nearest(Point::new(0.0, 0.0), 3).is_empty());
+        assert_eq!(index.query_point(Point::new(0.0, 0.0)).count(), 0);
+        assert!(!index.intersects_any(&Rectangle::from_corners((-1e9, -1e9), (1e9, 1e9))));
+        assert!(SpatialIndex::from_feature_collection(&FeatureCollection::default()).is_empty());
+    }
+}
@@ -3,4 +3,10 @@ pub mod remove_near_points;
 pub mod grouping;
 pub mod process_vertices_and_bends;
 pub mod extend_features;
-pub mod pick_features_by_boundingbox;
\ No newline at end of file
+pub mod pick_features_by_boundingbox;
+pub mod validate_coordinate_dims;
+pub mod snap_vertices;
+pub mod bbox_consistency;
+pub mod bounding_box_pipeline;
+pub mod index;
+pub mod spatial_join;
@@ -36,48 +36,41 @@ pub fn process_vertices_and_bends(
     let bend_threshold_radians = bend_threshold_degrees.to_radians();
 
     for feature in &features {
-        if let Some(geometry) = &feature.geometry {
-            match geometry {
-                GeoGeometry::LineString(line_coords) => {
-                    // Filter out "Gebäudekante"
-                    if is_gebaeudekante(feature) {
-                        continue;
-                    }
-
-                    // Convert to geo::LineString coordinates
-                    let coords: Vec<Coord<f64>> = line_coords
-                        .into_iter()
-                        .map(|coord| Coord {
-                            x: coord.x,
-                            y: coord.y,
-                        })
-                        .collect();
-
-                    let num_points = coords.len();
-
-                    if num_points < 2 {
-                        continue;
-                    }
-
-                    // --- Process Multi-Segment LineStrings (length > 2) ---
-                    if num_points > 2 {
-                        let lines = process_multisegment_line(
-                            &coords,
-                            num_points,
-                            bend_threshold_radians,
-                            extension_distance,
-                        );
-                        generated_features.extend(lines);
-                    }
-                    // --- Process Simple 2-Point LineStrings ---
-                    else if num_points == 2 {
-                        let lines = process_simple_line(&coords, extension_distance);
-                        generated_features.extend(lines);
-                    }
-                }
-                _ => {
-                    // Handle other geometry types if necessary
-                }
+        if let Some(GeoGeometry::LineString(line_coords)) = &feature.geometry {
+            // Filter out "Gebäudekante"
+            if is_gebaeudekante(feature) {
+                continue;
+            }
+
+            // Convert to geo::LineString coordinates
+            let coords: Vec<Coord<f64>> = line_coords
+                .into_iter()
+                .map(|coord| Coord {
+                    x: coord.x,
+                    y: coord.y,
+                })
+                .collect();
+
+            let num_points = coords.len();
+
+            if num_points < 2 {
+                continue;
+            }
+
+            // --- Process Multi-Segment LineStrings (length > 2) ---
+            if num_points > 2 {
+                let lines = process_multisegment_line(
+                    &coords,
+                    num_points,
+                    bend_threshold_radians,
+                    extension_distance,
+                );
+                generated_features.extend(lines);
+            }
+            // --- Process Simple 2-Point LineStrings ---
+            else if num_points == 2 {
+                let lines = process_simple_line(&coords, extension_distance);
+                generated_features.extend(lines);
             }
         }
     }
@@ -281,23 +274,20 @@ fn generate_lines_at_point(
     );
 
     // --- Build the generated LineString for this point ---
-    let mut generated_segments_coords: Vec<Coord<f64>> = Vec::with_capacity(8);
-
-    // 1. Segment: current_point -> extended_forward
-    generated_segments_coords.push(current_point.into());
-    generated_segments_coords.push(extended_forward.into());
-
-    // 2. Segment: current_point -> extended_backward
-    generated_segments_coords.push(current_point.into());
-    generated_segments_coords.push(extended_backward.into());
-
-    // 3. Segment: current_point -> extended_orthogonal_90
-    generated_segments_coords.push(current_point.into());
-    generated_segments_coords.push(extended_orthogonal_90.into());
-
-    // 4. Segment: current_point -> extended_orthogonal_minus_90
-    generated_segments_coords.push(current_point.into());
-    generated_segments_coords.push(extended_orthogonal_minus_90.into());
+    let generated_segments_coords: Vec<Coord<f64>> = vec![
+        // 1. Segment: current_point -> extended_forward
+        current_point.into(),
+        extended_forward.into(),
+        // 2. Segment: current_point -> extended_backward
+        current_point.into(),
+        extended_backward.into(),
+        // 3. Segment: current_point -> extended_orthogonal_90
+        current_point.into(),
+        extended_orthogonal_90.into(),
+        // 4. Segment: current_point -> extended_orthogonal_minus_90
+        current_point.into(),
+        extended_orthogonal_minus_90.into(),
+    ];
 
     let generated_line_string = LineString::new(generated_segments_coords);
 
@@ -0,0 +1,209 @@
+use std::collections::HashMap;
+
+use geo::{Coord, CoordsIter, MapCoords};
+
+use crate::utils::geometry::{GeoFeature, GeoFeatureCollection, GeoGeometry};
+
+/// Key of the grid bucket a coordinate falls into.
+type Cell = (i64, i64);
+
+/// Bit pattern of a coordinate, used to look up its snapped location.
+type CoordKey = (u64, u64);
+
+fn coord_key(coord: Coord) -> CoordKey {
+    (coord.x.to_bits(), coord.y.to_bits())
+}
+
+fn cell_of(coord: Coord, cell_size: f64) -> Cell {
+    (
+        (coord.x / cell_size).floor() as i64,
+        (coord.y / cell_size).floor() as i64,
+    )
+}
+
+/// Snaps vertices that lie within `tolerance` of each other, across all
+/// features, to a common location.
+///
+/// Vertices are visited in feature order. The first vertex of a cluster becomes
+/// its anchor, and every later vertex within `tolerance` of an anchor is moved
+/// onto the nearest one. Anchors are found through a grid of `tolerance`-sized
+/// buckets, so only the 3x3 neighbouring buckets are searched per vertex.
+/// Vertices of one geometry may collapse onto each other if they are closer
+/// than `tolerance`.
+///
+/// # Arguments
+/// * `fc` - The feature collection to snap.
+/// * `tolerance` - The snapping distance, in coordinate units.
+///
+/// # Returns
+/// The feature collection with snapped geometries, or an unchanged copy if
+/// `tolerance` is not a positive finite number.
+pub fn snap_vertices(fc: &GeoFeatureCollection, tolerance: f64) -> GeoFeatureCollection {
+    if !(tolerance.is_finite() && tolerance > 0.0) {
+        return fc.clone();
+    }
+
+    let mut anchors: Vec<Coord> = Vec::new();
+    let mut buckets: HashMap<Cell, Vec<usize>> = HashMap::new();
+    let mut snapped: HashMap<CoordKey, Coord> = HashMap::new();
+    let tolerance_squared = tolerance * tolerance;
+
+    let coords = fc
+        .features
+        .iter()
+        .filter_map(|feature| feature.geometry.as_ref())
+        .flat_map(geometry_coords);
+    for coord in coords {
+        if snapped.contains_key(&coord_key(coord)) {
+            continue;
+        }
+        let (cx, cy) = cell_of(coord, tolerance);
+        let nearest_anchor = (cx - 1..=cx + 1)
+            .flat_map(|x| (cy - 1..=cy + 1).map(move |y| (x, y)))
+            .filter_map(|cell| buckets.get(&cell))
+            .flatten()
+            .map(|&index| anchors[index])
+            .map(|anchor| {
+                let (dx, dy) = (anchor.x - coord.x, anchor.y - coord.y);
+                (anchor, dx * dx + dy * dy)
+            })
+            .filter(|&(_, distance_squared)| distance_squared <= tolerance_squared)
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(anchor, _)| anchor);
+
+        let target = match nearest_anchor {
+            Some(anchor) => anchor,
+            None => {
+                buckets.entry((cx, cy)).or_default().push(anchors.len());
+                anchors.push(coord);
+                coord
+            }
+        };
+        snapped.insert(coord_key(coord), target);
+    }
+
+    let snap = |coord: Coord| snapped.get(&coord_key(coord)).copied().unwrap_or(coord);
+    GeoFeatureCollection {
+        bbox: fc.bbox.clone(),
+        foreign_members: fc.foreign_members.clone(),
+        features: fc
+            .features
+            .iter()
+            .map(|feature| GeoFeature {
+                geometry: feature
+                    .geometry
+                    .as_ref()
+                    .map(|geometry| map_geometry_coords(geometry, snap)),
+                ..feature.clone()
+            })
+            .collect(),
+    }
+}
+
+/// Collects all vertices of a geometry.
+fn geometry_coords(geometry: &GeoGeometry) -> Vec<Coord> {
+    match geometry {
+        GeoGeometry::Point(point) => point.coords_iter().collect(),
+        GeoGeometry::LineString(line) => line.coords_iter().collect(),
+        GeoGeometry::Polygon(polygon) => polygon.coords_iter().collect(),
+        GeoGeometry::MultiPoint(multi_point) => multi_point.coords_iter().collect(),
+        GeoGeometry::MultiLineString(multi_line) => multi_line.coords_iter().collect(),
+        GeoGeometry::MultiPolygon(multi_polygon) => multi_polygon.coords_iter().collect(),
+    }
+}
+
+/// Applies `func` to all vertices of a geometry.
+fn map_geometry_coords(
+    geometry: &GeoGeometry,
+    func: impl Fn(Coord) -> Coord + Copy,
+) -> GeoGeometry {
+    match geometry {
+        GeoGeometry::Point(point) => GeoGeometry::Point(point.map_coords(func)),
+        GeoGeometry::LineString(line) => GeoGeometry::LineString(line.map_coords(func)),
+        GeoGeometry::Polygon(polygon) => GeoGeometry::Polygon(polygon.map_coords(func)),
+        GeoGeometry::MultiPoint(multi_point) => {
+            GeoGeometry::MultiPoint(multi_point.map_coords(func))
+        }
+        GeoGeometry::MultiLineString(multi_line) => {
+            GeoGeometry::MultiLineString(multi_line.map_coords(func))
+        }
+        GeoGeometry::MultiPolygon(multi_polygon) => {
+            GeoGeometry::MultiPolygon(multi_polygon.map_coords(func))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geo::{LineString, Point, Polygon, coord};
+
+    fn feature(geometry: GeoGeometry) -> GeoFeature {
+        GeoFeature {
+            id: None,
+            properties: None,
+            bbox: None,
+            foreign_members: None,
+            geometry: Some(geometry),
+        }
+    }
+
+    fn square(min_x: f64, min_y: f64, size: f64) -> Polygon {
+        Polygon::new(
+            LineString::from(vec![
+                coord! { x: min_x, y: min_y },
+                coord! { x: min_x + size, y: min_y },
+                coord! { x: min_x + size, y: min_y + size },
+                coord! { x: min_x, y: min_y + size },
+                coord! { x: min_x, y: min_y },
+            ]),
+            vec![],
+        )
+    }
+
+    #[test]
+    fn test_snap_vertices_shares_edge_between_buildings() {
+        // The right building's left edge is off by a micro-degree
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature(GeoGeometry::Polygon(square(0.0, 0.0, 1.0))),
+                feature(GeoGeometry::Polygon(square(1.000_000_4, 0.0, 1.0))),
+            ],
+            ..Default::default()
+        };
+
+        let snapped = snap_vertices(&fc, 1e-6);
+
+        match &snapped.features[1].geometry {
+            Some(GeoGeometry::Polygon(polygon)) => {
+                assert_eq!(polygon.exterior().0[0], coord! { x: 1.0, y: 0.0 });
+                assert_eq!(polygon.exterior().0[3], coord! { x: 1.0, y: 1.0 });
+                assert_eq!(polygon.exterior().0[1], coord! { x: 1.000_000_4 + 1.0, y: 0.0 });
+            }
+            other => panic!("unexpected geometry {:?}", other),
+        }
+        // The first feature's vertices are the anchors and stay in place
+        assert_eq!(snapped.features[0], fc.features[0]);
+    }
+
+    #[test]
+    fn test_snap_vertices_leaves_distant_vertices() {
+        let fc = GeoFeatureCollection {
+            features: vec![
+                feature(GeoGeometry::Point(Point::new(0.0, 0.0))),
+                feature(GeoGeometry::Point(Point::new(0.5, 0.0))),
+                feature(GeoGeometry::Point(Point::new(0.05, 0.05))),
+            ],
+            ..Default::default()
+        };
+
+        let snapped = snap_vertices(&fc, 0.1);
+
+        assert_eq!(snapped.features[1], fc.features[1]);
+        assert_eq!(
+            snapped.features[2].geometry,
+            Some(GeoGeometry::Point(Point::new(0.0, 0.0)))
+        );
+        assert_eq!(snap_vertices(&fc, 0.0), fc);
+    }
+}
@@ -0,0 +1,154 @@
+// Joins point entities to the buildings they lie in.
+
+use geo::{BoundingRect, Geometry, Intersects, MultiPolygon, Point, Polygon};
+
+use crate::domain::DomainEntity;
+use crate::processing::index::SpatialIndex;
+use crate::utils::geometry::Rectangle;
+
+/// Finds the building each point entity lies in.
+///
+/// Buildings are indexed by their bounding rects, and each candidate is then
+/// checked against the exact footprint. Polygon, MultiPolygon and closed
+/// LineString footprints are supported, holes excluded. A point on the
+/// boundary of a footprint counts as inside; a point on an edge shared by
+/// several buildings is assigned to the first of them.
+///
+/// # Arguments
+/// * `entities` - The entities to join, buildings and point entities mixed.
+///
+/// # Returns
+/// For every point entity, in input order, its index in `entities` and the
+/// index of its building in `entities`, or None if it is not in any building.
+pub fn assign_points_to_buildings(entities: &[DomainEntity]) -> Vec<(usize, Option<usize>)> {
+    let footprints: Vec<(usize, Vec<Polygon>)> = entities
+        .iter()
+        .enumerate()
+        .filter_map(|(index, entity)| match entity {
+            DomainEntity::Building(building) => Some((index, footprint(&building.geometry))),
+            _ => None,
+        })
+        .collect();
+    let index = SpatialIndex::new(footprints.iter().enumerate().filter_map(
+        |(position, (_, polygons))| {
+            let rect = MultiPolygon::new(polygons.clone()).bounding_rect()?;
+            Some((Rectangle::from(rect), position))
+        },
+    ));
+
+    entities
+        .iter()
+        .enumerate()
+        .filter_map(|(entity_index, entity)| {
+            let point = entity_point(entity)?;
+            let building = index
+                .query_point(point)
+                .map(|(_, &position)| &footprints[position])
+                .filter(|(_, polygons)| polygons.iter().any(|polygon| polygon.intersects(&point)))
+                .map(|(building_index, _)| *building_index)
+                .min();
+            Some((entity_index, building))
+        })
+        .collect()
+}
+
+/// The polygons making up a building footprint.
+fn footprint(geometry: &Geometry) -> Vec<Polygon> {
+    match geometry {
+        Geometry::Polygon(polygon) => vec![polygon.clone()],
+        Geometry::MultiPolygon(multi_polygon) => multi_polygon.0.clone(),
+        // Buildings keep closed rings as LineStrings
+        Geometry::LineString(ring) => vec![Polygon::new(ring.clone(), vec![])],
+        _ => vec![],
+    }
+}
+
+/// The location of an entity with a Point geometry.
+fn entity_point(entity: &DomainEntity) -> Option<Point> {
+    match entity {
+        DomainEntity::CapturedMarker(marker) => Some(marker.geometry),
+        DomainEntity::SupplyPoint(supply_point) => Some(supply_point.geometry),
+        DomainEntity::OperationSite(operation_site) => Some(operation_site.geometry),
+        DomainEntity::DrillingPoint(drilling_point) => Some(drilling_point.geometry),
+        DomainEntity::CableTunnel(cable_tunnel) => Some(cable_tunnel.geometry),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::identify_domain_entity;
+    use geojson::Feature;
+    use serde_json::json;
+
+    fn entity(object_id: &str, geometry: serde_json::Value) -> DomainEntity {
+        let feature: Feature = serde_json::from_value(json!({
+            "type": "Feature",
+            "properties": { "properties": { "objectId": object_id } },
+            "geometry": geometry,
+        }))
+        .unwrap();
+        identify_domain_entity(feature)
+    }
+
+    fn marker(x: f64, y: f64) -> DomainEntity {
+        entity("Kugelmarker", json!({ "type": "Point", "coordinates": [x, y] }))
+    }
+
+    fn square(min_x: f64, min_y: f64, size: f64) -> serde_json::Value {
+        json!([[
+            [min_x, min_y],
+            [min_x + size, min_y],
+            [min_x + size, min_y + size],
+            [min_x, min_y + size],
+            [min_x, min_y]
+        ]])
+    }
+
+    #[test]
+    fn test_assign_points_to_buildings() {
+        let entities = vec![
+            entity("Gebaeude", json!({ "type": "Polygon", "coordinates": square(0.0, 0.0, 2.0) })),
+            marker(1.0, 1.0),
+            entity("Gebaeude", json!({ "type": "Polygon", "coordinates": square(2.0, 0.0, 2.0) })),
+            marker(10.0, 10.0),
+            // On the edge shared by both buildings
+            marker(2.0, 1.0),
+            entity(
+                "Gebaeude",
+                json!({
+                    "type": "MultiPolygon",
+                    "coordinates": [square(20.0, 20.0, 1.0), square(30.0, 30.0, 1.0)]
+                }),
+            ),
+            marker(30.5, 30.5),
+        ];
+        assert!(entities[0].is_building() && entities[1].is_marker());
+
+        let assignments = assign_points_to_buildings(&entities);
+
+        assert_eq!(
+            assignments,
+            vec![(1, Some(0)), (3, None), (4, Some(0)), (6, Some(5))]
+        );
+    }
+
+    #[test]
+    fn test_assign_points_to_buildings_excludes_holes() {
+        let mut rings = square(0.0, 0.0, 10.0).as_array().unwrap().clone();
+        rings.push(square(4.0, 4.0, 2.0)[0].clone());
+        let entities = vec![
+            entity("Gebaeude", json!({ "type": "Polygon", "coordinates": rings })),
+            marker(5.0, 5.0),
+            marker(4.0, 5.0),
+            marker(1.0, 1.0),
+        ];
+
+        assert_eq!(
+            assign_points_to_buildings(&entities),
+            vec![(1, None), (2, Some(0)), (3, Some(0))]
+        );
+        assert_eq!(assign_points_to_buildings(&[marker(1.0, 1.0)]), vec![(0, None)]);
+    }
+}
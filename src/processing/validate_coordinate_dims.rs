@@ -0,0 +1,112 @@
+use geojson::{FeatureCollection, Geometry, Value, feature::Id};
+
+/// Report features whose geometry contains coordinate arrays with fewer than two elements.
+///
+/// Returns one entry per offending feature: its id (if any) and the number of short
+/// positions found in its geometry, including those nested in GeometryCollections.
+pub fn validate_coordinate_dims(fc: &FeatureCollection) -> Vec<(Option<Id>, usize)> {
+    fc.features
+        .iter()
+        .filter_map(|feature| {
+            let short_positions = feature.geometry.as_ref().map_or(0, count_short_positions);
+            (short_positions > 0).then(|| (feature.id.clone(), short_positions))
+        })
+        .collect()
+}
+
+fn count_short_positions(geometry: &Geometry) -> usize {
+    let is_short = |position: &Vec<f64>| position.len() < 2;
+    match &geometry.value {
+        Value::Point(position) => usize::from(is_short(position)),
+        Value::MultiPoint(positions) | Value::LineString(positions) => {
+            positions.iter().filter(|p| is_short(p)).count()
+        }
+        Value::MultiLineString(lines) | Value::Polygon(lines) => {
+            lines.iter().flatten().filter(|p| is_short(p)).count()
+        }
+        Value::MultiPolygon(polygons) => polygons
+            .iter()
+            .flatten()
+            .flatten()
+            .filter(|p| is_short(p))
+            .count(),
+        Value::GeometryCollection(geometries) => geometries.iter().map(count_short_positions).sum(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::error::Error;
+    use crate::utils::geometry::{GeoFeature, GeoGeometry};
+    use geojson::Feature;
+
+    fn feature(id: Option<&str>, value: Value) -> Feature {
+        Feature {
+            bbox: None,
+            geometry: Some(Geometry::new(value)),
+            id: id.map(|id| Id::String(id.to_string())),
+            properties: None,
+            foreign_members: None,
+        }
+    }
+
+    #[test]
+    fn test_validate_coordinate_dims_reports_short_positions() {
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![
+                feature(
+                    Some("ok"),
+                    Value::LineString(vec![vec![0.0, 0.0], vec![1.0, 1.0]]),
+                ),
+                feature(
+                    Some("short"),
+                    Value::LineString(vec![vec![0.0], vec![1.0, 1.0], vec![]]),
+                ),
+                feature(None, Value::Point(vec![5.0])),
+                feature(
+                    Some("nested"),
+                    Value::GeometryCollection(vec![
+                        Geometry::new(Value::Point(vec![1.0, 2.0])),
+                        Geometry::new(Value::Polygon(vec![vec![vec![0.0, 0.0], vec![1.0]]])),
+                    ]),
+                ),
+            ],
+            foreign_members: None,
+        };
+
+        let report = validate_coordinate_dims(&fc);
+
+        assert_eq!(
+            report,
+            vec![
+                (Some(Id::String("short".to_string())), 2),
+                (None, 1),
+                (Some(Id::String("nested".to_string())), 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_validate_coordinate_dims_empty_for_valid_collection() {
+        let fc = FeatureCollection {
+            bbox: None,
+            features: vec![feature(None, Value::Point(vec![1.0, 2.0, 3.0]))],
+            foreign_members: None,
+        };
+        assert!(validate_coordinate_dims(&fc).is_empty());
+    }
+
+    #[test]
+    fn test_short_positions_convert_to_typed_error() {
+        let geometry = Geometry::new(Value::LineString(vec![vec![0.0, 0.0], vec![1.0]]));
+        assert!(matches!(
+            GeoGeometry::try_from(&geometry),
+            Err(Error::InvalidCoordinates)
+        ));
+
+        let geo_feature = GeoFeature::from(feature(None, Value::Point(vec![1.0])));
+        assert!(geo_feature.geometry.is_none());
+    }
+}
@@ -14,17 +14,11 @@ fn process_raw_feature(mut feature: geojson::Feature) -> geojson::Feature {
     let properties = Map::with_capacity(1);
 
     // Get and convert geometry to geo::Geometry
-    let geo_geometry: Option<geo::Geometry> = feature.geometry.clone().and_then(|geom| {
-        match geo::Geometry::try_from(geom) {
-            Ok(geo_geom) => Some(geo_geom),
-            Err(_e) => {
-                // Logging removed in this simplified version
-                // let _geojson_type = feature.geometry.as_ref().map(|g| g.value.type_name()).unwrap_or("None");
-                // eprintln!("Raw processing: Failed to convert geojson::{} to geo::Geometry for feature {:?}: {}", _geojson_type, feature_id, _e);
-                None // Conversion failed
-            }
-        }
-    });
+    // Logging removed in this simplified version, a failed conversion just yields None
+    let geo_geometry: Option<geo::Geometry> = feature
+        .geometry
+        .clone()
+        .and_then(|geom| geo::Geometry::try_from(geom).ok());
 
     // --- Processing Logic based on Geometry Type and Properties ---
     match geo_geometry {
@@ -21,6 +21,27 @@ pub enum Error {
     InvalidFeatureGeometry,
     #[error("Invalid objectId: {0}")]
     InvalidObjectId(String),
+    #[error("Duplicate feature id: {0}")]
+    DuplicateFeatureId(String),
+    #[error("Too many results: {0}")]
+    TooManyResults(usize),
+    #[error("Projection failed: {0}")]
+    Projection(String),
+    #[error("Empty input")]
+    EmptyInput,
+    #[error("Invalid radius")]
+    InvalidRadius,
+    #[error("Invalid grid: {0}")]
+    InvalidGrid(String),
     #[error("Error converting geometry: {0}")]
-    GeometryConversionError(#[from] Box<dyn StdError>),
-}
\ No newline at end of file
+    GeometryConversion(#[from] Box<dyn StdError>),
+    #[error("Error serializing GeoJSON: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Invalid GeoJSON feature on line {line}: {source}")]
+    InvalidFeatureLine {
+        line: usize,
+        source: serde_json::Error,
+    },
+    #[error("I/O error: {0}")]
+    Io(#[from] std::io::Error),
+}
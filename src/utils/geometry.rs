@@ -1,12 +1,16 @@
-use geo::{BoundingRect, Rect};
+use geo::{BoundingRect, Centroid, Contains, Coord, Rect};
 use geo::{LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon};
 use geojson::{Bbox, feature::Id};
 use geojson::{Feature, FeatureCollection, Geometry, Value};
-use rstar::{AABB, RTreeObject};
+use rstar::{AABB, PointDistance, RTreeObject};
 use serde_json::Map;
 use serde_json::Value as JsonValue;
+use std::fmt;
 use std::ops::Deref;
 
+use crate::utils::error::Error;
+use crate::utils::helpers::BoundingBoxOps;
+
 /// Struct to hold a rectangle
 #[derive(Debug, Clone, PartialEq)]
 pub struct Rectangle(Rect<f64>);
@@ -27,6 +31,120 @@ impl Rectangle {
     pub fn to_geo_rect(&self) -> Rect<f64> {
         self.0
     }
+
+    /// Area of the rectangle, zero if it is degenerate.
+    pub fn area(&self) -> f64 {
+        self.0.width() * self.0.height()
+    }
+
+    /// Area shared with `other`, 0.0 if they are disjoint or only touch.
+    pub fn intersection_area(&self, other: &Rectangle) -> f64 {
+        let width = self.max().x.min(other.max().x) - self.min().x.max(other.min().x);
+        let height = self.max().y.min(other.max().y) - self.min().y.max(other.min().y);
+        width.max(0.0) * height.max(0.0)
+    }
+
+    /// Width of the rectangle.
+    pub fn width(&self) -> f64 {
+        self.0.width()
+    }
+
+    /// Height of the rectangle.
+    pub fn height(&self) -> f64 {
+        self.0.height()
+    }
+
+    /// Center of the rectangle.
+    pub fn center(&self) -> Coord<f64> {
+        self.0.center()
+    }
+
+    /// Rectangle shared with `other`, or None if they are disjoint. Rectangles
+    /// that only touch share a degenerate rectangle along the touching edge
+    /// or corner.
+    pub fn intersection(&self, other: &Rectangle) -> Option<Rectangle> {
+        let min_x = self.min().x.max(other.min().x);
+        let min_y = self.min().y.max(other.min().y);
+        let max_x = self.max().x.min(other.max().x);
+        let max_y = self.max().y.min(other.max().y);
+        (min_x <= max_x && min_y <= max_y)
+            .then(|| Rectangle::from_corners((min_x, min_y), (max_x, max_y)))
+    }
+
+    /// Smallest rectangle covering both rectangles.
+    pub fn union(&self, other: &Rectangle) -> Rectangle {
+        Rectangle::from_corners(
+            (self.min().x.min(other.min().x), self.min().y.min(other.min().y)),
+            (self.max().x.max(other.max().x), self.max().y.max(other.max().y)),
+        )
+    }
+
+    /// Whether the rectangle contains `other`, as defined by `geo::Contains`.
+    /// A rectangle contains itself and rectangles sharing its edges.
+    pub fn contains<T>(&self, other: &T) -> bool
+    where
+        Rect<f64>: Contains<T>,
+    {
+        self.0.contains(other)
+    }
+
+    /// Grows the rectangle by `distance` on every side. A negative distance
+    /// shrinks it, down to a degenerate rectangle at its center.
+    pub fn expand(&self, distance: f64) -> Rectangle {
+        Self(self.0.expand_xy(distance, distance))
+    }
+}
+
+/// Allows `Rectangle::contains` and `geo::Rect::contains` on Rectangles.
+impl Contains<Rectangle> for Rect<f64> {
+    fn contains(&self, rhs: &Rectangle) -> bool {
+        self.contains(&rhs.0)
+    }
+}
+
+/// Formats the rectangle as a GeoJSON bbox, `[min_x, min_y, max_x, max_y]`.
+impl fmt::Display for Rectangle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "[{}, {}, {}, {}]",
+            self.min().x,
+            self.min().y,
+            self.max().x,
+            self.max().y
+        )
+    }
+}
+
+/// Conversion from the bounding rect of a geo::Polygon to Rectangle.
+impl TryFrom<geo::Polygon<f64>> for Rectangle {
+    type Error = Error;
+
+    fn try_from(polygon: geo::Polygon<f64>) -> Result<Self, Self::Error> {
+        polygon
+            .bounding_rect()
+            .map(Rectangle)
+            .ok_or(Error::InvalidCoordinates)
+    }
+}
+
+/// Conversion from Rectangle to a GeoJSON Polygon feature with its bbox.
+impl From<&Rectangle> for Feature {
+    fn from(rect: &Rectangle) -> Self {
+        Feature {
+            bbox: Some(vec![rect.min().x, rect.min().y, rect.max().x, rect.max().y]),
+            geometry: Some(Geometry::from(&rect.0.to_polygon())),
+            id: None,
+            properties: None,
+            foreign_members: None,
+        }
+    }
+}
+
+impl From<Rectangle> for Feature {
+    fn from(rect: Rectangle) -> Self {
+        Feature::from(&rect)
+    }
 }
 
 /// Conversion from geo::Rect<f64> to Rectangle.
@@ -63,6 +181,13 @@ impl RTreeObject for Rectangle {
     }
 }
 
+/// Allow nearest neighbor queries on Rectangles, points inside being at distance zero
+impl PointDistance for Rectangle {
+    fn distance_2(&self, point: &[f64; 2]) -> f64 {
+        self.envelope().distance_2(point)
+    }
+}
+
 /// Struct to hold a rectangle with an associated index
 pub struct RectangleWithId<'a>(pub &'a Rectangle, pub usize);
 
@@ -101,6 +226,18 @@ impl GeoGeometry {
             // GeoGeometry::GeometryCollection(geometry_collection) => geometry_collection.bounding_rect(),
         }
     }
+
+    /// Calculates the centroid of the geometry, if it has one.
+    pub fn centroid(&self) -> Option<Point<f64>> {
+        match self {
+            GeoGeometry::Point(point) => Some(*point),
+            GeoGeometry::LineString(line_string) => line_string.centroid(),
+            GeoGeometry::Polygon(polygon) => polygon.centroid(),
+            GeoGeometry::MultiPoint(multi_point) => multi_point.centroid(),
+            GeoGeometry::MultiLineString(multi_line_string) => multi_line_string.centroid(),
+            GeoGeometry::MultiPolygon(multi_polygon) => multi_polygon.centroid(),
+        }
+    }
 }
 
 /// Struct to hold a feature's data with geo geometry
@@ -121,109 +258,104 @@ pub struct GeoFeatureCollection {
     pub features: Vec<GeoFeature>,
 }
 
-/// Convert a geojson Geometry to a GeoGeometry
-impl From<Geometry> for GeoGeometry {
-    fn from(value: Geometry) -> Self {
-        match &value.value {
-            Value::Point(coords) => {
-                let point = Point::new(coords[0], coords[1]);
-                GeoGeometry::Point(point)
+/// Convert a GeoGeometry to a geo Geometry
+impl From<&GeoGeometry> for geo::Geometry<f64> {
+    fn from(value: &GeoGeometry) -> Self {
+        match value {
+            GeoGeometry::Point(point) => geo::Geometry::Point(*point),
+            GeoGeometry::LineString(line_string) => geo::Geometry::LineString(line_string.clone()),
+            GeoGeometry::Polygon(polygon) => geo::Geometry::Polygon(polygon.clone()),
+            GeoGeometry::MultiPoint(multi_point) => geo::Geometry::MultiPoint(multi_point.clone()),
+            GeoGeometry::MultiLineString(multi_line_string) => {
+                geo::Geometry::MultiLineString(multi_line_string.clone())
             }
-            Value::LineString(coords) => {
-                let line_string = LineString::from(
-                    coords
-                        .iter()
-                        .map(|p| Point::new(p[0], p[1]))
-                        .collect::<Vec<Point<f64>>>(),
-                );
-                GeoGeometry::LineString(line_string)
+            GeoGeometry::MultiPolygon(multi_polygon) => {
+                geo::Geometry::MultiPolygon(multi_polygon.clone())
             }
-            Value::Polygon(coords) => {
-                let exterior = coords.first();
-                let exterior_ring = if let Some(exterior) = exterior {
-                    LineString::from(
-                        exterior
-                            .iter()
-                            .map(|p| Point::new(p[0], p[1]))
-                            .collect::<Vec<Point<f64>>>(),
-                    )
-                } else {
-                    LineString::new(vec![])
-                };
-                let interior_rings = if coords.len() > 1 {
-                    let interiors = coords.iter().skip(1).collect::<Vec<_>>();
-                    interiors
-                        .iter()
-                        .map(|ls| {
-                            ls.iter()
-                            .map(|p| Point::new(p[0], p[1]))
-                            .collect::<LineString>()
-                        })
-                        .collect::<Vec<_>>()
-                } else {
-                    vec![]
-                };
-                let polygon = Polygon::new(exterior_ring, interior_rings);
-                GeoGeometry::Polygon(polygon)
+        }
+    }
+}
+
+/// Convert a geojson position to a Point, checking it has at least two ordinates
+fn position_to_point(position: &[f64]) -> Result<Point<f64>, Error> {
+    match position {
+        [x, y, ..] => Ok(Point::new(*x, *y)),
+        _ => Err(Error::InvalidCoordinates),
+    }
+}
+
+/// Convert a list of geojson positions to a LineString
+fn positions_to_line_string(positions: &[Vec<f64>]) -> Result<LineString<f64>, Error> {
+    positions
+        .iter()
+        .map(|p| position_to_point(p))
+        .collect::<Result<Vec<Point<f64>>, Error>>()
+        .map(LineString::from)
+}
+
+/// Convert a list of geojson rings to a Polygon
+fn rings_to_polygon(rings: &[Vec<Vec<f64>>]) -> Result<Polygon<f64>, Error> {
+    let exterior_ring = match rings.first() {
+        Some(exterior) => positions_to_line_string(exterior)?,
+        None => LineString::new(vec![]),
+    };
+    let interior_rings = rings
+        .iter()
+        .skip(1)
+        .map(|ring| positions_to_line_string(ring))
+        .collect::<Result<Vec<LineString<f64>>, Error>>()?;
+    Ok(Polygon::new(exterior_ring, interior_rings))
+}
+
+/// Convert a geojson Geometry to a GeoGeometry, failing with
+/// `Error::InvalidCoordinates` on positions with fewer than two ordinates and
+/// `Error::UnsupportedGeometryType` on GeometryCollections.
+impl TryFrom<&Geometry> for GeoGeometry {
+    type Error = Error;
+
+    fn try_from(value: &Geometry) -> Result<Self, Self::Error> {
+        match &value.value {
+            Value::Point(coords) => Ok(GeoGeometry::Point(position_to_point(coords)?)),
+            Value::LineString(coords) => {
+                Ok(GeoGeometry::LineString(positions_to_line_string(coords)?))
             }
+            Value::Polygon(coords) => Ok(GeoGeometry::Polygon(rings_to_polygon(coords)?)),
             Value::MultiPoint(coords) => {
-                let multi_point = MultiPoint::from(
-                    coords
-                        .iter()
-                        .map(|p| Point::new(p[0], p[1]))
-                        .collect::<Vec<Point<f64>>>(),
-                );
-                GeoGeometry::MultiPoint(multi_point)
+                let points = coords
+                    .iter()
+                    .map(|p| position_to_point(p))
+                    .collect::<Result<Vec<Point<f64>>, Error>>()?;
+                Ok(GeoGeometry::MultiPoint(MultiPoint::from(points)))
             }
             Value::MultiLineString(coords) => {
-                let multi_line_string = MultiLineString(
-                    coords
-                        .iter()
-                        .map(|ls| {
-                            LineString::from(
-                                ls.iter()
-                                    .map(|p| Point::new(p[0], p[1]))
-                                    .collect::<Vec<Point<f64>>>(),
-                            )
-                        })
-                        .collect::<Vec<LineString<f64>>>(),
-                );
-                GeoGeometry::MultiLineString(multi_line_string)
+                let line_strings = coords
+                    .iter()
+                    .map(|ls| positions_to_line_string(ls))
+                    .collect::<Result<Vec<LineString<f64>>, Error>>()?;
+                Ok(GeoGeometry::MultiLineString(MultiLineString(line_strings)))
             }
             Value::MultiPolygon(coords) => {
-                let mut polygons = vec![];
-                for coord in coords {
-                    let exterior = coord.first();
-                    let exterior_ring = if let Some(exterior) = exterior {
-                        LineString::from(
-                            exterior
-                                .iter()
-                                .map(|p| Point::new(p[0], p[1]))
-                                .collect::<Vec<Point<f64>>>(),
-                        )
-                    } else {
-                        LineString::new(vec![])
-                    };
-                    let interior_rings = if coord.len() > 1 {
-                        let interiors = coord.iter().skip(1).collect::<Vec<_>>();
-                        interiors
-                            .iter()
-                            .map(|ls| {
-                                ls.iter()
-                                    .map(|p| Point::new(p[0], p[1]))
-                                    .collect::<LineString>()
-                            })
-                            .collect::<Vec<_>>()
-                    } else {
-                        vec![]
-                    };
-                    let polygon = Polygon::new(exterior_ring, interior_rings);
-                    polygons.push(polygon);
-                }
-                let multi_polygon = MultiPolygon(polygons);
-                GeoGeometry::MultiPolygon(multi_polygon)
+                let polygons = coords
+                    .iter()
+                    .map(|rings| rings_to_polygon(rings))
+                    .collect::<Result<Vec<Polygon<f64>>, Error>>()?;
+                Ok(GeoGeometry::MultiPolygon(MultiPolygon(polygons)))
             }
-            _ => unimplemented!(),
+            Value::GeometryCollection(_) => Err(Error::UnsupportedGeometryType),
+        }
+    }
+}
+
+/// Convert a geojson Geometry to a GeoGeometry
+///
+/// # Panics
+/// On positions with fewer than two ordinates and on GeometryCollections;
+/// use `GeoGeometry::try_from(&geometry)` for untrusted input.
+impl From<Geometry> for GeoGeometry {
+    fn from(value: Geometry) -> Self {
+        match GeoGeometry::try_from(&value) {
+            Ok(geometry) => geometry,
+            Err(e) => panic!("Cannot convert geojson geometry: {}", e),
         }
     }
 }
@@ -310,7 +442,10 @@ impl From<Feature> for GeoFeature {
             properties: feature.properties,
             bbox: feature.bbox,
             foreign_members: feature.foreign_members,
-            geometry: feature.geometry.map(|g| g.into()),
+            // Geometries that cannot be converted are dropped, so collectors skip the feature
+            geometry: feature
+                .geometry
+                .and_then(|g| GeoGeometry::try_from(&g).ok()),
         }
     }
 }
@@ -336,3 +471,116 @@ impl From<GeoFeatureCollection> for FeatureCollection {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rectangle_area() {
+        assert_eq!(Rectangle::from_corners((0.0, 0.0), (4.0, 2.5)).area(), 10.0);
+        assert_eq!(Rectangle::from_corners((1.0, 1.0), (1.0, 5.0)).area(), 0.0);
+    }
+
+    #[test]
+    fn test_rectangle_intersection_area() {
+        let rect = Rectangle::from_corners((0.0, 0.0), (2.0, 2.0));
+
+        let disjoint = Rectangle::from_corners((5.0, 5.0), (6.0, 6.0));
+        assert_eq!(rect.intersection_area(&disjoint), 0.0);
+
+        let touching = Rectangle::from_corners((2.0, 0.0), (3.0, 2.0));
+        assert_eq!(rect.intersection_area(&touching), 0.0);
+
+        let overlapping = Rectangle::from_corners((1.0, 1.5), (3.0, 3.0));
+        assert_eq!(rect.intersection_area(&overlapping), 0.5);
+        assert_eq!(overlapping.intersection_area(&rect), 0.5);
+
+        let contained = Rectangle::from_corners((0.5, 0.5), (1.0, 1.0));
+        assert_eq!(rect.intersection_area(&contained), contained.area());
+    }
+
+    #[test]
+    fn test_rectangle_intersection() {
+        let rect = Rectangle::from_corners((0.0, 0.0), (2.0, 2.0));
+
+        let disjoint = Rectangle::from_corners((5.0, 5.0), (6.0, 6.0));
+        assert_eq!(rect.intersection(&disjoint), None);
+
+        // Shared edges and corners intersect in a degenerate rectangle
+        let touching = Rectangle::from_corners((2.0, 0.5), (3.0, 3.0));
+        assert_eq!(
+            rect.intersection(&touching),
+            Some(Rectangle::from_corners((2.0, 0.5), (2.0, 2.0)))
+        );
+        let corner = Rectangle::from_corners((2.0, 2.0), (3.0, 3.0));
+        assert_eq!(
+            rect.intersection(&corner),
+            Some(Rectangle::from_corners((2.0, 2.0), (2.0, 2.0)))
+        );
+        assert_eq!(rect.intersection(&touching).unwrap().area(), 0.0);
+
+        let contained = Rectangle::from_corners((0.5, 0.5), (1.0, 1.0));
+        assert_eq!(rect.intersection(&contained), Some(contained.clone()));
+        assert_eq!(contained.intersection(&rect), Some(contained));
+
+        let overlapping = Rectangle::from_corners((1.0, 1.5), (3.0, 3.0));
+        assert_eq!(
+            rect.intersection(&overlapping),
+            overlapping.intersection(&rect)
+        );
+        assert_eq!(
+            rect.intersection(&overlapping).unwrap().area(),
+            rect.intersection_area(&overlapping)
+        );
+    }
+
+    #[test]
+    fn test_rectangle_union_and_contains() {
+        let rect = Rectangle::from_corners((0.0, 0.0), (2.0, 2.0));
+        let other = Rectangle::from_corners((3.0, -1.0), (4.0, 1.0));
+
+        let union = rect.union(&other);
+        assert_eq!(union, Rectangle::from_corners((0.0, -1.0), (4.0, 2.0)));
+        assert!(union.contains(&rect) && union.contains(&other));
+        assert!(rect.contains(&rect));
+        assert!(rect.contains(&Rectangle::from_corners((0.0, 0.0), (1.0, 2.0))));
+        assert!(!rect.contains(&other));
+        assert!(rect.contains(&Point::new(1.0, 1.0)));
+        assert!(!rect.contains(&Point::new(2.0, 1.0)));
+    }
+
+    #[test]
+    fn test_rectangle_measures_and_expand() {
+        let rect = Rectangle::from_corners((1.0, 2.0), (5.0, 4.0));
+        assert_eq!((rect.width(), rect.height()), (4.0, 2.0));
+        assert_eq!(rect.center(), Coord { x: 3.0, y: 3.0 });
+
+        assert_eq!(rect.expand(1.0), Rectangle::from_corners((0.0, 1.0), (6.0, 5.0)));
+        assert_eq!(rect.expand(0.0), rect);
+        assert_eq!(
+            rect.expand(-1.5),
+            Rectangle::from_corners((2.5, 3.0), (3.5, 3.0))
+        );
+        assert_eq!(rect.to_string(), "[1, 2, 5, 4]");
+    }
+
+    #[test]
+    fn test_rectangle_conversions() {
+        let polygon = Polygon::new(
+            LineString::from(vec![(1.0, 1.0), (3.0, 1.0), (2.0, 4.0), (1.0, 1.0)]),
+            vec![],
+        );
+        let rect = Rectangle::try_from(polygon).unwrap();
+        assert_eq!(rect, Rectangle::from_corners((1.0, 1.0), (3.0, 4.0)));
+        let empty = Polygon::new(LineString::new(vec![]), vec![]);
+        assert!(matches!(Rectangle::try_from(empty), Err(Error::InvalidCoordinates)));
+
+        let feature: Feature = rect.into();
+        assert_eq!(feature.bbox, Some(vec![1.0, 1.0, 3.0, 4.0]));
+        let Some(Value::Polygon(rings)) = feature.geometry.map(|geometry| geometry.value) else {
+            panic!("expected a polygon");
+        };
+        assert_eq!(rings[0].len(), 5);
+    }
+}
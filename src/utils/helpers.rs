@@ -0,0 +1,1432 @@
+use crate::utils::error::Error;
+use crate::utils::geometry::GeoGeometry;
+use geo::{BoundingRect, Contains, Coord, CoordsIter, Geometry, Intersects, LineString, MultiLineString, MultiPoint, MultiPolygon, Point, Polygon, Rect};
+use std::sync::OnceLock;
+
+pub const GERMANY_BBOX: [f64; 4] = [
+    5.866211,  // Min longitude
+    47.270111, // Min latitude
+    15.013611, // Max longitude
+    55.058333, // Max latitude
+];
+
+/// Simplified outline of Germany as (longitude, latitude) pairs, accurate to a
+/// few kilometers. Islands are left out.
+const GERMANY_BOUNDARY: &[(f64, f64)] = &[
+    (7.59, 47.59), // Basel
+    (7.52, 47.70),
+    (7.56, 48.03),
+    (7.57, 48.12),
+    (7.80, 48.57), // Kehl
+    (7.97, 48.76),
+    (8.23, 48.97),
+    (7.93, 49.05),
+    (7.63, 49.05),
+    (7.45, 49.17),
+    (7.10, 49.15),
+    (6.84, 49.21),
+    (6.56, 49.42),
+    (6.36, 49.47),
+    (6.51, 49.72),
+    (6.49, 49.81),
+    (6.14, 50.13),
+    (6.40, 50.32),
+    (6.02, 50.75), // Aachen
+    (5.87, 51.05),
+    (6.17, 51.19),
+    (6.07, 51.25),
+    (6.20, 51.50),
+    (5.95, 51.80),
+    (6.10, 51.85),
+    (6.73, 51.90),
+    (6.83, 52.12),
+    (7.05, 52.24),
+    (6.70, 52.48),
+    (7.05, 52.64),
+    (7.20, 53.00),
+    (7.21, 53.24),
+    (6.70, 53.60),
+    (8.00, 53.75),
+    (8.50, 53.55),
+    (8.80, 53.90),
+    (8.60, 54.30),
+    (8.30, 55.05),
+    (8.66, 54.91),
+    (9.45, 54.82), // Flensburg
+    (10.00, 54.70),
+    (10.90, 54.40),
+    (11.10, 54.50),
+    (10.90, 54.00),
+    (11.50, 54.10),
+    (12.30, 54.40),
+    (13.40, 54.70),
+    (14.20, 53.90),
+    (14.40, 53.30),
+    (14.10, 52.90),
+    (14.60, 52.60),
+    (14.70, 52.10),
+    (14.60, 51.80),
+    (15.00, 51.30), // Görlitz
+    (14.80, 50.85),
+    (14.30, 51.05),
+    (13.50, 50.70),
+    (12.90, 50.40),
+    (12.10, 50.30),
+    (12.50, 49.70),
+    (13.00, 49.30),
+    (13.84, 48.77),
+    (13.45, 48.57), // Passau
+    (13.03, 48.26),
+    (12.75, 48.12),
+    (13.00, 47.75),
+    (13.00, 47.47),
+    (12.70, 47.68),
+    (12.20, 47.60),
+    (11.00, 47.40),
+    (10.45, 47.55),
+    (10.20, 47.28),
+    (9.56, 47.53), // Lindau
+    (9.18, 47.65),
+    (8.60, 47.80),
+    (8.21, 47.61),
+    (7.80, 47.56),
+    (7.59, 47.59),
+];
+
+static GERMANY_BOUNDARY_POLYGON: OnceLock<Polygon> = OnceLock::new();
+
+/// Returns the simplified boundary polygon of Germany.
+pub fn germany_boundary() -> &'static Polygon {
+    GERMANY_BOUNDARY_POLYGON
+        .get_or_init(|| Polygon::new(LineString::from(GERMANY_BOUNDARY.to_vec()), vec![]))
+}
+
+/// Checks if a GeoJSON position is within the specified bounding box.
+///
+/// The first two elements are x and y; further elements such as an elevation
+/// are ignored.
+///
+/// # Arguments
+/// * `coord`: The position to check
+/// * `bbox`: The bounding box to check against
+///
+/// # Returns
+/// * `Ok(true)` if the position is within the bounding box, `Ok(false)` if it
+///   is outside, or `Error::InvalidCoordinates` if it has fewer than two elements
+pub fn in_bounding_box_checked(coord: &[f64], bbox: &[f64; 4]) -> Result<bool, Error> {
+    match coord {
+        [x, y, ..] => {
+            let rect = Rect::new(
+                Coord { x: bbox[0], y: bbox[1] },
+                Coord { x: bbox[2], y: bbox[3] },
+            );
+            Ok(rect.contains(&Point::new(*x, *y)))
+        }
+        _ => Err(Error::InvalidCoordinates),
+    }
+}
+
+/// Checks if a GeoJSON position is within `GERMANY_BBOX`, treating malformed
+/// positions as outside. Use `in_bounding_box_checked` to tell them apart.
+#[allow(dead_code)]
+pub fn is_coordinate_in_germany(coord: &[f64]) -> bool {
+    in_bounding_box_checked(coord, &GERMANY_BBOX).unwrap_or(false)
+}
+
+/// Checks if a GeoJSON position is within the boundary polygon of Germany,
+/// using `GERMANY_BBOX` as a fast pre-filter. Malformed positions are outside.
+#[allow(dead_code)]
+pub fn is_coordinate_in_germany_precise(coord: &[f64]) -> bool {
+    is_coordinate_in_germany(coord) && germany_boundary().contains(&Point::new(coord[0], coord[1]))
+}
+
+/// Checks if every coordinate of a geometry lies within a polygon. Like
+/// `InBoundingBox`, only the exterior rings of polygons are checked.
+///
+/// # Arguments
+/// * `geometry`: The geometry to check
+/// * `polygon`: The polygon to check against
+///
+/// # Returns
+/// * `true` if all checked coordinates are within the polygon, `false` otherwise
+pub fn geometry_in_polygon(geometry: &GeoGeometry, polygon: &Polygon) -> bool {
+    let inside = |coord: Coord| polygon.contains(&coord);
+    match geometry {
+        GeoGeometry::Point(point) => inside(point.0),
+        GeoGeometry::LineString(line) => line.coords_iter().all(inside),
+        GeoGeometry::Polygon(poly) => poly.exterior_coords_iter().all(inside),
+        GeoGeometry::MultiPoint(points) => points.coords_iter().all(inside),
+        GeoGeometry::MultiLineString(lines) => lines.coords_iter().all(inside),
+        GeoGeometry::MultiPolygon(polygons) => polygons.exterior_coords_iter().all(inside),
+    }
+}
+
+/// Trait for checking if a geographic coordinate is within a specific bounding box.
+pub trait InBoundingBox {
+    /// Checks if the coordinate is within the specified bounding box.
+    /// 
+    /// # Arguments
+    /// * `bbox`: The bounding box to check against
+    /// 
+    /// # Returns
+    /// * `true` if the coordinate is within the bounding box, `false` otherwise
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool;
+}
+
+/// Trait for checking if a shape overlaps a specific bounding box.
+///
+/// Rectangles overlap if they share at least one point; all other shapes if
+/// at least one of their coordinates lies within the bounding box. Like
+/// `InBoundingBox`, only the exterior rings of polygons are checked.
+pub trait IntersectsBoundingBox {
+    /// Checks if the shape shares at least one point with the specified bounding box.
+    ///
+    /// # Arguments
+    /// * `bbox`: The bounding box to check against
+    ///
+    /// # Returns
+    /// * `true` if the shape intersects the bounding box, `false` otherwise
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool;
+}
+
+/// Trait for expanding and extending bounding boxes.
+pub trait BoundingBoxOps {
+    /// Expands a bounding box by a given radius.
+    /// 
+    /// # Arguments
+    /// * `radius`: The amount to expand the box by
+    /// 
+    /// # Returns
+    /// A new expanded bounding box
+    fn expand(&self, radius: f64) -> Rect;
+
+    /// Expands a bounding box by separate radii along the x and y axes.
+    ///
+    /// A negative radius shrinks the box along its axis, down to a degenerate
+    /// box at the center rather than inverting it.
+    ///
+    /// # Arguments
+    /// * `radius_x`: The amount to expand the box by along the x axis
+    /// * `radius_y`: The amount to expand the box by along the y axis
+    ///
+    /// # Returns
+    /// A new expanded bounding box
+    fn expand_xy(&self, radius_x: f64, radius_y: f64) -> Rect;
+
+    /// Expands a bounding box in WGS84 degrees by a distance in meters.
+    ///
+    /// The distance is converted to degree offsets on a sphere, with the
+    /// longitude offset widened by the cosine of `latitude`. Like `expand_xy`,
+    /// a negative distance shrinks the box down to a degenerate box.
+    ///
+    /// # Arguments
+    /// * `meters`: The distance to expand the box by
+    /// * `latitude`: The latitude in degrees the distance is converted at
+    ///
+    /// # Returns
+    /// A new expanded bounding box
+    #[allow(dead_code)]
+    fn expand_degrees(&self, meters: f64, latitude: f64) -> Rect;
+    
+    #[allow(dead_code)]
+    /// Extends a bounding box to align with a grid of specified cell size.
+    /// 
+    /// # Arguments
+    /// * `cell_size`: The size of the grid cells
+    /// 
+    /// # Returns
+    /// A new extended bounding box
+    fn extend(&self, cell_size: f64) -> Rect;
+}
+impl InBoundingBox for MultiLineString {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        self.iter().all(|ls| ls.coords().all(|coord| rect.contains(coord)))
+    }
+}
+
+impl InBoundingBox for Point {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        rect.contains(self)
+    }
+}
+
+impl InBoundingBox for MultiPoint {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        if let Some(bounding_rect) = self.bounding_rect() {
+            rect.contains(&bounding_rect)
+        } else {
+            false
+        }
+    }
+}
+
+impl InBoundingBox for LineString {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        self.coords().all(|coord| rect.contains(coord))
+    }
+}
+
+impl InBoundingBox for Polygon {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        self.exterior().coords().all(|coord| rect.contains(coord))
+    }
+}
+
+impl InBoundingBox for MultiPolygon {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        self.iter().all(|poly| poly.exterior().coords().all(|coord| rect.contains(coord)))
+    }
+}
+
+impl InBoundingBox for [f64; 2] {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        in_bounding_box_checked(self, bbox).unwrap_or(false)
+    }
+}
+
+impl InBoundingBox for Vec<f64> {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        if self.len() != 4 {
+            return false;
+        }
+        let coords = Rect::new(
+            Coord { x: self[0], y: self[1] },
+            Coord { x: self[2], y: self[3] },
+        );
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        rect.contains(&coords)
+    }
+}
+
+impl InBoundingBox for geo::Coord {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let point = Point::new(self.x, self.y);
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        rect.contains(&point)
+    }
+}
+
+impl InBoundingBox for Rect {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        rect.contains(self)
+    }
+}
+
+impl IntersectsBoundingBox for Rect {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        let rect = Rect::new(
+            Coord { x: bbox[0], y: bbox[1] },
+            Coord { x: bbox[2], y: bbox[3] },
+        );
+        rect.intersects(self)
+    }
+}
+
+impl InBoundingBox for Geometry {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        match self {
+            Geometry::Point(point) => point.in_bounding_box(bbox),
+            Geometry::Line(line) => {
+                line.start.in_bounding_box(bbox) && line.end.in_bounding_box(bbox)
+            }
+            Geometry::LineString(line) => line.in_bounding_box(bbox),
+            Geometry::Polygon(polygon) => polygon.in_bounding_box(bbox),
+            Geometry::MultiPoint(points) => points.in_bounding_box(bbox),
+            Geometry::MultiLineString(lines) => lines.in_bounding_box(bbox),
+            Geometry::MultiPolygon(polygons) => polygons.in_bounding_box(bbox),
+            Geometry::Rect(rect) => rect.in_bounding_box(bbox),
+            Geometry::Triangle(triangle) => {
+                triangle.to_array().iter().all(|c| c.in_bounding_box(bbox))
+            }
+            // An empty collection has no extent and is never inside
+            Geometry::GeometryCollection(collection) => collection
+                .bounding_rect()
+                .is_some_and(|rect| rect.in_bounding_box(bbox)),
+        }
+    }
+}
+
+impl InBoundingBox for GeoGeometry {
+    fn in_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        match self {
+            GeoGeometry::Point(point) => point.in_bounding_box(bbox),
+            GeoGeometry::LineString(line) => line.in_bounding_box(bbox),
+            GeoGeometry::Polygon(polygon) => polygon.in_bounding_box(bbox),
+            GeoGeometry::MultiPoint(points) => points.in_bounding_box(bbox),
+            GeoGeometry::MultiLineString(lines) => lines.in_bounding_box(bbox),
+            GeoGeometry::MultiPolygon(polygons) => polygons.in_bounding_box(bbox),
+        }
+    }
+}
+
+impl IntersectsBoundingBox for geo::Coord {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.in_bounding_box(bbox)
+    }
+}
+
+impl IntersectsBoundingBox for Point {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.in_bounding_box(bbox)
+    }
+}
+
+impl IntersectsBoundingBox for MultiPoint {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.iter().any(|point| point.in_bounding_box(bbox))
+    }
+}
+
+impl IntersectsBoundingBox for LineString {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.coords().any(|coord| coord.in_bounding_box(bbox))
+    }
+}
+
+impl IntersectsBoundingBox for MultiLineString {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.iter().any(|ls| ls.intersects_bounding_box(bbox))
+    }
+}
+
+impl IntersectsBoundingBox for Polygon {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.exterior().intersects_bounding_box(bbox)
+    }
+}
+
+impl IntersectsBoundingBox for MultiPolygon {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        self.iter().any(|poly| poly.intersects_bounding_box(bbox))
+    }
+}
+
+impl IntersectsBoundingBox for Geometry {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        match self {
+            Geometry::Point(point) => point.intersects_bounding_box(bbox),
+            Geometry::Line(line) => {
+                line.start.intersects_bounding_box(bbox) || line.end.intersects_bounding_box(bbox)
+            }
+            Geometry::LineString(line) => line.intersects_bounding_box(bbox),
+            Geometry::Polygon(polygon) => polygon.intersects_bounding_box(bbox),
+            Geometry::MultiPoint(points) => points.intersects_bounding_box(bbox),
+            Geometry::MultiLineString(lines) => lines.intersects_bounding_box(bbox),
+            Geometry::MultiPolygon(polygons) => polygons.intersects_bounding_box(bbox),
+            Geometry::Rect(rect) => rect.intersects_bounding_box(bbox),
+            Geometry::Triangle(triangle) => {
+                triangle.to_array().iter().any(|c| c.intersects_bounding_box(bbox))
+            }
+            Geometry::GeometryCollection(collection) => collection
+                .iter()
+                .any(|geometry| geometry.intersects_bounding_box(bbox)),
+        }
+    }
+}
+
+impl IntersectsBoundingBox for GeoGeometry {
+    fn intersects_bounding_box(&self, bbox: &[f64; 4]) -> bool {
+        match self {
+            GeoGeometry::Point(point) => point.intersects_bounding_box(bbox),
+            GeoGeometry::LineString(line) => line.intersects_bounding_box(bbox),
+            GeoGeometry::Polygon(polygon) => polygon.intersects_bounding_box(bbox),
+            GeoGeometry::MultiPoint(points) => points.intersects_bounding_box(bbox),
+            GeoGeometry::MultiLineString(lines) => lines.intersects_bounding_box(bbox),
+            GeoGeometry::MultiPolygon(polygons) => polygons.intersects_bounding_box(bbox),
+        }
+    }
+}
+
+impl BoundingBoxOps for Rect {
+    fn expand(&self, radius: f64) -> Rect {
+        let expansion_amount = if radius == 0.0 { 4.0 } else { radius };
+        self.expand_xy(expansion_amount, expansion_amount)
+    }
+
+    fn expand_xy(&self, radius_x: f64, radius_y: f64) -> Rect {
+        let center = self.center();
+        let expanded_min_x = (self.min().x - radius_x).min(center.x);
+        let expanded_max_x = (self.max().x + radius_x).max(center.x);
+        let expanded_min_y = (self.min().y - radius_y).min(center.y);
+        let expanded_max_y = (self.max().y + radius_y).max(center.y);
+        Rect::new(
+            Coord {
+                x: expanded_min_x,
+                y: expanded_min_y,
+            },
+            Coord {
+                x: expanded_max_x,
+                y: expanded_max_y,
+            },
+        )
+    }
+    
+    fn expand_degrees(&self, meters: f64, latitude: f64) -> Rect {
+        let degrees_y = meters / METERS_PER_DEGREE;
+        // Keep the offset finite at the poles
+        let cos_latitude = latitude.to_radians().cos().abs().max(1e-6);
+        self.expand_xy(degrees_y / cos_latitude, degrees_y)
+    }
+
+    fn extend(&self, cell_size: f64) -> Rect {
+        if cell_size <= 0.0 {
+            return *self;
+        }
+        
+        let width = self.width();
+        let height = self.height();
+        
+        let extra_width = padding_to_multiple(width, cell_size);
+        let extra_height = padding_to_multiple(height, cell_size);
+        
+        let new_min_x = self.min().x - extra_width / 2.0;
+        let new_min_y = self.min().y - extra_height / 2.0;
+        let new_max_x = self.max().x + extra_width / 2.0;
+        let new_max_y = self.max().y + extra_height / 2.0;
+        
+        Rect::new(
+            Coord {
+                x: new_min_x,
+                y: new_min_y,
+            },
+            Coord {
+                x: new_max_x,
+                y: new_max_y,
+            },
+        )
+    }
+}
+
+/// Padding that grows `length` to the smallest multiple of `cell_size` that is
+/// at least `length`. Ratios within floating point noise of a whole number, such
+/// as 1.1 / 0.1, count as exact multiples and get no padding.
+fn padding_to_multiple(length: f64, cell_size: f64) -> f64 {
+    let cells = (length / cell_size - 1e-9).ceil().max(0.0);
+    (cells * cell_size - length).max(0.0)
+}
+
+/// Approximate length of one degree of latitude in meters.
+#[allow(dead_code)]
+const METERS_PER_DEGREE: f64 = 111_320.0;
+
+#[allow(dead_code)]
+pub struct Grid {
+    pub cells: Vec<Rect>,
+    num_cols: usize,
+    num_rows: usize,
+    cell_width: f64,
+    cell_height: f64,
+}
+
+impl Grid {
+    pub fn new(bbox: Rect, cell_width: f64, cell_height: f64) -> Self {
+        let indexed_cells = create_indexed_square_grid(bbox, cell_width, cell_height);
+        // Cells are pushed column by column, so the last one has the highest indices
+        let Some(&((last_col, last_row), _)) = indexed_cells.last() else {
+            return Self::empty();
+        };
+        let cells = indexed_cells.into_iter().map(|(_, cell)| cell).collect();
+
+        Self {
+            cells,
+            num_cols: last_col + 1,
+            num_rows: last_row + 1,
+            cell_width,
+            cell_height,
+        }
+    }
+
+    pub fn empty() -> Self {
+        Self {
+            cells: Vec::new(),
+            num_cols: 0,
+            num_rows: 0,
+            cell_width: 0.0,
+            cell_height: 0.0,
+        }
+    }
+
+}
+
+/// Creates a grid of cells covering a bounding box, keyed by their grid position.
+///
+/// Cells start at the minimum corner of the bbox; the last column and row are
+/// clipped to its maximum. A non-positive cell size or a degenerate bbox yields
+/// no cells.
+///
+/// # Arguments
+/// * `bbox`: The bounding box to cover
+/// * `cell_width`: The width of a cell
+/// * `cell_height`: The height of a cell
+///
+/// # Returns
+/// `((col, row), cell)` tuples, column by column from the minimum x
+pub fn create_indexed_square_grid(
+    bbox: Rect,
+    cell_width: f64,
+    cell_height: f64,
+) -> Vec<((usize, usize), Rect)> {
+    if cell_width <= 0.0 || cell_height <= 0.0 {
+        return Vec::new();
+    }
+
+    let bbox_min_x = bbox.min().x;
+    let bbox_min_y = bbox.min().y;
+    let bbox_max_x = bbox.max().x;
+    let bbox_max_y = bbox.max().y;
+
+    // Handle cases where bbox is degenerate or inverted (max <= min)
+    if bbox_min_x >= bbox_max_x || bbox_min_y >= bbox_max_y {
+        return Vec::new();
+    }
+
+    let num_cols = ((bbox_max_x - bbox_min_x) / cell_width).ceil() as usize;
+    let num_rows = ((bbox_max_y - bbox_min_y) / cell_height).ceil() as usize;
+    let mut cells = Vec::with_capacity(num_cols * num_rows);
+
+    for i in 0..num_cols {
+        let x = bbox_min_x + (i as f64 * cell_width);
+        for j in 0..num_rows {
+            let y = bbox_min_y + (j as f64 * cell_height);
+            let cell_rect = Rect::new(
+                Coord { x, y },
+                Coord {
+                    x: f64::min(x + cell_width, bbox_max_x),
+                    y: f64::min(y + cell_height, bbox_max_y),
+                },
+            );
+            cells.push(((i, j), cell_rect));
+        }
+    }
+
+    cells
+}
+
+/// Creates a grid of flat-top hexagons covering a bounding box.
+///
+/// `hex_size` is the distance from a hexagon's center to its vertices. The
+/// centers lie on a hexagonal lattice anchored at the minimum corner of the
+/// bbox, and every hexagon overlapping the bbox is returned whole, so the
+/// outer cells extend beyond it. Like `Grid::new`, a non-positive size or a
+/// degenerate bbox yields no cells.
+///
+/// # Arguments
+/// * `bbox`: The bounding box to cover
+/// * `hex_size`: The circumradius of the hexagons
+///
+/// # Returns
+/// The hexagons, column by column from the minimum x
+pub fn create_hex_grid(bbox: Rect, hex_size: f64) -> Vec<Polygon> {
+    if hex_size <= 0.0 {
+        return Vec::new();
+    }
+    let (min, max) = (bbox.min(), bbox.max());
+    if min.x >= max.x || min.y >= max.y {
+        return Vec::new();
+    }
+
+    let column_step = 1.5 * hex_size;
+    let row_step = 3f64.sqrt() * hex_size;
+    // The last column's center lies on or beyond the max x; every column is
+    // shifted down by half a row against its neighbors
+    let num_cols = ((max.x - min.x) / column_step).ceil() as usize + 1;
+    let num_rows = ((max.y - min.y) / row_step).ceil() as usize + 2;
+
+    let mut cells = Vec::new();
+    for i in 0..num_cols {
+        let center_x = min.x + i as f64 * column_step;
+        let offset = if i % 2 == 1 { row_step / 2.0 } else { 0.0 };
+        for j in 0..num_rows {
+            let center_y = min.y + (j as f64 - 1.0) * row_step + offset;
+            let overlaps = center_x - hex_size < max.x
+                && center_x + hex_size > min.x
+                && center_y - row_step / 2.0 < max.y
+                && center_y + row_step / 2.0 > min.y;
+            if overlaps {
+                cells.push(hexagon(Coord { x: center_x, y: center_y }, hex_size));
+            }
+        }
+    }
+    cells
+}
+
+/// A flat-top hexagon around `center` with vertices `size` away from it.
+fn hexagon(center: Coord, size: f64) -> Polygon {
+    let ring: Vec<Coord> = (0..6)
+        .map(|k| {
+            let angle = (60.0 * k as f64).to_radians();
+            Coord {
+                x: center.x + size * angle.cos(),
+                y: center.y + size * angle.sin(),
+            }
+        })
+        .collect();
+    Polygon::new(LineString::from(ring), vec![])
+}
+
+/// Default tolerance for treating a ring as closed.
+pub const CLOSED_RING_EPSILON: f64 = 1e-8;
+
+/// Checks if a LineString is closed within a given tolerance.
+///
+/// Unlike `LineString::is_closed`, the first and last coordinates only have to
+/// agree to within `eps` on each axis, so floating-point noise is tolerated.
+///
+/// # Arguments
+/// * `line`: The LineString to check
+/// * `eps`: The maximum allowed per-axis distance between the first and last coordinate
+///
+/// # Returns
+/// * `true` if the LineString is closed within `eps`, `false` otherwise
+pub fn is_closed_eps(line: &LineString, eps: f64) -> bool {
+    match (line.0.first(), line.0.last()) {
+        (Some(first), Some(last)) => {
+            (first.x - last.x).abs() <= eps && (first.y - last.y).abs() <= eps
+        }
+        // An empty LineString is closed, matching `LineString::is_closed`
+        _ => true,
+    }
+}
+
+/// Highest number of decimals that still changes an f64 coordinate.
+const MAX_PRECISION_DECIMALS: u32 = 15;
+
+/// Rounds a coordinate value to a fixed number of decimal places.
+///
+/// # Arguments
+/// * `value`: The coordinate value to round
+/// * `decimals`: The number of decimal places to keep
+///
+/// # Returns
+/// * The rounded value; unchanged if `decimals` exceeds f64 precision
+pub fn snap_precision(value: f64, decimals: u32) -> f64 {
+    if decimals > MAX_PRECISION_DECIMALS {
+        return value;
+    }
+    let factor = 10f64.powi(decimals as i32);
+    (value * factor).round() / factor
+}
+
+/// The shape of a LineString, as far as ring handling is concerned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineKind {
+    /// The LineString has no coordinates.
+    Empty,
+    /// The first and last coordinates differ.
+    Open,
+    /// The first and last coordinates agree, so the line can be treated as a polygon ring.
+    ClosedRing,
+}
+
+/// Classifies a LineString as empty, open or a closed ring.
+///
+/// # Arguments
+/// * `line`: The LineString to classify
+/// * `eps`: The tolerance for the first and last coordinate to count as equal
+///
+/// # Returns
+/// * The kind of the LineString
+pub fn classify_linestring(line: &LineString<f64>, eps: f64) -> LineKind {
+    if line.0.is_empty() {
+        LineKind::Empty
+    } else if is_closed_eps(line, eps) {
+        LineKind::ClosedRing
+    } else {
+        LineKind::Open
+    }
+}
+
+/// Snaps the last coordinate of a LineString onto its first one,
+/// so a ring closed within a tolerance becomes exactly closed.
+pub fn snap_ring_closed(line: &mut LineString) {
+    if let Some(&first) = line.0.first()
+        && let Some(last) = line.0.last_mut()
+    {
+        *last = first;
+    }
+}
+
+/// Reads the z value (elevation) of a geojson position.
+///
+/// # Arguments
+/// * `c`: The position to read
+///
+/// # Returns
+/// * The third element of the position, or None for a 2D position
+pub fn coord_z(c: &[f64]) -> Option<f64> {
+    c.get(2).copied()
+}
+
+/// Copies the z values of `original` onto the matching positions of `transformed`.
+///
+/// Positions are matched by their place in the geometry, so `transformed` is
+/// expected to come from `original` through a 2D transform that keeps the
+/// positions in order. Each z value is passed through `map_z` on the way.
+/// Positions without a counterpart, or whose original is 2D, are left as they are.
+///
+/// # Arguments
+/// * `original`: The geometry to take the z values from
+/// * `transformed`: The geometry to add the z values to
+/// * `map_z`: Maps an original z value to the output z value
+pub fn restore_z(
+    original: &geojson::Geometry,
+    transformed: &mut geojson::Geometry,
+    map_z: impl Fn(f64) -> f64,
+) {
+    use geojson::Value;
+
+    let set_z = |original: &Vec<f64>, position: &mut Vec<f64>| {
+        if let Some(z) = coord_z(original) {
+            position.truncate(2);
+            position.push(map_z(z));
+        }
+    };
+    let set_line_z = |original: &Vec<Vec<f64>>, line: &mut Vec<Vec<f64>>| {
+        original.iter().zip(line.iter_mut()).for_each(|(o, p)| set_z(o, p));
+    };
+    match (&original.value, &mut transformed.value) {
+        (Value::Point(original), Value::Point(position)) => set_z(original, position),
+        (Value::LineString(original), Value::LineString(line))
+        | (Value::MultiPoint(original), Value::MultiPoint(line)) => set_line_z(original, line),
+        (Value::Polygon(original), Value::Polygon(lines))
+        | (Value::MultiLineString(original), Value::MultiLineString(lines)) => {
+            original.iter().zip(lines.iter_mut()).for_each(|(o, l)| set_line_z(o, l));
+        }
+        (Value::MultiPolygon(original), Value::MultiPolygon(polygons)) => {
+            for (original, rings) in original.iter().zip(polygons.iter_mut()) {
+                original.iter().zip(rings.iter_mut()).for_each(|(o, r)| set_line_z(o, r));
+            }
+        }
+        // Differently shaped geometries have no matching positions
+        _ => {}
+    }
+}
+
+/// Unions GeoJSON bboxes of one dimension.
+///
+/// # Arguments
+/// * `bboxes`: The bboxes to union; bboxes of another dimension are ignored
+/// * `dimensions`: The number of dimensions, 2 or 3
+///
+/// # Returns
+/// * The union, or None if there are no bboxes of that dimension
+pub(crate) fn union_bboxes<'a>(
+    bboxes: impl IntoIterator<Item = &'a geojson::Bbox>,
+    dimensions: usize,
+) -> Option<geojson::Bbox> {
+    bboxes
+        .into_iter()
+        .filter(|bbox| bbox.len() == 2 * dimensions)
+        .fold(None, |union: Option<geojson::Bbox>, bbox| match union {
+            None => Some(bbox.clone()),
+            Some(union) => Some(
+                (0..2 * dimensions)
+                    .map(|i| {
+                        if i < dimensions {
+                            union[i].min(bbox[i])
+                        } else {
+                            union[i].max(bbox[i])
+                        }
+                    })
+                    .collect(),
+            ),
+        })
+}
+
+#[allow(unused_imports)]
+mod tests {
+    use super::*;
+    use geo::{Coord, LineString, MultiPoint, Point, Polygon, Rect};
+    use geojson::{Feature, FeatureCollection, Value};
+    use ordered_float::OrderedFloat;
+    use std::collections::HashSet;
+
+    // Helper to create Rects more concisely in tests
+    #[allow(unused)]
+    fn r(min_x: f64, min_y: f64, max_x: f64, max_y: f64) -> Rect {
+        Rect::new(Coord { x: min_x, y: min_y }, Coord { x: max_x, y: max_y })
+    }
+    #[test]
+    fn test_is_closed_eps() {
+        let ring = LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (1e-9, 0.0)]);
+        assert!(!ring.is_closed());
+        assert!(is_closed_eps(&ring, CLOSED_RING_EPSILON));
+        assert!(!is_closed_eps(&ring, 1e-12));
+
+        let open = LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0)]);
+        assert!(!is_closed_eps(&open, CLOSED_RING_EPSILON));
+    }
+
+    #[test]
+    fn test_snap_precision() {
+        assert_eq!(snap_precision(9.123456789, 6), 9.123457);
+        assert_eq!(snap_precision(-9.5, 0), -10.0);
+        assert_eq!(snap_precision(1.23, 20), 1.23);
+    }
+
+    #[test]
+    fn test_classify_linestring() {
+        let eps = CLOSED_RING_EPSILON;
+        assert_eq!(classify_linestring(&LineString::new(vec![]), eps), LineKind::Empty);
+        assert_eq!(
+            classify_linestring(&LineString::from(vec![(0.0, 0.0), (1.0, 0.0)]), eps),
+            LineKind::Open
+        );
+        let ring = LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 0.0)]);
+        assert_eq!(classify_linestring(&ring, eps), LineKind::ClosedRing);
+        let nearly_closed = LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (1e-9, 0.0)]);
+        assert_eq!(classify_linestring(&nearly_closed, eps), LineKind::ClosedRing);
+        assert_eq!(classify_linestring(&nearly_closed, 0.0), LineKind::Open);
+    }
+
+    #[test]
+    fn test_snap_ring_closed() {
+        let mut ring = LineString::from(vec![(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (1e-9, 0.0)]);
+        snap_ring_closed(&mut ring);
+        assert!(ring.is_closed());
+        assert_eq!(ring.0.len(), 4);
+    }
+
+    #[test]
+    fn test_create_square_grid_exact_fit_single_cell() {
+        let bbox = r(0.0, 0.0, 200.0, 200.0);
+        let cell_width = 200.0;
+        let cell_height = 200.0;
+
+        let expected_grid = vec![r(0.0, 0.0, 200.0, 200.0)];
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+        assert_eq!(grid.cells.len(), expected_grid.len());
+        assert_eq!(grid.cells, expected_grid);
+    }
+
+    #[test]
+    fn test_create_square_grid_exact_fit_multiple_cells() {
+        let bbox = r(0.0, 0.0, 400.0, 400.0);
+        let cell_width = 200.0;
+        let cell_height = 200.0;
+
+        let expected_grid = vec![
+            r(0.0, 0.0, 200.0, 200.0),
+            r(0.0, 200.0, 200.0, 400.0),
+            r(200.0, 0.0, 400.0, 200.0),
+            r(200.0, 200.0, 400.0, 400.0),
+        ];
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+
+        // Sort both vectors for reliable comparison, as the order might depend on loop implementation
+        let mut grid_sorted = grid;
+        grid_sorted.cells.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+        let mut expected_grid_sorted = expected_grid;
+        expected_grid_sorted.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+
+        assert_eq!(grid_sorted.cells.len(), expected_grid_sorted.len());
+        assert_eq!(grid_sorted.cells, expected_grid_sorted);
+    }
+
+    #[test]
+    fn test_create_square_grid_clipping_required() {
+        let bbox = r(0.0, 0.0, 450.0, 350.0);
+        let cell_width = 200.0;
+        let cell_height = 200.0;
+
+        let expected_grid = vec![
+            // Row 1 (y=0)
+            r(0.0, 0.0, 200.0, 200.0),
+            r(200.0, 0.0, 400.0, 200.0),
+            r(400.0, 0.0, 450.0, 200.0), // Clipped x
+            // Row 2 (y=200)
+            r(0.0, 200.0, 200.0, 350.0),   // Clipped y
+            r(200.0, 200.0, 400.0, 350.0), // Clipped y
+            r(400.0, 200.0, 450.0, 350.0), // Clipped x and y
+        ];
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+
+        // Sort both vectors for reliable comparison
+        let mut grid_sorted = grid;
+        grid_sorted.cells.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+        let mut expected_grid_sorted = expected_grid;
+        expected_grid_sorted.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+
+        assert_eq!(grid_sorted.cells.len(), expected_grid_sorted.len());
+        assert_eq!(grid_sorted.cells, expected_grid_sorted);
+    }
+
+    #[test]
+    fn test_create_indexed_square_grid_indices_and_clipping() {
+        let bbox = r(0.0, 0.0, 450.0, 350.0);
+
+        let grid = create_indexed_square_grid(bbox, 200.0, 200.0);
+
+        assert_eq!(
+            grid,
+            vec![
+                ((0, 0), r(0.0, 0.0, 200.0, 200.0)),
+                ((0, 1), r(0.0, 200.0, 200.0, 350.0)),   // Clipped y
+                ((1, 0), r(200.0, 0.0, 400.0, 200.0)),
+                ((1, 1), r(200.0, 200.0, 400.0, 350.0)), // Clipped y
+                ((2, 0), r(400.0, 0.0, 450.0, 200.0)),   // Clipped x
+                ((2, 1), r(400.0, 200.0, 450.0, 350.0)), // Clipped x and y
+            ]
+        );
+        let flat: Vec<Rect> = grid.into_iter().map(|(_, cell)| cell).collect();
+        assert_eq!(Grid::new(bbox, 200.0, 200.0).cells, flat);
+    }
+
+    #[test]
+    fn test_create_square_grid_bbox_smaller_than_cell() {
+        let bbox = r(10.0, 20.0, 50.0, 70.0);
+        let cell_width = 200.0;
+        let cell_height = 200.0;
+
+        // Should result in a single cell clipped to the bbox
+        let expected_grid = vec![r(10.0, 20.0, 50.0, 70.0)];
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+        assert_eq!(grid.cells.len(), expected_grid.len());
+        assert_eq!(grid.cells, expected_grid);
+    }
+
+    #[test]
+    fn test_create_square_grid_zero_cell_width() {
+        let bbox = r(0.0, 0.0, 200.0, 200.0);
+        let cell_width = 0.0;
+        let cell_height = 200.0;
+
+        let expected_grid: Vec<Rect> = vec![]; // Should return empty grid
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+        assert_eq!(grid.cells.len(), expected_grid.len());
+        assert_eq!(grid.cells, expected_grid);
+    }
+
+    #[test]
+    fn test_create_square_grid_zero_cell_height() {
+        let bbox = r(0.0, 0.0, 200.0, 200.0);
+        let cell_width = 200.0;
+        let cell_height = 0.0;
+
+        let expected_grid: Vec<Rect> = vec![]; // Should return empty grid
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+        assert_eq!(grid.cells.len(), expected_grid.len());
+        assert_eq!(grid.cells, expected_grid);
+    }
+
+    #[test]
+    fn test_create_square_grid_zero_bbox_area() {
+        let bbox = r(0.0, 0.0, 0.0, 0.0); // Point-like bbox
+        let cell_width = 10.0;
+        let cell_height = 10.0;
+
+        let expected_grid: Vec<Rect> = vec![]; // Should return empty grid
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+        assert_eq!(grid.cells.len(), expected_grid.len());
+        assert_eq!(grid.cells, expected_grid);
+    }
+
+    #[test]
+    fn test_create_square_grid_negative_coords() {
+        let bbox = r(-100.0, -100.0, 100.0, 100.0);
+        let cell_width = 50.0;
+        let cell_height = 50.0;
+
+        let expected_grid = vec![
+            // Row 1 (y=-100)
+            r(-100.0, -100.0, -50.0, -50.0),
+            r(-50.0, -100.0, 0.0, -50.0),
+            r(0.0, -100.0, 50.0, -50.0),
+            r(50.0, -100.0, 100.0, -50.0),
+            // Row 2 (y=-50)
+            r(-100.0, -50.0, -50.0, 0.0),
+            r(-50.0, -50.0, 0.0, 0.0),
+            r(0.0, -50.0, 50.0, 0.0),
+            r(50.0, -50.0, 100.0, 0.0),
+            // Row 3 (y=0)
+            r(-100.0, 0.0, -50.0, 50.0),
+            r(-50.0, 0.0, 0.0, 50.0),
+            r(0.0, 0.0, 50.0, 50.0),
+            r(50.0, 0.0, 100.0, 50.0),
+            // Row 4 (y=50)
+            r(-100.0, 50.0, -50.0, 100.0),
+            r(-50.0, 50.0, 0.0, 100.0),
+            r(0.0, 50.0, 50.0, 100.0),
+            r(50.0, 50.0, 100.0, 100.0),
+        ];
+
+        let grid = Grid::new(bbox, cell_width, cell_height);
+
+        // Sort both vectors for reliable comparison
+        let mut grid_sorted = grid;
+        grid_sorted.cells.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+        let mut expected_grid_sorted = expected_grid;
+        expected_grid_sorted.sort_by(|a, b| {
+            a.min()
+                .x
+                .partial_cmp(&b.min().x)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then(
+                    a.min()
+                        .y
+                        .partial_cmp(&b.min().y)
+                        .unwrap_or(std::cmp::Ordering::Equal),
+                )
+        });
+
+        assert_eq!(grid_sorted.cells.len(), expected_grid_sorted.len());
+        assert_eq!(grid_sorted.cells, expected_grid_sorted);
+    }
+
+    #[test]
+    fn test_expand_xy_asymmetric() {
+        let expanded = r(0.0, 0.0, 10.0, 4.0).expand_xy(5.0, 1.0);
+        assert_eq!(expanded, r(-5.0, -1.0, 15.0, 5.0));
+        assert_eq!(r(0.0, 0.0, 10.0, 4.0).expand(2.0), r(0.0, 0.0, 10.0, 4.0).expand_xy(2.0, 2.0));
+    }
+
+    #[test]
+    fn test_expand_xy_shrink_does_not_invert() {
+        let rect = r(0.0, 0.0, 10.0, 4.0);
+        assert_eq!(rect.expand_xy(-1.0, -1.0), r(1.0, 1.0, 9.0, 3.0));
+        // Shrinking by more than half the height collapses y onto the center
+        assert_eq!(rect.expand_xy(-1.0, -3.0), r(1.0, 2.0, 9.0, 2.0));
+        assert_eq!(rect.expand_xy(-20.0, -20.0), r(5.0, 2.0, 5.0, 2.0));
+    }
+
+    #[test]
+    fn test_extend_exact_multiple_adds_no_padding() {
+        assert_eq!(r(0.0, 0.0, 20.0, 10.0).extend(10.0), r(0.0, 0.0, 20.0, 10.0));
+        assert_eq!(r(0.0, 0.0, 1.1, 0.1).extend(0.1), r(0.0, 0.0, 1.1, 0.1));
+    }
+
+    #[test]
+    fn test_extend_zero_width() {
+        assert_eq!(r(5.0, 0.0, 5.0, 10.0).extend(10.0), r(5.0, 0.0, 5.0, 10.0));
+    }
+
+    #[test]
+    fn test_extend_smaller_than_cell() {
+        // Padded evenly on both sides to one full cell
+        assert_eq!(r(0.0, 0.0, 4.0, 12.0).extend(10.0), r(-3.0, -4.0, 7.0, 16.0));
+    }
+
+    #[test]
+    fn test_rect_in_bounding_box() {
+        let bbox = [0.0, 0.0, 10.0, 10.0];
+        assert!(r(2.0, 2.0, 4.0, 4.0).in_bounding_box(&bbox));
+        assert!(r(2.0, 2.0, 4.0, 4.0).intersects_bounding_box(&bbox));
+
+        // Partially overlapping
+        assert!(!r(8.0, 8.0, 12.0, 12.0).in_bounding_box(&bbox));
+        assert!(r(8.0, 8.0, 12.0, 12.0).intersects_bounding_box(&bbox));
+
+        assert!(!r(20.0, 20.0, 30.0, 30.0).in_bounding_box(&bbox));
+        assert!(!r(20.0, 20.0, 30.0, 30.0).intersects_bounding_box(&bbox));
+    }
+
+    #[test]
+    fn test_in_bounding_box_checked() {
+        assert!(in_bounding_box_checked(&[10.0, 50.0], &GERMANY_BBOX).unwrap());
+        assert!(!in_bounding_box_checked(&[0.0, 0.0], &GERMANY_BBOX).unwrap());
+        // The elevation is ignored
+        assert!(in_bounding_box_checked(&[10.0, 50.0, 300.0], &GERMANY_BBOX).unwrap());
+        assert!(is_coordinate_in_germany(&[10.0, 50.0, 300.0]));
+
+        assert!(matches!(
+            in_bounding_box_checked(&[10.0], &GERMANY_BBOX),
+            Err(Error::InvalidCoordinates)
+        ));
+        assert!(matches!(
+            in_bounding_box_checked(&[], &GERMANY_BBOX),
+            Err(Error::InvalidCoordinates)
+        ));
+        assert!(!is_coordinate_in_germany(&[10.0]));
+    }
+
+    #[test]
+    fn test_expand_degrees() {
+        let point = r(9.0, 50.0, 9.0, 50.0);
+        let expanded = point.expand_degrees(1000.0, 50.0);
+
+        let (dx, dy) = (expanded.max().x - 9.0, expanded.max().y - 50.0);
+        assert!((dy - 1000.0 / METERS_PER_DEGREE).abs() < 1e-12);
+        // A degree of longitude is shorter at 50°, so it takes more of them
+        assert!(dx > dy);
+        assert!((dx * 50f64.to_radians().cos() - dy).abs() < 1e-12);
+        assert_eq!(expanded.min(), Coord { x: 9.0 - dx, y: 50.0 - dy });
+
+        assert!((point.expand_degrees(1000.0, 0.0).width() - expanded.height()).abs() < 1e-12);
+        assert_eq!(expanded.expand_degrees(-5000.0, 50.0), point);
+        assert!(point.expand_degrees(1000.0, 90.0).width().is_finite());
+    }
+
+    #[test]
+    fn test_polygon_in_and_intersects_germany_bbox() {
+        let inside = r(9.0, 50.0, 10.0, 51.0).to_polygon();
+        // Crosses the eastern border
+        let partial = r(14.0, 50.0, 16.0, 51.0).to_polygon();
+        let outside = r(20.0, 50.0, 21.0, 51.0).to_polygon();
+
+        assert!(inside.in_bounding_box(&GERMANY_BBOX));
+        assert!(!partial.in_bounding_box(&GERMANY_BBOX));
+        assert!(!outside.in_bounding_box(&GERMANY_BBOX));
+
+        assert!(inside.intersects_bounding_box(&GERMANY_BBOX));
+        assert!(partial.intersects_bounding_box(&GERMANY_BBOX));
+        assert!(!outside.intersects_bounding_box(&GERMANY_BBOX));
+
+        let multi = MultiPolygon::new(vec![inside.clone(), outside.clone()]);
+        assert!(!multi.in_bounding_box(&GERMANY_BBOX));
+        assert!(multi.intersects_bounding_box(&GERMANY_BBOX));
+        assert!(!MultiPolygon::new(vec![outside.clone()]).intersects_bounding_box(&GERMANY_BBOX));
+    }
+
+    #[test]
+    fn test_geometry_in_and_intersects_bounding_box() {
+        let bbox = [0.0, 0.0, 10.0, 10.0];
+        let partial = Geometry::LineString(LineString::from(vec![(5.0, 5.0), (15.0, 5.0)]));
+        assert!(!partial.in_bounding_box(&bbox));
+        assert!(partial.intersects_bounding_box(&bbox));
+
+        let inside = Geometry::Point(Point::new(1.0, 1.0));
+        let outside = Geometry::Rect(r(20.0, 20.0, 30.0, 30.0));
+        let collection = Geometry::GeometryCollection(geo::GeometryCollection::new_from(vec![
+            inside.clone(),
+            outside.clone(),
+        ]));
+        assert!(inside.in_bounding_box(&bbox) && inside.intersects_bounding_box(&bbox));
+        assert!(!outside.in_bounding_box(&bbox) && !outside.intersects_bounding_box(&bbox));
+        assert!(!collection.in_bounding_box(&bbox));
+        assert!(collection.intersects_bounding_box(&bbox));
+        let empty = Geometry::GeometryCollection(geo::GeometryCollection::new_from(vec![]));
+        assert!(!empty.in_bounding_box(&bbox) && !empty.intersects_bounding_box(&bbox));
+
+        let geo_geometry = GeoGeometry::LineString(LineString::from(vec![(5.0, 5.0), (15.0, 5.0)]));
+        assert!(!geo_geometry.in_bounding_box(&bbox));
+        assert!(geo_geometry.intersects_bounding_box(&bbox));
+    }
+
+    #[test]
+    fn test_is_coordinate_in_germany_precise() {
+        // Basel, Strasbourg, Luxembourg and Szczecin lie within the bbox only
+        for outside in [[7.588, 47.558], [7.75, 48.58], [6.13, 49.61], [14.55, 53.43]] {
+            assert!(is_coordinate_in_germany(&outside));
+            assert!(!is_coordinate_in_germany_precise(&outside), "{:?}", outside);
+        }
+        // Berlin, Munich, Hamburg, Cologne, Freiburg, Dresden, Rostock, Flensburg
+        for inside in [
+            [13.40, 52.52],
+            [11.58, 48.14],
+            [9.99, 53.55],
+            [6.96, 50.94],
+            [7.85, 48.00],
+            [13.74, 51.05],
+            [12.10, 54.09],
+            [9.43, 54.78],
+        ] {
+            assert!(is_coordinate_in_germany_precise(&inside), "{:?}", inside);
+        }
+        assert!(!is_coordinate_in_germany_precise(&[20.0, 50.0]));
+        assert!(!is_coordinate_in_germany_precise(&[10.0]));
+        assert!(germany_boundary().exterior().is_closed());
+    }
+
+    #[test]
+    fn test_geometry_in_polygon() {
+        let polygon = r(0.0, 0.0, 10.0, 10.0).to_polygon();
+        let inside = GeoGeometry::LineString(LineString::from(vec![(1.0, 1.0), (9.0, 9.0)]));
+        let partial = GeoGeometry::LineString(LineString::from(vec![(1.0, 1.0), (11.0, 9.0)]));
+        assert!(geometry_in_polygon(&inside, &polygon));
+        assert!(!geometry_in_polygon(&partial, &polygon));
+        assert!(!geometry_in_polygon(&GeoGeometry::Point(Point::new(0.0, 5.0)), &polygon));
+    }
+
+    #[test]
+    fn test_create_hex_grid() {
+        use geo::Area;
+
+        let bbox = r(0.0, 0.0, 10.0, 8.0);
+        let hex_size = 1.0;
+        let cells = create_hex_grid(bbox, hex_size);
+
+        assert!(!cells.is_empty());
+        let expected_area = 1.5 * 3f64.sqrt() * hex_size * hex_size;
+        for cell in &cells {
+            // Six vertices, closed
+            assert_eq!(cell.exterior().0.len(), 7);
+            assert!(cell.exterior().is_closed());
+            assert!((cell.unsigned_area() - expected_area).abs() < 1e-9);
+            assert!(cell.intersects(&bbox));
+        }
+
+        // Sampled interior points all lie in some hexagon
+        for i in 0..40 {
+            for j in 0..32 {
+                let point = Point::new(0.01 + i as f64 * 0.25, 0.02 + j as f64 * 0.25);
+                assert!(
+                    cells.iter().any(|cell| cell.intersects(&point)),
+                    "gap at {:?}",
+                    point
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_create_hex_grid_guards() {
+        assert!(create_hex_grid(r(0.0, 0.0, 10.0, 10.0), 0.0).is_empty());
+        assert!(create_hex_grid(r(0.0, 0.0, 10.0, 10.0), -1.0).is_empty());
+        assert!(create_hex_grid(r(0.0, 0.0, 0.0, 10.0), 1.0).is_empty());
+        assert!(create_hex_grid(r(0.0, 0.0, 10.0, 0.0), 1.0).is_empty());
+        // A bbox smaller than a cell is covered by the cells around its corner
+        let tiny = create_hex_grid(r(0.0, 0.0, 0.1, 0.1), 1.0);
+        assert!(!tiny.is_empty());
+        assert!(tiny.iter().any(|cell| cell.contains(&Point::new(0.05, 0.05))));
+    }
+
+    #[test]
+    fn test_coord_z() {
+        assert_eq!(coord_z(&[1.0, 2.0, 3.0]), Some(3.0));
+        assert_eq!(coord_z(&[1.0, 2.0]), None);
+        assert_eq!(coord_z(&[]), None);
+    }
+
+    #[test]
+    fn test_restore_z() {
+        let original = geojson::Geometry::new(Value::Polygon(vec![vec![
+            vec![0.0, 0.0, 10.0],
+            vec![1.0, 0.0],
+            vec![1.0, 1.0, 12.0, 99.0],
+            vec![0.0, 0.0, 10.0],
+        ]]));
+        let mut transformed = geojson::Geometry::new(Value::Polygon(vec![vec![
+            vec![5.0, 5.0],
+            vec![6.0, 5.0],
+            vec![6.0, 6.0],
+            vec![5.0, 5.0],
+        ]]));
+
+        restore_z(&original, &mut transformed, |z| z * 2.0);
+
+        assert_eq!(
+            transformed.value,
+            Value::Polygon(vec![vec![
+                vec![5.0, 5.0, 20.0],
+                vec![6.0, 5.0],
+                vec![6.0, 6.0, 24.0],
+                vec![5.0, 5.0, 20.0],
+            ]])
+        );
+
+        // Geometries of another type are left alone
+        let mut point = geojson::Geometry::new(Value::Point(vec![1.0, 2.0]));
+        restore_z(&original, &mut point, |z| z);
+        assert_eq!(point.value, Value::Point(vec![1.0, 2.0]));
+    }
+
+    #[test]
+    fn test_union_bboxes() {
+        let bboxes = [
+            vec![0.0, 1.0, 2.0, 3.0],
+            vec![-1.0, 2.0, 1.0, 5.0],
+            // A 3D bbox is skipped in a 2D union
+            vec![-9.0, -9.0, -9.0, 9.0, 9.0, 9.0],
+        ];
+
+        assert_eq!(union_bboxes(&bboxes, 2), Some(vec![-1.0, 1.0, 2.0, 5.0]));
+        assert_eq!(union_bboxes(&bboxes, 3), Some(bboxes[2].clone()));
+        assert_eq!(union_bboxes(&bboxes[..0], 2), None);
+    }
+}
@@ -1,3 +1,4 @@
 pub(crate) mod geometry;
-pub(crate) mod utils;
+pub(crate) mod helpers;
 pub(crate) mod error;
+pub(crate) mod transformer;
\ No newline at end of file
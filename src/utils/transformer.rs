@@ -0,0 +1,74 @@
+use proj::{Proj, ProjCreateError};
+
+use crate::geometry::crs::{DEFAULT_CRS, normalize_crs_name};
+
+/// Forward and reverse PROJ transformers between a source and a metric CRS.
+///
+/// Creating the PROJ objects costs far more than transforming the coordinates
+/// of a small tile, so callers processing many tiles create one transformer
+/// and pass it to every call by reference.
+pub struct Transformer {
+    from_crs: String,
+    to_crs: String,
+    forward: Proj,
+    reverse: Proj,
+    geographic: Option<Proj>,
+}
+
+impl Transformer {
+    /// Creates the transformers from `from_crs` to `to_crs` and back.
+    ///
+    /// # Errors
+    /// Returns the PROJ error if either CRS is unknown.
+    pub fn new(from_crs: &str, to_crs: &str) -> Result<Self, ProjCreateError> {
+        Ok(Self {
+            from_crs: from_crs.to_string(),
+            to_crs: to_crs.to_string(),
+            forward: Proj::new_known_crs(from_crs, to_crs, None)?,
+            reverse: Proj::new_known_crs(to_crs, from_crs, None)?,
+            geographic: geographic_proj(from_crs)?,
+        })
+    }
+
+    /// The CRS coordinates are transformed from.
+    pub fn source_crs(&self) -> &str {
+        &self.from_crs
+    }
+
+    /// The metric CRS coordinates are transformed to.
+    pub fn metric_crs(&self) -> &str {
+        &self.to_crs
+    }
+
+    /// Whether this transforms between `from_crs` and `to_crs`.
+    pub fn is_for(&self, from_crs: &str, to_crs: &str) -> bool {
+        self.from_crs == from_crs && self.to_crs == to_crs
+    }
+
+    /// The transformer into the metric CRS.
+    pub fn forward(&self) -> &Proj {
+        &self.forward
+    }
+
+    /// The transformer back into the source CRS.
+    pub fn reverse(&self) -> &Proj {
+        &self.reverse
+    }
+
+    /// The transformer into EPSG:4326, in which geographic filters are
+    /// checked, or None if the source CRS already is EPSG:4326.
+    pub fn geographic(&self) -> Option<&Proj> {
+        self.geographic.as_ref()
+    }
+}
+
+/// Creates the transformer from `from_crs` into EPSG:4326.
+///
+/// # Returns
+/// None if `from_crs` already is EPSG:4326, or the PROJ error if it is unknown.
+pub(crate) fn geographic_proj(from_crs: &str) -> Result<Option<Proj>, ProjCreateError> {
+    if normalize_crs_name(from_crs) == DEFAULT_CRS {
+        return Ok(None);
+    }
+    Proj::new_known_crs(from_crs, DEFAULT_CRS, None).map(Some)
+}